@@ -62,6 +62,7 @@ function setup() {
     mkdir -p ${data_directory}/manifests
 	mkdir -p ${data_directory}/catalogs
 	mkdir -p ${data_directory}/catalog-schema
+	mkdir -p ${data_directory}/source
 }
 
 function cleanup() {
@@ -73,6 +74,7 @@ function cleanup() {
     rm -rf ${data_directory}/manifests/*
 	rm -rf ${data_directory}/catalogs/*
 	rm -rf ${data_directory}/catalog-schema/*
+	rm -rf ${data_directory}/source/*
 }
 
 # Entrypoint of data volume setup script
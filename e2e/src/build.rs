@@ -3,11 +3,11 @@
 mod tests {
 
     use crate::utils::{
-        build, build_api_client, create_namespace, create_project, delete_build_cache,
-        get_build_metadata, list_manifest_metadata, list_namespace, list_project, push_manifest,
-        scan_build_cache_metadata, wait,
+        build, build_api_client, build_with_options, create_namespace, create_project,
+        delete_build_cache, get_build_metadata, list_manifest_metadata, list_namespace,
+        list_project, pull_source, push_manifest, scan_build_cache_metadata, wait,
     };
-    use pipebuilder_common::{read_file, BuildStatus};
+    use pipebuilder_common::{read_file, BuildOptions, BuildStatus};
 
     const TEST_CLUSTER_READY_MILLIS: u64 = 30000;
     const TEST_NAMESPACE: &str = "dev";
@@ -80,6 +80,8 @@ mod tests {
                 String::from(TEST_PROJECT),
                 0,
                 None,
+                None,
+                None,
             )
             .await
             .unwrap();
@@ -137,5 +139,48 @@ mod tests {
             .await
             .unwrap();
         assert!(build_caches.is_empty());
+
+        // failed build still publish its source
+        let options = BuildOptions {
+            features: vec![String::from("not-exist")],
+            ..Default::default()
+        };
+        let build_response = build_with_options(
+            &client,
+            String::from(TEST_NAMESPACE),
+            String::from(TEST_PROJECT),
+            0,
+            options,
+        )
+        .await
+        .unwrap();
+        let build_version = build_response.build_version;
+        let mut status = BuildStatus::Pending;
+        for _ in 0..TEST_BUILD_WAIT_RETRY {
+            wait(TEST_BUILD_WAIT_MILLIS).await;
+            let build_metadata = get_build_metadata(
+                &client,
+                String::from(TEST_NAMESPACE),
+                String::from(TEST_PROJECT),
+                build_version,
+            )
+            .await
+            .unwrap();
+            status = build_metadata.status;
+            if matches!(status, BuildStatus::Fail | BuildStatus::Succeed) {
+                break;
+            }
+        }
+        assert!(matches!(status, BuildStatus::Fail));
+        let sources = pull_source(
+            &client,
+            String::from(TEST_NAMESPACE),
+            String::from(TEST_PROJECT),
+            build_version,
+        )
+        .await
+        .unwrap();
+        assert!(sources.iter().any(|source| source.path == "Cargo.toml"));
+        assert!(sources.iter().any(|source| source.path == "src/main.rs"));
     }
 }
@@ -1,11 +1,11 @@
-use std::path::Path;
+use std::{collections::HashMap, path::Path};
 
 use pipebuilder_common::{
     api::{
         client::{ApiClient, ApiClientConfig},
         models,
     },
-    open_file, parse_config, NodeRole, Result,
+    open_file, parse_config, BuildOptions, NodeRole, Result, Source,
 };
 
 use tokio::time::{sleep, Duration};
@@ -89,7 +89,11 @@ pub async fn create_project(
     namespace: String,
     id: String,
 ) -> Result<models::Project> {
-    let request = models::UpdateProjectRequest { namespace, id };
+    let request = models::UpdateProjectRequest {
+        namespace,
+        id,
+        selector: None,
+    };
     let project = client.update_project(&request).await?;
     Ok(project)
 }
@@ -209,12 +213,41 @@ pub async fn build(
     id: String,
     manifest_version: u64,
     target_platform: Option<String>,
+    step_timeout: Option<u64>,
+    build_timeout: Option<u64>,
 ) -> Result<models::BuildResponse> {
     let request = models::BuildRequest {
         namespace,
         id,
         manifest_version,
         target_platform,
+        step_timeout,
+        build_timeout,
+        options: None,
+        // always rebuild, e2e build test exercise build cache
+        force: true,
+        selector: HashMap::new(),
+    };
+    client.build(&request).await
+}
+
+pub async fn build_with_options(
+    client: &ApiClient,
+    namespace: String,
+    id: String,
+    manifest_version: u64,
+    options: BuildOptions,
+) -> Result<models::BuildResponse> {
+    let request = models::BuildRequest {
+        namespace,
+        id,
+        manifest_version,
+        target_platform: None,
+        step_timeout: None,
+        build_timeout: None,
+        options: Some(options),
+        force: true,
+        selector: HashMap::new(),
     };
     client.build(&request).await
 }
@@ -233,6 +266,21 @@ pub async fn get_build_metadata(
     client.get_build_metadata(&request).await
 }
 
+pub async fn pull_source(
+    client: &ApiClient,
+    namespace: String,
+    id: String,
+    build_version: u64,
+) -> Result<Vec<Source>> {
+    let request = models::GetSourceRequest {
+        namespace,
+        id,
+        build_version,
+    };
+    let response = client.pull_source(&request).await?;
+    Source::from_buffer(response.buffer.as_slice())
+}
+
 pub async fn scan_build_cache_metadata(
     client: &ApiClient,
     builder_id: String,
@@ -1,10 +1,10 @@
 pub mod filters {
-    use crate::{admin, app, build, catalog_schema, catalogs, manifest, namespace, node, project};
+    use crate::{
+        admin, app, build, catalog_schema, catalogs, dependency_policy, lint_policy, manifest,
+        namespace, node, project, source,
+    };
     use pipebuilder_common::{
-        grpc::{
-            repository::repository_client::RepositoryClient,
-            schedule::scheduler_client::SchedulerClient,
-        },
+        grpc::{client::SchedulerLeaderClient, repository::repository_client::RepositoryClient},
         NodeService, Register,
     };
     use tonic::transport::Channel;
@@ -12,7 +12,7 @@ pub mod filters {
 
     pub fn api(
         repository_client: RepositoryClient<Channel>,
-        scheduler_client: SchedulerClient<Channel>,
+        scheduler_client: SchedulerLeaderClient,
         register: Register,
         lease_id: i64,
         node_svc: NodeService,
@@ -20,6 +20,7 @@ pub mod filters {
         app::filters::v1_app(repository_client.clone(), register.clone())
             .or(build::filters::v1_build(
                 scheduler_client,
+                repository_client.clone(),
                 register.clone(),
                 lease_id,
             ))
@@ -35,12 +36,27 @@ pub mod filters {
             ))
             .boxed()
             .or(catalog_schema::filters::v1_catalog_schema(
+                repository_client.clone(),
+                register.clone(),
+            ))
+            .boxed()
+            .or(source::filters::v1_source(
                 repository_client,
                 register.clone(),
             ))
             .boxed()
             .or(namespace::filters::v1_namespace(register.clone(), lease_id))
             .boxed()
+            .or(dependency_policy::filters::v1_dependency_policy(
+                register.clone(),
+                lease_id,
+            ))
+            .boxed()
+            .or(lint_policy::filters::v1_lint_policy(
+                register.clone(),
+                lease_id,
+            ))
+            .boxed()
             .or(node::filters::v1_node(register.clone(), lease_id))
             .boxed()
             .or(project::filters::v1_project(register, lease_id))
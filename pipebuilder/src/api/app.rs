@@ -153,6 +153,8 @@ mod handlers {
                     pulls: meta.pulls,
                     size: meta.size,
                     created: meta.created,
+                    digest: meta.digest,
+                    compression: meta.compression,
                 }
             })
             .collect::<Vec<models::AppMetadata>>();
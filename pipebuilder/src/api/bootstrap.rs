@@ -1,6 +1,6 @@
 use crate::{api, config::ApiConfig};
 use pipebuilder_common::{
-    grpc::client::{RepositoryClientBuilder, SchedulerClientBuilder},
+    grpc::client::{RepositoryClientBuilder, SchedulerLeaderClient},
     NodeService, Register, Result,
 };
 use tracing::info;
@@ -27,19 +27,8 @@ pub async fn bootstrap(
         .address(address.as_str())
         .connect()
         .await?;
-    // connect scheduler service
-    let endpoint = clients.scheduler.endpoint();
-    let protocol = clients.scheduler.protocol;
-    let address = clients.scheduler.address;
-    info!(
-        endpoint = endpoint.as_str(),
-        "connect scheduler service ..."
-    );
-    let scheduler_client = SchedulerClientBuilder::default()
-        .protocol(protocol)
-        .address(address.as_str())
-        .connect()
-        .await?;
+    // scheduler service connected on demand, to current elected leader
+    let scheduler_client = SchedulerLeaderClient::new(clients.scheduler.protocol, register.clone());
     let api = api::filters::api(
         repository_client,
         scheduler_client,
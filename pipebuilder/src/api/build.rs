@@ -2,42 +2,111 @@ pub mod filters {
     use super::handlers;
     use crate::utils;
     use pipebuilder_common::{
-        api::models, grpc::schedule::scheduler_client::SchedulerClient, Register,
+        api::models,
+        grpc::{client::SchedulerLeaderClient, repository::repository_client::RepositoryClient},
+        Register,
     };
     use tonic::transport::Channel;
     use warp::Filter;
 
     // build api
     pub fn v1_build(
-        scheduler_client: SchedulerClient<Channel>,
+        scheduler_client: SchedulerLeaderClient,
+        repository_client: RepositoryClient<Channel>,
         register: Register,
         lease_id: i64,
     ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
-        v1_build_post(scheduler_client, register.clone())
+        v1_build_post(scheduler_client.clone(), register.clone(), lease_id)
+            .or(v1_build_matrix_post(
+                scheduler_client,
+                register.clone(),
+                lease_id,
+            ))
+            .or(v1_build_matrix_get(register.clone(), lease_id))
+            .or(v1_build_matrix_delete(register.clone()))
+            .or(v1_build_matrix_snapshot_delete(register.clone()))
             .or(v1_build_snapshot_list(register.clone()))
             .or(v1_build_snapshot_delete(register.clone()))
             .or(v1_build_metadata_get(register.clone(), lease_id))
             .or(v1_build_metadata_list(register.clone()))
+            .or(v1_build_pending_list(register.clone()))
             .or(v1_build_cancel(register.clone(), lease_id))
             .or(v1_build_log_get(register.clone(), lease_id))
-            .or(v1_build_delete(register.clone(), lease_id))
+            .or(v1_build_log_stream(register.clone(), lease_id))
+            .or(v1_build_delete(
+                repository_client,
+                register.clone(),
+                lease_id,
+            ))
             .or(v1_build_scan(register.clone(), lease_id))
             .or(v1_build_cache_scan(register.clone(), lease_id))
             .or(v1_build_cache_delete(register, lease_id))
     }
 
     pub fn v1_build_post(
-        scheduler_client: SchedulerClient<Channel>,
+        scheduler_client: SchedulerLeaderClient,
         register: Register,
+        lease_id: i64,
     ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
         warp::path!("api" / "v1" / "build")
             .and(warp::post())
             .and(utils::filters::with_scheduler_client(scheduler_client))
             .and(utils::filters::with_register(register))
+            .and(utils::filters::with_lease_id(lease_id))
             .and(utils::filters::json_request::<models::BuildRequest>())
             .and_then(handlers::build)
     }
 
+    pub fn v1_build_matrix_post(
+        scheduler_client: SchedulerLeaderClient,
+        register: Register,
+        lease_id: i64,
+    ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+        warp::path!("api" / "v1" / "build" / "matrix")
+            .and(warp::post())
+            .and(utils::filters::with_scheduler_client(scheduler_client))
+            .and(utils::filters::with_register(register))
+            .and(utils::filters::with_lease_id(lease_id))
+            .and(utils::filters::json_request::<models::BuildMatrixRequest>())
+            .and_then(handlers::build_matrix)
+    }
+
+    pub fn v1_build_matrix_get(
+        register: Register,
+        lease_id: i64,
+    ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+        warp::path!("api" / "v1" / "build" / "matrix")
+            .and(warp::get())
+            .and(utils::filters::with_register(register))
+            .and(utils::filters::with_lease_id(lease_id))
+            .and(warp::query::<models::GetBuildMatrixRequest>())
+            .and_then(handlers::get_build_matrix)
+    }
+
+    pub fn v1_build_matrix_delete(
+        register: Register,
+    ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+        warp::path!("api" / "v1" / "build" / "matrix")
+            .and(warp::delete())
+            .and(utils::filters::with_register(register))
+            .and(utils::filters::json_request::<
+                models::DeleteBuildMatrixRequest,
+            >())
+            .and_then(handlers::delete_build_matrix)
+    }
+
+    pub fn v1_build_matrix_snapshot_delete(
+        register: Register,
+    ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+        warp::path!("api" / "v1" / "build" / "matrix" / "snapshot")
+            .and(warp::delete())
+            .and(utils::filters::with_register(register))
+            .and(utils::filters::json_request::<
+                models::DeleteBuildMatrixSnapshotRequest,
+            >())
+            .and_then(handlers::delete_build_matrix_snapshot)
+    }
+
     pub fn v1_build_snapshot_list(
         register: Register,
     ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
@@ -82,6 +151,16 @@ pub mod filters {
             .and_then(handlers::list_build_metadata)
     }
 
+    pub fn v1_build_pending_list(
+        register: Register,
+    ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+        warp::path!("api" / "v1" / "build" / "pending")
+            .and(warp::get())
+            .and(utils::filters::with_register(register))
+            .and(warp::query::<models::ListBuildRequest>())
+            .and_then(handlers::list_pending_build)
+    }
+
     pub fn v1_build_cancel(
         register: Register,
         lease_id: i64,
@@ -95,11 +174,13 @@ pub mod filters {
     }
 
     pub fn v1_build_delete(
+        repository_client: RepositoryClient<Channel>,
         register: Register,
         lease_id: i64,
     ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
         warp::path!("api" / "v1" / "build")
             .and(warp::delete())
+            .and(utils::filters::with_repository_client(repository_client))
             .and(utils::filters::with_register(register))
             .and(utils::filters::with_lease_id(lease_id))
             .and(utils::filters::json_request::<models::DeleteBuildRequest>())
@@ -118,6 +199,18 @@ pub mod filters {
             .and_then(handlers::get_build_log)
     }
 
+    pub fn v1_build_log_stream(
+        register: Register,
+        lease_id: i64,
+    ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+        warp::path!("api" / "v1" / "build" / "log" / "stream")
+            .and(warp::get())
+            .and(utils::filters::with_register(register))
+            .and(utils::filters::with_lease_id(lease_id))
+            .and(warp::query::<models::GetBuildLogRequest>())
+            .and_then(handlers::stream_build_log)
+    }
+
     pub fn v1_build_scan(
         register: Register,
         lease_id: i64,
@@ -159,62 +252,120 @@ pub mod filters {
 
 mod handlers {
     use crate::{utils, validations};
+    use chrono::Utc;
+    use futures_util::{Stream, StreamExt};
     use pipebuilder_common::{
         api::models::{self, Failure},
+        get_build_lint,
         grpc::{
             build::{
                 builder_client::BuilderClient, BuildRequest, CancelBuildRequest,
                 DeleteBuildCacheRequest, GetBuildLogRequest, ScanBuildCacheRequest,
-                ScanBuildRequest,
+                ScanBuildRequest, StreamBuildLogRequest, StreamBuildLogResponse,
+            },
+            client::SchedulerLeaderClient,
+            repository::{repository_client::RepositoryClient, DeleteSourceRequest},
+            schedule::{
+                scheduler_client::SchedulerClient, ReleaseRequest, ScheduleRequest,
+                ScheduleResponse,
             },
-            schedule::{scheduler_client::SchedulerClient, ScheduleRequest, ScheduleResponse},
         },
-        remove_resource_namespace, BuildMetadata, BuildSnapshot, NodeRole, Register,
+        remove_resource_namespace, AppMetadata, BuildMatrixEntry, BuildMatrixMetadata,
+        BuildMatrixSnapshot, BuildMatrixStatus, BuildMetadata, BuildPolicies, BuildSnapshot,
+        BuildStatus, BuildStep, BuildStepOutcome, CatalogsSnapshot, DependencyPolicy, NodeRole,
+        PendingBuild, Project, Register, Snapshot,
     };
-    use std::convert::Infallible;
+    use std::{collections::HashMap, convert::Infallible};
     use tonic::transport::Channel;
-    use tracing::info;
+    use tracing::{info, warn};
+    use warp::{http::StatusCode, Reply};
 
     pub async fn build(
-        mut client: SchedulerClient<Channel>,
+        client: SchedulerLeaderClient,
         mut register: Register,
-        mut request: models::BuildRequest,
+        lease_id: i64,
+        request: models::BuildRequest,
     ) -> Result<impl warp::Reply, Infallible> {
         // validate build request
         match validations::validate_build_request(&mut register, &request).await {
             Ok(_) => (),
             Err(err) => return Ok(utils::handlers::http_bad_request(err.into())),
         };
+        let mut client = match client.connect().await {
+            Ok(client) => client,
+            Err(err) => {
+                return Ok(utils::handlers::failure(
+                    StatusCode::SERVICE_UNAVAILABLE,
+                    err.into(),
+                ))
+            }
+        };
+        match trigger_build(&mut client, &mut register, lease_id, request).await {
+            Ok(response) => Ok(utils::handlers::ok(&response)),
+            Err((status_code, failure)) => Ok(utils::handlers::failure(status_code, failure)),
+        }
+    }
+
+    // schedule a builder and trigger the build, return status code and failure if fail
+    async fn trigger_build(
+        client: &mut SchedulerClient<Channel>,
+        register: &mut Register,
+        lease_id: i64,
+        mut request: models::BuildRequest,
+    ) -> Result<models::BuildResponse, (StatusCode, Failure)> {
+        let selector = match resolve_selector(register, lease_id, &request).await {
+            Ok(selector) => selector,
+            Err(err) => return Err((StatusCode::INTERNAL_SERVER_ERROR, err.into())),
+        };
+        // builder records resolved selector as build input
+        request.selector = selector.clone();
+        if let Some(response) = reuse_build(register, lease_id, &request).await? {
+            return Ok(response);
+        }
         let namespace = request.namespace.clone();
         let id = request.id.clone();
         let target_platform = request.target_platform.clone();
         // find a builder
-        let response = match schedule(&mut client, namespace, id, target_platform.clone()).await {
+        let response = match schedule(
+            client,
+            namespace,
+            id,
+            target_platform.clone(),
+            selector.clone(),
+        )
+        .await
+        {
             Ok(response) => response,
-            Err(err) => return Ok(utils::handlers::http_internal_error(err.into())),
+            Err(err) => return Err((StatusCode::INTERNAL_SERVER_ERROR, err.into())),
         };
         let builder_info = match response.builder_info {
             Some(builder_info) => builder_info,
-            None => {
-                return Ok(utils::handlers::http_service_unavailable(Failure::new(
-                    String::from("builder unavailable"),
-                )))
-            }
+            None => return enqueue_build(register, lease_id, request, selector).await,
         };
-        // target platform validation
+        // target platform validation, never trust scheduler of another version
         let builder_target_platform = builder_info.target_platform;
         match target_platform {
             Some(target_platform) => {
                 if target_platform != builder_target_platform {
-                    return Ok(utils::handlers::http_service_unavailable(Failure::new(
-                        format!(
+                    // scheduled builder never receive the build
+                    release(client, builder_info.id).await;
+                    return Err((
+                        StatusCode::SERVICE_UNAVAILABLE,
+                        Failure::new(format!(
                             "builder target platform miss match '{}' != '{}'",
                             builder_target_platform, target_platform
-                        ),
-                    )));
+                        )),
+                    ));
+                }
+            }
+            None => {
+                request.set_target_platform(builder_target_platform);
+                if let Some(response) = reuse_build(register, lease_id, &request).await? {
+                    // scheduled builder never receive the build
+                    release(client, builder_info.id).await;
+                    return Ok(response);
                 }
             }
-            None => request.set_target_platform(builder_target_platform),
         };
         let builder_id = builder_info.id;
         let builder_address = builder_info.address;
@@ -226,45 +377,402 @@ mod handlers {
         // check whether builder is active
         let mut node_client = match utils::handlers::node_client(builder_address.as_str()).await {
             Ok(node_client) => node_client,
-            Err(err) => return Ok(utils::handlers::http_internal_error(err.into())),
+            Err(err) => return Err((StatusCode::INTERNAL_SERVER_ERROR, err.into())),
         };
         let active = match utils::handlers::is_node_status_active(&mut node_client).await {
             Ok(active) => active,
-            Err(err) => return Ok(utils::handlers::http_internal_error(err.into())),
+            Err(err) => return Err((StatusCode::INTERNAL_SERVER_ERROR, err.into())),
         };
         if !active {
-            return Ok(utils::handlers::http_service_unavailable(Failure::new(
-                format!("builder '{}' is inactive", builder_id),
-            )));
+            info!(builder_id = builder_id.as_str(), "builder inactive");
+            return enqueue_build(register, lease_id, request, selector).await;
         }
         // trigger the build
         let mut builder_client =
             match utils::handlers::builder_client(builder_address.as_str()).await {
                 Ok(builder_client) => builder_client,
-                Err(err) => return Ok(utils::handlers::http_internal_error(err.into())),
+                Err(err) => return Err((StatusCode::INTERNAL_SERVER_ERROR, err.into())),
             };
         match do_build(&mut builder_client, request).await {
+            Ok(response) => Ok(response),
+            Err(err) => Err((StatusCode::INTERNAL_SERVER_ERROR, err.into())),
+        }
+    }
+
+    // project selector overridden by request selector per key
+    async fn resolve_selector(
+        register: &mut Register,
+        lease_id: i64,
+        request: &models::BuildRequest,
+    ) -> pipebuilder_common::Result<HashMap<String, String>> {
+        let mut selector = register
+            .get_resource::<Project>(
+                Some(request.namespace.as_str()),
+                request.id.as_str(),
+                None,
+                lease_id,
+            )
+            .await?
+            .map(|project| project.selector)
+            .unwrap_or_default();
+        selector.extend(request.selector.clone());
+        Ok(selector)
+    }
+
+    // queue build until a builder available, dispatched by scheduler
+    async fn enqueue_build(
+        register: &mut Register,
+        lease_id: i64,
+        request: models::BuildRequest,
+        selector: HashMap<String, String>,
+    ) -> Result<models::BuildResponse, (StatusCode, Failure)> {
+        match do_enqueue_build(register, lease_id, request, selector).await {
+            Ok(response) => Ok(response),
+            Err(err) => Err((StatusCode::INTERNAL_SERVER_ERROR, err.into())),
+        }
+    }
+
+    async fn do_enqueue_build(
+        register: &mut Register,
+        lease_id: i64,
+        request: models::BuildRequest,
+        selector: HashMap<String, String>,
+    ) -> pipebuilder_common::Result<models::BuildResponse> {
+        let namespace = request.namespace.as_str();
+        let id = request.id.as_str();
+        // allocate build version so that pending build is tracked as usual
+        let (_, snapshot) = register
+            .update_snapshot_resource::<BuildSnapshot>(namespace, id, lease_id)
+            .await?;
+        let build_version = snapshot.get_version();
+        let options = request.options.clone().unwrap_or_default();
+        let timestamp = Utc::now();
+        let mut build_metadata = BuildMetadata::new(
+            request.target_platform.clone().unwrap_or_default(),
+            BuildStatus::Pending,
+            timestamp,
+            // no builder assigned until dispatched
+            String::new(),
+            String::new(),
+            None,
+            vec![BuildStep::start(BuildStatus::Pending)],
+        );
+        build_metadata.set_options(options.clone());
+        build_metadata.set_inputs(request.manifest_version, None);
+        register
+            .put_resource(
+                Some(namespace),
+                id,
+                Some(build_version),
+                build_metadata,
+                lease_id,
+            )
+            .await?;
+        let pending_build = PendingBuild {
+            manifest_version: request.manifest_version,
+            target_platform: request.target_platform.clone(),
+            step_timeout: request.step_timeout,
+            build_timeout: request.build_timeout,
+            options,
+            selector,
+            timestamp,
+        };
+        register
+            .put_resource(
+                Some(namespace),
+                id,
+                Some(build_version),
+                pending_build,
+                lease_id,
+            )
+            .await?;
+        info!(
+            namespace = namespace,
+            id = id,
+            build_version = build_version,
+            "builder unavailable, queue pending build"
+        );
+        Ok(models::BuildResponse {
+            build_version,
+            reused: false,
+            pending: true,
+        })
+    }
+
+    // return identical succeed build unless forced or target platform unresolved
+    async fn reuse_build(
+        register: &mut Register,
+        lease_id: i64,
+        request: &models::BuildRequest,
+    ) -> Result<Option<models::BuildResponse>, (StatusCode, Failure)> {
+        let target_platform = match request.target_platform.as_ref() {
+            Some(target_platform) if !request.force => target_platform,
+            _ => return Ok(None),
+        };
+        let build_version =
+            match find_identical_build(register, lease_id, request, target_platform).await {
+                Ok(build_version) => build_version,
+                Err(err) => return Err((StatusCode::INTERNAL_SERVER_ERROR, err.into())),
+            };
+        Ok(build_version.map(|build_version| {
+            info!(
+                namespace = request.namespace.as_str(),
+                id = request.id.as_str(),
+                build_version = build_version,
+                "reuse identical build"
+            );
+            models::BuildResponse {
+                build_version,
+                reused: true,
+                pending: false,
+            }
+        }))
+    }
+
+    // find latest identical succeed build with app published
+    async fn find_identical_build(
+        register: &mut Register,
+        lease_id: i64,
+        request: &models::BuildRequest,
+        target_platform: &str,
+    ) -> pipebuilder_common::Result<Option<u64>> {
+        let namespace = request.namespace.as_str();
+        let id = request.id.as_str();
+        let options = request.options.clone().unwrap_or_default();
+        let catalogs_version = register
+            .get_resource::<CatalogsSnapshot>(Some(namespace), id, None, lease_id)
+            .await?
+            .map(|snapshot| snapshot.get_version());
+        let policies = BuildPolicies {
+            dependency_policy: register
+                .get_resource::<DependencyPolicy>(None, namespace, None, lease_id)
+                .await?,
+            lint: get_build_lint(register, lease_id, namespace, id).await?,
+            selector: request.selector.clone(),
+        };
+        let build_metadatas = register
+            .list_resource::<BuildMetadata>(Some(namespace), Some(id))
+            .await?;
+        let mut build_versions = build_metadatas
+            .into_iter()
+            .filter(|(_, build_metadata)| {
+                build_metadata.is_identical_build(
+                    request.manifest_version,
+                    target_platform,
+                    &options,
+                    catalogs_version,
+                    &policies,
+                )
+            })
+            .filter_map(|(key, _)| {
+                let id_version =
+                    remove_resource_namespace::<BuildMetadata>(key.as_str(), namespace);
+                let (build_id, build_version) = id_version.split_once('/')?;
+                match build_id == id {
+                    true => build_version.parse::<u64>().ok(),
+                    false => None,
+                }
+            })
+            .collect::<Vec<u64>>();
+        build_versions.sort_unstable_by(|a, b| b.cmp(a));
+        for build_version in build_versions {
+            // app could be deleted
+            if register
+                .get_resource::<AppMetadata>(Some(namespace), id, Some(build_version), lease_id)
+                .await?
+                .is_some()
+            {
+                return Ok(Some(build_version));
+            }
+        }
+        Ok(None)
+    }
+
+    pub async fn build_matrix(
+        client: SchedulerLeaderClient,
+        mut register: Register,
+        lease_id: i64,
+        request: models::BuildMatrixRequest,
+    ) -> Result<impl warp::Reply, Infallible> {
+        // validate build matrix request
+        match validations::validate_build_matrix_request(&mut register, &request).await {
+            Ok(_) => (),
+            Err(err) => return Ok(utils::handlers::http_bad_request(err.into())),
+        };
+        let mut client = match client.connect().await {
+            Ok(client) => client,
+            Err(err) => {
+                return Ok(utils::handlers::failure(
+                    StatusCode::SERVICE_UNAVAILABLE,
+                    err.into(),
+                ))
+            }
+        };
+        match do_build_matrix(&mut client, &mut register, lease_id, request).await {
             Ok(response) => Ok(utils::handlers::ok(&response)),
             Err(err) => Ok(utils::handlers::http_internal_error(err.into())),
         }
     }
 
+    async fn do_build_matrix(
+        client: &mut SchedulerClient<Channel>,
+        register: &mut Register,
+        lease_id: i64,
+        request: models::BuildMatrixRequest,
+    ) -> pipebuilder_common::Result<models::BuildMatrixResponse> {
+        let namespace = request.namespace.as_str();
+        let id = request.id.as_str();
+        let (_, snapshot) = register
+            .update_snapshot_resource::<BuildMatrixSnapshot>(namespace, id, lease_id)
+            .await?;
+        let version = snapshot.get_version();
+        // record matrix before fan out, so that builds triggered are linked even if interrupted
+        let builds = request
+            .target_platforms
+            .iter()
+            .map(|target_platform| BuildMatrixEntry {
+                target_platform: target_platform.to_owned(),
+                build_version: None,
+                message: Some(String::from("build not triggered yet")),
+            })
+            .collect::<Vec<BuildMatrixEntry>>();
+        let mut matrix = BuildMatrixMetadata::new(request.manifest_version, builds);
+        register
+            .put_resource(Some(namespace), id, Some(version), matrix.clone(), lease_id)
+            .await?;
+        // fan out one build per target platform, record failure if build not triggered
+        for index in 0..matrix.builds.len() {
+            let target_platform = matrix.builds[index].target_platform.to_owned();
+            let build_request = request.build_request(target_platform);
+            let build = &mut matrix.builds[index];
+            match trigger_build(client, register, lease_id, build_request).await {
+                Ok(response) => {
+                    build.build_version = Some(response.build_version);
+                    build.message = None;
+                }
+                Err((_, failure)) => build.message = Some(failure.error),
+            };
+            register
+                .put_resource(Some(namespace), id, Some(version), matrix.clone(), lease_id)
+                .await?;
+        }
+        Ok(models::BuildMatrixResponse {
+            version,
+            builds: matrix.builds,
+        })
+    }
+
+    pub async fn get_build_matrix(
+        mut register: Register,
+        lease_id: i64,
+        request: models::GetBuildMatrixRequest,
+    ) -> Result<impl warp::Reply, Infallible> {
+        // validate request
+        match validations::validate_get_build_matrix_request(&mut register, &request).await {
+            Ok(_) => (),
+            Err(err) => return Ok(utils::handlers::http_bad_request(err.into())),
+        };
+        let response = match do_get_build_matrix(&mut register, lease_id, request).await {
+            Ok(response) => response,
+            Err(err) => return Ok(utils::handlers::http_internal_error(err.into())),
+        };
+        match response {
+            Some(response) => Ok(utils::handlers::ok(&response)),
+            None => Ok(utils::handlers::http_not_found(Failure::new(String::from(
+                "build matrix not found",
+            )))),
+        }
+    }
+
+    async fn do_get_build_matrix(
+        register: &mut Register,
+        lease_id: i64,
+        request: models::GetBuildMatrixRequest,
+    ) -> pipebuilder_common::Result<Option<models::BuildMatrix>> {
+        let namespace = request.namespace;
+        let id = request.id;
+        let version = request.version;
+        let matrix = match register
+            .get_resource::<BuildMatrixMetadata>(
+                Some(namespace.as_str()),
+                id.as_str(),
+                Some(version),
+                lease_id,
+            )
+            .await?
+        {
+            Some(matrix) => matrix,
+            None => return Ok(None),
+        };
+        // collect build status per target platform
+        let mut builds: Vec<models::BuildMatrixBuild> = vec![];
+        for build in matrix.builds {
+            let status = match build.build_version {
+                Some(build_version) => register
+                    .get_resource::<BuildMetadata>(
+                        Some(namespace.as_str()),
+                        id.as_str(),
+                        Some(build_version),
+                        lease_id,
+                    )
+                    .await?
+                    .map(|build_metadata| build_metadata.status),
+                None => None,
+            };
+            builds.push(models::BuildMatrixBuild {
+                target_platform: build.target_platform,
+                build_version: build.build_version,
+                status,
+                message: build.message,
+            });
+        }
+        let statuses = builds
+            .iter()
+            .map(|build| build.status.clone())
+            .collect::<Vec<Option<BuildStatus>>>();
+        Ok(Some(models::BuildMatrix {
+            id,
+            version,
+            manifest_version: matrix.manifest_version,
+            status: BuildMatrixStatus::aggregate(statuses.as_slice()),
+            timestamp: matrix.timestamp,
+            builds,
+        }))
+    }
+
     async fn schedule(
         client: &mut SchedulerClient<Channel>,
         namespace: String,
         id: String,
         target_platform: Option<String>,
+        selector: HashMap<String, String>,
     ) -> pipebuilder_common::Result<ScheduleResponse> {
         let response = client
             .schedule(ScheduleRequest {
                 namespace,
                 id,
                 target_platform,
+                selector,
             })
             .await?;
         Ok(response.into_inner())
     }
 
+    // release scheduled builder slot, counted until the builder reports load otherwise
+    async fn release(client: &mut SchedulerClient<Channel>, builder_id: String) {
+        if let Err(err) = client
+            .release(ReleaseRequest {
+                builder_id: builder_id.clone(),
+            })
+            .await
+        {
+            warn!(
+                builder_id = builder_id.as_str(),
+                "release scheduled builder fail, error: '{:#?}'", err
+            );
+        }
+    }
+
     async fn do_build(
         client: &mut BuilderClient<Channel>,
         request: models::BuildRequest,
@@ -308,6 +816,89 @@ mod handlers {
         Ok(snapshots)
     }
 
+    pub async fn delete_build_matrix(
+        mut register: Register,
+        request: models::DeleteBuildMatrixRequest,
+    ) -> Result<impl warp::Reply, Infallible> {
+        match validations::validate_delete_build_matrix_request(&mut register, &request).await {
+            Ok(()) => (),
+            Err(err) => return Ok(utils::handlers::http_bad_request(err.into())),
+        };
+        match do_delete_build_matrix(&mut register, request).await {
+            Ok(response) => Ok(utils::handlers::ok(&response)),
+            Err(err) => Ok(utils::handlers::http_internal_error(err.into())),
+        }
+    }
+
+    async fn do_delete_build_matrix(
+        register: &mut Register,
+        request: models::DeleteBuildMatrixRequest,
+    ) -> pipebuilder_common::Result<models::DeleteBuildMatrixResponse> {
+        let namespace = request.namespace.as_str();
+        let id = request.id.as_str();
+        let versions = match request.version {
+            Some(version) => vec![version],
+            None => list_build_matrix_version(register, namespace, id).await?,
+        };
+        for version in versions {
+            register
+                .delete_resource::<BuildMatrixMetadata>(Some(namespace), id, Some(version))
+                .await?;
+        }
+        Ok(models::DeleteBuildMatrixResponse {})
+    }
+
+    async fn list_build_matrix_version(
+        register: &mut Register,
+        namespace: &str,
+        id: &str,
+    ) -> pipebuilder_common::Result<Vec<u64>> {
+        let matrices = register
+            .list_resource::<BuildMatrixMetadata>(Some(namespace), Some(id))
+            .await?;
+        let versions = matrices
+            .into_iter()
+            .filter_map(|(key, _)| {
+                let id_version =
+                    remove_resource_namespace::<BuildMatrixMetadata>(key.as_str(), namespace);
+                let (matrix_id, version) = id_version.split_once('/')?;
+                match matrix_id == id {
+                    true => version.parse::<u64>().ok(),
+                    false => None,
+                }
+            })
+            .collect();
+        Ok(versions)
+    }
+
+    pub async fn delete_build_matrix_snapshot(
+        mut register: Register,
+        request: models::DeleteBuildMatrixSnapshotRequest,
+    ) -> Result<impl warp::Reply, Infallible> {
+        match validations::validate_delete_build_matrix_snapshot_request(&mut register, &request)
+            .await
+        {
+            Ok(()) => (),
+            Err(err) => return Ok(utils::handlers::http_bad_request(err.into())),
+        };
+        match do_delete_build_matrix_snapshot(&mut register, request).await {
+            Ok(response) => Ok(utils::handlers::ok(&response)),
+            Err(err) => Ok(utils::handlers::http_internal_error(err.into())),
+        }
+    }
+
+    async fn do_delete_build_matrix_snapshot(
+        register: &mut Register,
+        request: models::DeleteBuildMatrixSnapshotRequest,
+    ) -> pipebuilder_common::Result<models::DeleteBuildMatrixSnapshotResponse> {
+        let namespace = request.namespace;
+        let id = request.id;
+        register
+            .delete_resource::<BuildMatrixSnapshot>(Some(namespace.as_str()), id.as_str(), None)
+            .await?;
+        Ok(models::DeleteBuildMatrixSnapshotResponse {})
+    }
+
     pub async fn delete_build_snapshot(
         mut register: Register,
         request: models::DeleteBuildSnapshotRequest,
@@ -381,6 +972,9 @@ mod handlers {
             builder_id: b.builder_id,
             builder_address: b.builder_address,
             message: b.message,
+            timeline: b.timeline,
+            options: b.options,
+            lint: b.lint,
         }))
     }
 
@@ -429,6 +1023,9 @@ mod handlers {
                     builder_id: build_metadata.builder_id,
                     builder_address: build_metadata.builder_address,
                     message: build_metadata.message,
+                    timeline: build_metadata.timeline,
+                    options: build_metadata.options,
+                    lint: build_metadata.lint,
                 }
             })
             .collect::<Vec<models::BuildMetadata>>();
@@ -473,6 +1070,17 @@ mod handlers {
                 ))))
             }
         };
+        // cancel pending build unless dispatched to builder
+        if build_metadata.status == BuildStatus::Pending {
+            return match do_cancel_pending_build(&mut register, lease_id, request_clone).await {
+                Ok(true) => Ok(utils::handlers::ok(&models::CancelBuildResponse {})),
+                Ok(false) => Ok(utils::handlers::http_bad_request(Failure::new(format!(
+                    "build {}/{}/{} dispatched to builder, retry cancel",
+                    namespace, id, version
+                )))),
+                Err(err) => Ok(utils::handlers::http_internal_error(err.into())),
+            };
+        }
         // cancel local build at builder
         let builder_id = build_metadata.builder_id;
         let builder_address = build_metadata.builder_address;
@@ -501,7 +1109,90 @@ mod handlers {
         Ok(resp.into_inner().into())
     }
 
+    // remove build from pending queue, return false if dispatched already
+    async fn do_cancel_pending_build(
+        register: &mut Register,
+        lease_id: i64,
+        request: models::CancelBuildRequest,
+    ) -> pipebuilder_common::Result<bool> {
+        let namespace = request.namespace.as_str();
+        let id = request.id.as_str();
+        let version = request.version;
+        if register
+            .take_resource::<PendingBuild>(Some(namespace), id, Some(version))
+            .await?
+            .is_none()
+        {
+            return Ok(false);
+        }
+        let mut build_metadata = match register
+            .get_resource::<BuildMetadata>(Some(namespace), id, Some(version), lease_id)
+            .await?
+        {
+            Some(build_metadata) => build_metadata,
+            None => return Ok(true),
+        };
+        build_metadata.status = BuildStatus::Cancel;
+        build_metadata.timestamp = Utc::now();
+        build_metadata.end_timeline(BuildStepOutcome::Cancel);
+        register
+            .put_resource(Some(namespace), id, Some(version), build_metadata, lease_id)
+            .await?;
+        info!(
+            namespace = namespace,
+            id = id,
+            build_version = version,
+            "cancel pending build"
+        );
+        Ok(true)
+    }
+
+    pub async fn list_pending_build(
+        mut register: Register,
+        request: models::ListBuildRequest,
+    ) -> Result<impl warp::Reply, Infallible> {
+        // validate request
+        match validations::validate_list_build_request(&mut register, &request).await {
+            Ok(_) => (),
+            Err(err) => return Ok(utils::handlers::http_bad_request(err.into())),
+        };
+        match do_list_pending_build(&mut register, request).await {
+            Ok(response) => Ok(utils::handlers::ok(&response)),
+            Err(err) => Ok(utils::handlers::http_internal_error(err.into())),
+        }
+    }
+
+    async fn do_list_pending_build(
+        register: &mut Register,
+        request: models::ListBuildRequest,
+    ) -> pipebuilder_common::Result<Vec<models::PendingBuild>> {
+        let namespace = request.namespace;
+        let id = request.id.as_deref();
+        let pending_builds = register
+            .list_resource::<PendingBuild>(Some(namespace.as_str()), id)
+            .await?;
+        let mut pending_builds = pending_builds
+            .into_iter()
+            .filter_map(|(key, pending_build)| {
+                let id_version =
+                    remove_resource_namespace::<PendingBuild>(key.as_str(), namespace.as_str());
+                let (id, version) = id_version.split_once('/')?;
+                Some(models::PendingBuild {
+                    id: id.to_owned(),
+                    version: version.parse().ok()?,
+                    manifest_version: pending_build.manifest_version,
+                    target_platform: pending_build.target_platform,
+                    timestamp: pending_build.timestamp,
+                })
+            })
+            .collect::<Vec<models::PendingBuild>>();
+        // in dispatch order
+        pending_builds.sort_by_key(|pending_build| pending_build.timestamp);
+        Ok(pending_builds)
+    }
+
     pub async fn delete_build_metadata(
+        mut repository_client: RepositoryClient<Channel>,
         mut register: Register,
         lease_id: i64,
         request: models::DeleteBuildRequest,
@@ -536,19 +1227,28 @@ mod handlers {
                 namespace, id, version
             ))));
         }
-        match do_delete_build(&mut register, request).await {
+        match do_delete_build(&mut repository_client, &mut register, request).await {
             Ok(response) => Ok(utils::handlers::ok(&response)),
             Err(err) => Ok(utils::handlers::http_internal_error(err.into())),
         }
     }
 
     async fn do_delete_build(
+        repository_client: &mut RepositoryClient<Channel>,
         register: &mut Register,
         request: models::DeleteBuildRequest,
     ) -> pipebuilder_common::Result<models::DeleteBuildResponse> {
         let namespace = request.namespace;
         let id = request.id;
         let version = request.version;
+        // source bundle published by the build, delete first so a failure can be retried
+        repository_client
+            .delete_source(DeleteSourceRequest {
+                namespace: namespace.clone(),
+                id: id.clone(),
+                version,
+            })
+            .await?;
         register
             .delete_resource::<BuildMetadata>(Some(namespace.as_str()), id.as_str(), Some(version))
             .await?;
@@ -620,6 +1320,77 @@ mod handlers {
         Ok(resp.into_inner().into())
     }
 
+    pub async fn stream_build_log(
+        mut register: Register,
+        lease_id: i64,
+        request: models::GetBuildLogRequest,
+    ) -> Result<warp::reply::Response, Infallible> {
+        // validate request
+        match validations::validate_get_build_log_request(&mut register, &request).await {
+            Ok(_) => (),
+            Err(err) => return Ok(utils::handlers::http_bad_request(err.into()).into_response()),
+        };
+        let request_clone = request.clone();
+        let namespace = request.namespace;
+        let id = request.id;
+        let version = request.version;
+        let build_metadata = match do_get_build_metadata(
+            &mut register,
+            lease_id,
+            models::GetBuildRequest {
+                namespace: namespace.clone(),
+                id: id.clone(),
+                version,
+            },
+        )
+        .await
+        {
+            Ok(build_metadata) => build_metadata,
+            Err(err) => return Ok(utils::handlers::http_internal_error(err.into()).into_response()),
+        };
+        let build_metadata = match build_metadata {
+            Some(build_metadata) => build_metadata,
+            None => {
+                return Ok(utils::handlers::http_not_found(Failure::new(format!(
+                    "build metadata {}/{}/{} not found",
+                    namespace, id, version
+                )))
+                .into_response())
+            }
+        };
+        // stream local build log at builder
+        let builder_id = build_metadata.builder_id;
+        let builder_address = build_metadata.builder_address;
+        info!(
+            builder_id = builder_id.as_str(),
+            builder_address = builder_address.as_str(),
+            "stream build log at builder",
+        );
+        let mut builder_client = match utils::handlers::builder_client(builder_address.as_str())
+            .await
+        {
+            Ok(builder_client) => builder_client,
+            Err(err) => return Ok(utils::handlers::http_internal_error(err.into()).into_response()),
+        };
+        match do_stream_build_log(&mut builder_client, request_clone).await {
+            Ok(stream) => Ok(utils::handlers::ok_stream(stream).into_response()),
+            Err(err) => Ok(utils::handlers::http_internal_error(err.into()).into_response()),
+        }
+    }
+
+    async fn do_stream_build_log(
+        client: &mut BuilderClient<Channel>,
+        request: models::GetBuildLogRequest,
+    ) -> pipebuilder_common::Result<impl Stream<Item = pipebuilder_common::Result<Vec<u8>>>> {
+        let request: StreamBuildLogRequest = request.into();
+        let response = client.stream_build_log(request).await?;
+        let stream = response.into_inner().map(|r| match r {
+            Ok(StreamBuildLogResponse { buffer }) => Ok(buffer),
+            Err(status) => Err(status.into()),
+        });
+        Ok(stream)
+    }
+
     pub async fn scan_build(
         mut register: Register,
         lease_id: i64,
@@ -1,10 +1,19 @@
-use pipebuilder_common::{grpc::client::RpcClientConfig, BaseConfig};
+use pipebuilder_common::{
+    grpc::client::{RpcClientConfig, RpcProtocolType},
+    BaseConfig,
+};
 use serde::Deserialize;
 
 #[derive(Deserialize)]
 pub struct RpcClientConfigs {
     pub repository: RpcClientConfig,
-    pub scheduler: RpcClientConfig,
+    pub scheduler: SchedulerClientConfig,
+}
+
+// scheduler address discovered by leader election
+#[derive(Deserialize)]
+pub struct SchedulerClientConfig {
+    pub protocol: RpcProtocolType,
 }
 
 #[derive(Deserialize)]
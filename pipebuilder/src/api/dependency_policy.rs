@@ -0,0 +1,172 @@
+pub mod filters {
+
+    use super::handlers;
+    use crate::utils;
+    use pipebuilder_common::{api::models, Register};
+    use warp::Filter;
+
+    // dependency policy api
+    pub fn v1_dependency_policy(
+        register: Register,
+        lease_id: i64,
+    ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+        v1_dependency_policy_put(register.clone(), lease_id)
+            .or(v1_dependency_policy_get(register.clone(), lease_id))
+            .or(v1_dependency_policy_delete(register))
+    }
+
+    pub fn v1_dependency_policy_put(
+        register: Register,
+        lease_id: i64,
+    ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+        warp::path!("api" / "v1" / "dependency-policy")
+            .and(warp::post())
+            .and(utils::filters::with_register(register))
+            .and(utils::filters::with_lease_id(lease_id))
+            .and(utils::filters::json_request::<
+                models::UpdateDependencyPolicyRequest,
+            >())
+            .and_then(handlers::put_dependency_policy)
+    }
+
+    pub fn v1_dependency_policy_get(
+        register: Register,
+        lease_id: i64,
+    ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+        warp::path!("api" / "v1" / "dependency-policy")
+            .and(warp::get())
+            .and(utils::filters::with_register(register))
+            .and(utils::filters::with_lease_id(lease_id))
+            .and(warp::query::<models::GetDependencyPolicyRequest>())
+            .and_then(handlers::get_dependency_policy)
+    }
+
+    pub fn v1_dependency_policy_delete(
+        register: Register,
+    ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+        warp::path!("api" / "v1" / "dependency-policy")
+            .and(warp::delete())
+            .and(utils::filters::with_register(register))
+            .and(utils::filters::json_request::<
+                models::DeleteDependencyPolicyRequest,
+            >())
+            .and_then(handlers::delete_dependency_policy)
+    }
+}
+
+mod handlers {
+    use crate::{utils, validations};
+    use pipebuilder_common::{
+        api::models::{self, Failure},
+        DependencyPolicy, Register,
+    };
+    use std::convert::Infallible;
+
+    pub async fn put_dependency_policy(
+        mut register: Register,
+        lease_id: i64,
+        request: models::UpdateDependencyPolicyRequest,
+    ) -> Result<impl warp::Reply, Infallible> {
+        // validate request
+        match validations::validate_put_dependency_policy_request(&mut register, &request).await {
+            Ok(_) => (),
+            Err(err) => return Ok(utils::handlers::http_bad_request(err.into())),
+        };
+        match do_put_dependency_policy(&mut register, lease_id, request).await {
+            Ok(response) => Ok(utils::handlers::ok(&response)),
+            Err(err) => Ok(utils::handlers::http_internal_error(err.into())),
+        }
+    }
+
+    async fn do_put_dependency_policy(
+        register: &mut Register,
+        lease_id: i64,
+        request: models::UpdateDependencyPolicyRequest,
+    ) -> pipebuilder_common::Result<models::DependencyPolicy> {
+        let namespace = request.namespace;
+        let dependency_policy = DependencyPolicy {
+            registries: request.registries,
+            git_hosts: request.git_hosts,
+            banned_crates: request.banned_crates,
+            allow_path: request.allow_path,
+        };
+        let (_, dependency_policy) = register
+            .put_resource(None, namespace.as_str(), None, dependency_policy, lease_id)
+            .await?;
+        Ok(into_model(namespace, dependency_policy))
+    }
+
+    pub async fn get_dependency_policy(
+        mut register: Register,
+        lease_id: i64,
+        request: models::GetDependencyPolicyRequest,
+    ) -> Result<impl warp::Reply, Infallible> {
+        // validate request
+        match validations::validate_get_dependency_policy_request(&mut register, &request).await {
+            Ok(_) => (),
+            Err(err) => return Ok(utils::handlers::http_bad_request(err.into())),
+        };
+        let response = match do_get_dependency_policy(&mut register, lease_id, request).await {
+            Ok(response) => response,
+            Err(err) => return Ok(utils::handlers::http_internal_error(err.into())),
+        };
+        match response {
+            Some(response) => Ok(utils::handlers::ok(&response)),
+            None => Ok(utils::handlers::http_not_found(Failure::new(String::from(
+                "dependency policy not found",
+            )))),
+        }
+    }
+
+    async fn do_get_dependency_policy(
+        register: &mut Register,
+        lease_id: i64,
+        request: models::GetDependencyPolicyRequest,
+    ) -> pipebuilder_common::Result<Option<models::DependencyPolicy>> {
+        let namespace = request.namespace;
+        let dependency_policy = register
+            .get_resource::<DependencyPolicy>(None, namespace.as_str(), None, lease_id)
+            .await?;
+        Ok(dependency_policy.map(|dependency_policy| into_model(namespace, dependency_policy)))
+    }
+
+    pub async fn delete_dependency_policy(
+        mut register: Register,
+        request: models::DeleteDependencyPolicyRequest,
+    ) -> Result<impl warp::Reply, Infallible> {
+        // validate request
+        match validations::validate_delete_dependency_policy_request(&mut register, &request).await
+        {
+            Ok(_) => (),
+            Err(err) => return Ok(utils::handlers::http_bad_request(err.into())),
+        };
+        match do_delete_dependency_policy(&mut register, request).await {
+            Ok(response) => Ok(utils::handlers::ok(&response)),
+            Err(err) => Ok(utils::handlers::http_internal_error(err.into())),
+        }
+    }
+
+    async fn do_delete_dependency_policy(
+        register: &mut Register,
+        request: models::DeleteDependencyPolicyRequest,
+    ) -> pipebuilder_common::Result<models::DeleteDependencyPolicyResponse> {
+        let namespace = request.namespace;
+        register
+            .delete_resource::<DependencyPolicy>(None, namespace.as_str(), None)
+            .await?;
+        Ok(models::DeleteDependencyPolicyResponse {})
+    }
+
+    fn into_model(
+        namespace: String,
+        dependency_policy: DependencyPolicy,
+    ) -> models::DependencyPolicy {
+        models::DependencyPolicy {
+            namespace,
+            registries: dependency_policy.registries,
+            git_hosts: dependency_policy.git_hosts,
+            banned_crates: dependency_policy.banned_crates,
+            allow_path: dependency_policy.allow_path,
+        }
+    }
+}
@@ -0,0 +1,202 @@
+pub mod filters {
+
+    use super::handlers;
+    use crate::utils;
+    use pipebuilder_common::{api::models, Register};
+    use warp::Filter;
+
+    // lint policy api
+    pub fn v1_lint_policy(
+        register: Register,
+        lease_id: i64,
+    ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+        v1_lint_policy_put(register.clone(), lease_id)
+            .or(v1_lint_policy_get(register.clone(), lease_id))
+            .or(v1_lint_policy_delete(register))
+    }
+
+    pub fn v1_lint_policy_put(
+        register: Register,
+        lease_id: i64,
+    ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+        warp::path!("api" / "v1" / "lint-policy")
+            .and(warp::post())
+            .and(utils::filters::with_register(register))
+            .and(utils::filters::with_lease_id(lease_id))
+            .and(utils::filters::json_request::<
+                models::UpdateLintPolicyRequest,
+            >())
+            .and_then(handlers::put_lint_policy)
+    }
+
+    pub fn v1_lint_policy_get(
+        register: Register,
+        lease_id: i64,
+    ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+        warp::path!("api" / "v1" / "lint-policy")
+            .and(warp::get())
+            .and(utils::filters::with_register(register))
+            .and(utils::filters::with_lease_id(lease_id))
+            .and(warp::query::<models::GetLintPolicyRequest>())
+            .and_then(handlers::get_lint_policy)
+    }
+
+    pub fn v1_lint_policy_delete(
+        register: Register,
+    ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+        warp::path!("api" / "v1" / "lint-policy")
+            .and(warp::delete())
+            .and(utils::filters::with_register(register))
+            .and(utils::filters::json_request::<
+                models::DeleteLintPolicyRequest,
+            >())
+            .and_then(handlers::delete_lint_policy)
+    }
+}
+
+mod handlers {
+    use crate::{utils, validations};
+    use pipebuilder_common::{
+        api::models::{self, Failure},
+        BuildLint, Register,
+    };
+    use std::convert::Infallible;
+
+    pub async fn put_lint_policy(
+        mut register: Register,
+        lease_id: i64,
+        request: models::UpdateLintPolicyRequest,
+    ) -> Result<impl warp::Reply, Infallible> {
+        // validate request
+        match validations::validate_put_lint_policy_request(&mut register, &request).await {
+            Ok(_) => (),
+            Err(err) => return Ok(utils::handlers::http_bad_request(err.into())),
+        };
+        match do_put_lint_policy(&mut register, lease_id, request).await {
+            Ok(response) => Ok(utils::handlers::ok(&response)),
+            Err(err) => Ok(utils::handlers::http_internal_error(err.into())),
+        }
+    }
+
+    async fn do_put_lint_policy(
+        register: &mut Register,
+        lease_id: i64,
+        request: models::UpdateLintPolicyRequest,
+    ) -> pipebuilder_common::Result<models::LintPolicy> {
+        let namespace = request.namespace;
+        let id = request.id;
+        let lint = BuildLint {
+            lints: request
+                .lints
+                .unwrap_or_else(|| vec![String::from("-D"), String::from("warnings")]),
+            block: request.block.unwrap_or(true),
+        };
+        // namespace rule keyed by namespace, project rule keyed by namespace and id
+        let (_, lint) = match id.as_ref() {
+            Some(id) => {
+                register
+                    .put_resource(Some(namespace.as_str()), id.as_str(), None, lint, lease_id)
+                    .await?
+            }
+            None => {
+                register
+                    .put_resource(None, namespace.as_str(), None, lint, lease_id)
+                    .await?
+            }
+        };
+        Ok(into_model(namespace, id, lint))
+    }
+
+    pub async fn get_lint_policy(
+        mut register: Register,
+        lease_id: i64,
+        request: models::GetLintPolicyRequest,
+    ) -> Result<impl warp::Reply, Infallible> {
+        // validate request
+        match validations::validate_get_lint_policy_request(&mut register, &request).await {
+            Ok(_) => (),
+            Err(err) => return Ok(utils::handlers::http_bad_request(err.into())),
+        };
+        let response = match do_get_lint_policy(&mut register, lease_id, request).await {
+            Ok(response) => response,
+            Err(err) => return Ok(utils::handlers::http_internal_error(err.into())),
+        };
+        match response {
+            Some(response) => Ok(utils::handlers::ok(&response)),
+            None => Ok(utils::handlers::http_not_found(Failure::new(String::from(
+                "lint policy not found",
+            )))),
+        }
+    }
+
+    async fn do_get_lint_policy(
+        register: &mut Register,
+        lease_id: i64,
+        request: models::GetLintPolicyRequest,
+    ) -> pipebuilder_common::Result<Option<models::LintPolicy>> {
+        let namespace = request.namespace;
+        let id = request.id;
+        let lint = match id.as_ref() {
+            Some(id) => {
+                register
+                    .get_resource::<BuildLint>(
+                        Some(namespace.as_str()),
+                        id.as_str(),
+                        None,
+                        lease_id,
+                    )
+                    .await?
+            }
+            None => {
+                register
+                    .get_resource::<BuildLint>(None, namespace.as_str(), None, lease_id)
+                    .await?
+            }
+        };
+        Ok(lint.map(|lint| into_model(namespace, id, lint)))
+    }
+
+    pub async fn delete_lint_policy(
+        mut register: Register,
+        request: models::DeleteLintPolicyRequest,
+    ) -> Result<impl warp::Reply, Infallible> {
+        // validate request
+        match validations::validate_delete_lint_policy_request(&mut register, &request).await {
+            Ok(_) => (),
+            Err(err) => return Ok(utils::handlers::http_bad_request(err.into())),
+        };
+        match do_delete_lint_policy(&mut register, request).await {
+            Ok(response) => Ok(utils::handlers::ok(&response)),
+            Err(err) => Ok(utils::handlers::http_internal_error(err.into())),
+        }
+    }
+
+    async fn do_delete_lint_policy(
+        register: &mut Register,
+        request: models::DeleteLintPolicyRequest,
+    ) -> pipebuilder_common::Result<models::DeleteLintPolicyResponse> {
+        let namespace = request.namespace;
+        match request.id {
+            Some(id) => {
+                register
+                    .delete_resource::<BuildLint>(Some(namespace.as_str()), id.as_str(), None)
+                    .await?
+            }
+            None => {
+                register
+                    .delete_resource::<BuildLint>(None, namespace.as_str(), None)
+                    .await?
+            }
+        };
+        Ok(models::DeleteLintPolicyResponse {})
+    }
+
+    fn into_model(namespace: String, id: Option<String>, lint: BuildLint) -> models::LintPolicy {
+        models::LintPolicy {
+            namespace,
+            id,
+            lints: lint.lints,
+            block: lint.block,
+        }
+    }
+}
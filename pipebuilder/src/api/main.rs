@@ -6,10 +6,13 @@ mod build;
 mod catalog_schema;
 mod catalogs;
 mod config;
+mod dependency_policy;
+mod lint_policy;
 mod manifest;
 mod namespace;
 mod node;
 mod project;
+mod source;
 mod utils;
 mod validations;
 
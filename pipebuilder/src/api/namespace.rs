@@ -50,7 +50,9 @@ pub mod filters {
 
 mod handlers {
     use crate::{utils, validations};
-    use pipebuilder_common::{api::models, remove_resource, Namespace, Register};
+    use pipebuilder_common::{
+        api::models, remove_resource, BuildLint, DependencyPolicy, Namespace, Register,
+    };
     use std::convert::Infallible;
 
     pub async fn put_namespace(
@@ -125,6 +127,12 @@ mod handlers {
         request: models::DeleteNamespaceRequest,
     ) -> pipebuilder_common::Result<models::DeleteNamespaceResponse> {
         let id = request.id;
+        register
+            .delete_resource::<DependencyPolicy>(None, id.as_str(), None)
+            .await?;
+        register
+            .delete_resource::<BuildLint>(None, id.as_str(), None)
+            .await?;
         register
             .delete_resource::<Namespace>(None, id.as_str(), None)
             .await?;
@@ -108,6 +108,9 @@ mod handlers {
                     os: node_state.os,
                     status: node_state.status,
                     timestamp: node_state.timestamp,
+                    target_platforms: node_state.target_platforms,
+                    load: node_state.load,
+                    labels: node_state.labels,
                 };
                 let role = match role {
                     Some(role) => role,
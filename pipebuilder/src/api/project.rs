@@ -50,7 +50,9 @@ pub mod filters {
 
 mod handlers {
     use crate::{utils, validations};
-    use pipebuilder_common::{api::models, remove_resource_namespace, Project, Register};
+    use pipebuilder_common::{
+        api::models, remove_resource_namespace, BuildLint, Project, Register,
+    };
     use std::convert::Infallible;
 
     pub async fn put_project(
@@ -76,11 +78,39 @@ mod handlers {
     ) -> pipebuilder_common::Result<models::Project> {
         let namespace = request.namespace;
         let id = request.id;
-        let (_, project) = register
-            .update_default_resource::<Project>(Some(namespace.as_str()), id.as_str(), lease_id)
-            .await?;
-        let created = project.created;
-        Ok(models::Project { id, created })
+        let (_, project) = match request.selector {
+            // replace selector, keep created timestamp if exists
+            Some(selector) => {
+                let mut project = register
+                    .get_resource::<Project>(Some(namespace.as_str()), id.as_str(), None, lease_id)
+                    .await?
+                    .unwrap_or_default();
+                project.set_selector(selector);
+                register
+                    .put_resource(
+                        Some(namespace.as_str()),
+                        id.as_str(),
+                        None,
+                        project,
+                        lease_id,
+                    )
+                    .await?
+            }
+            None => {
+                register
+                    .update_default_resource::<Project>(
+                        Some(namespace.as_str()),
+                        id.as_str(),
+                        lease_id,
+                    )
+                    .await?
+            }
+        };
+        Ok(models::Project {
+            id,
+            created: project.created,
+            selector: project.selector,
+        })
     }
 
     pub async fn list_project(
@@ -113,6 +143,7 @@ mod handlers {
                 models::Project {
                     id: id.to_owned(),
                     created: project.created,
+                    selector: project.selector,
                 }
             })
             .collect::<Vec<models::Project>>();
@@ -140,6 +171,9 @@ mod handlers {
     ) -> pipebuilder_common::Result<models::DeleteProjectResponse> {
         let namespace = request.namespace;
         let id = request.id;
+        register
+            .delete_resource::<BuildLint>(Some(namespace.as_str()), id.as_str(), None)
+            .await?;
         register
             .delete_resource::<Project>(Some(namespace.as_str()), id.as_str(), None)
             .await?;
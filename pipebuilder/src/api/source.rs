@@ -0,0 +1,65 @@
+pub mod filters {
+    use super::handlers;
+    use crate::utils;
+    use pipebuilder_common::{
+        api::models, grpc::repository::repository_client::RepositoryClient, Register,
+    };
+    use tonic::transport::Channel;
+    use warp::Filter;
+
+    // source api
+    pub fn v1_source(
+        repository_client: RepositoryClient<Channel>,
+        register: Register,
+    ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+        v1_source_get(repository_client, register)
+    }
+
+    pub fn v1_source_get(
+        repository_client: RepositoryClient<Channel>,
+        register: Register,
+    ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+        warp::path!("api" / "v1" / "source")
+            .and(warp::get())
+            .and(utils::filters::with_repository_client(repository_client))
+            .and(utils::filters::with_register(register))
+            .and(warp::query::<models::GetSourceRequest>())
+            .and_then(handlers::get_source)
+    }
+}
+
+mod handlers {
+    use crate::{utils, validations};
+    use pipebuilder_common::{
+        api::models,
+        grpc::repository::{repository_client::RepositoryClient, GetSourceRequest},
+        Register,
+    };
+    use std::convert::Infallible;
+    use tonic::transport::Channel;
+
+    pub async fn get_source(
+        mut client: RepositoryClient<Channel>,
+        mut register: Register,
+        request: models::GetSourceRequest,
+    ) -> Result<impl warp::Reply, Infallible> {
+        // validate request
+        match validations::validate_get_source_request(&mut register, &request).await {
+            Ok(_) => (),
+            Err(err) => return Ok(utils::handlers::http_bad_request(err.into())),
+        };
+        match do_get_source(&mut client, request).await {
+            Ok(response) => Ok(utils::handlers::ok(&response)),
+            Err(err) => Ok(utils::handlers::http_not_found(err.into())),
+        }
+    }
+
+    async fn do_get_source(
+        client: &mut RepositoryClient<Channel>,
+        request: models::GetSourceRequest,
+    ) -> pipebuilder_common::Result<models::GetSourceResponse> {
+        let request: GetSourceRequest = request.into();
+        let response = client.get_source(request).await?;
+        Ok(response.into_inner().into())
+    }
+}
@@ -1,10 +1,7 @@
 pub mod filters {
 
     use pipebuilder_common::{
-        grpc::{
-            repository::repository_client::RepositoryClient,
-            schedule::scheduler_client::SchedulerClient,
-        },
+        grpc::{client::SchedulerLeaderClient, repository::repository_client::RepositoryClient},
         NodeService, Register,
     };
     use serde::de::DeserializeOwned;
@@ -12,8 +9,8 @@ pub mod filters {
     use warp::Filter;
 
     pub fn with_scheduler_client(
-        client: SchedulerClient<Channel>,
-    ) -> impl Filter<Extract = (SchedulerClient<Channel>,), Error = std::convert::Infallible> + Clone
+        client: SchedulerLeaderClient,
+    ) -> impl Filter<Extract = (SchedulerLeaderClient,), Error = std::convert::Infallible> + Clone
     {
         warp::any().map(move || client.clone())
     }
@@ -54,6 +51,7 @@ pub mod filters {
 
 pub mod handlers {
 
+    use futures_util::Stream;
     use pipebuilder_common::{
         api::models::Failure,
         grpc::{
@@ -65,7 +63,10 @@ pub mod handlers {
     };
     use serde::Serialize;
     use tonic::transport::Channel;
-    use warp::http::{Response, StatusCode};
+    use warp::{
+        http::{header::CONTENT_TYPE, Response, StatusCode},
+        hyper::{body::Bytes, Body},
+    };
 
     pub async fn get_internal_node_state(
         register: &mut Register,
@@ -112,10 +113,6 @@ pub mod handlers {
         failure(StatusCode::INTERNAL_SERVER_ERROR, f)
     }
 
-    pub fn http_service_unavailable(f: Failure) -> http::Result<Response<String>> {
-        failure(StatusCode::SERVICE_UNAVAILABLE, f)
-    }
-
     pub fn http_not_found(f: Failure) -> http::Result<Response<String>> {
         failure(StatusCode::NOT_FOUND, f)
     }
@@ -132,4 +129,17 @@ pub mod handlers {
             .status(StatusCode::OK)
             .body(serde_json::to_string::<T>(t).unwrap())
     }
+
+    // chunked response with body streamed
+    pub fn ok_stream<S, T, E>(stream: S) -> http::Result<Response<Body>>
+    where
+        S: Stream<Item = Result<T, E>> + Send + 'static,
+        T: Into<Bytes> + 'static,
+        E: Into<Box<dyn std::error::Error + Send + Sync>> + 'static,
+    {
+        Response::builder()
+            .status(StatusCode::OK)
+            .header(CONTENT_TYPE, "text/plain")
+            .body(Body::wrap_stream(stream))
+    }
 }
@@ -1,9 +1,11 @@
 use pipebuilder_common::{
-    api::models, invalid_api_request, AppMetadata, Build, BuildMetadata, BuildSnapshot,
-    CatalogSchemaMetadata, CatalogSchemaSnapshot, CatalogsMetadata, CatalogsSnapshot,
-    ManifestMetadata, ManifestSnapshot, NodeRole, NodeState, Project, Register, ResourceKeyBuilder,
-    ResourceType, Result,
+    api::models, invalid_api_request, AppMetadata, BuildMatrixMetadata, BuildMatrixSnapshot,
+    BuildMetadata, BuildOptions, BuildSnapshot, CatalogSchemaMetadata, CatalogSchemaSnapshot,
+    CatalogsMetadata, CatalogsSnapshot, DependencyPolicy, ManifestMetadata, ManifestSnapshot,
+    NodeRole, NodeState, PendingBuild, Project, Register, ResourceKeyBuilder, ResourceType, Result,
+    SourceMetadata,
 };
+use std::collections::HashMap;
 
 pub async fn validate_build_request(
     register: &mut Register,
@@ -13,10 +15,97 @@ pub async fn validate_build_request(
     validate_namespace(register, namespace).await?;
     let id = request.id.as_str();
     validate_project(register, namespace, id).await?;
+    let is_test_required = request
+        .options
+        .as_ref()
+        .is_some_and(BuildOptions::is_test_required);
     if let Some(target_platform) = request.target_platform.as_ref() {
-        validate_target_platform(target_platform)?;
+        validate_target_platform(register, target_platform, is_test_required).await?;
     };
-    Ok(())
+    validate_selector(register, namespace, id, &request.selector).await?;
+    validate_build_options(
+        register,
+        namespace,
+        id,
+        request.manifest_version,
+        request.options.as_ref(),
+    )
+    .await
+}
+
+pub async fn validate_build_matrix_request(
+    register: &mut Register,
+    request: &models::BuildMatrixRequest,
+) -> Result<()> {
+    let namespace = request.namespace.as_str();
+    validate_namespace(register, namespace).await?;
+    let id = request.id.as_str();
+    validate_project(register, namespace, id).await?;
+    let is_test_required = request
+        .options
+        .as_ref()
+        .is_some_and(BuildOptions::is_test_required);
+    let target_platforms = &request.target_platforms;
+    if target_platforms.is_empty() {
+        return Err(invalid_api_request(String::from(
+            "build matrix target platforms not provided",
+        )));
+    }
+    for (i, target_platform) in target_platforms.iter().enumerate() {
+        if target_platforms[..i].contains(target_platform) {
+            return Err(invalid_api_request(format!(
+                "duplicated target platform '{}'",
+                target_platform
+            )));
+        }
+        validate_target_platform(register, target_platform, is_test_required).await?;
+    }
+    validate_selector(register, namespace, id, &request.selector).await?;
+    validate_build_options(
+        register,
+        namespace,
+        id,
+        request.manifest_version,
+        request.options.as_ref(),
+    )
+    .await
+}
+
+pub async fn validate_get_build_matrix_request(
+    register: &mut Register,
+    request: &models::GetBuildMatrixRequest,
+) -> Result<()> {
+    let namespace = request.namespace.as_str();
+    validate_namespace(register, namespace).await?;
+    let id = request.id.as_str();
+    validate_project(register, namespace, id).await
+}
+
+pub async fn validate_delete_build_matrix_request(
+    register: &mut Register,
+    request: &models::DeleteBuildMatrixRequest,
+) -> Result<()> {
+    let namespace = request.namespace.as_str();
+    validate_namespace(register, namespace).await?;
+    let id = request.id.as_str();
+    validate_project(register, namespace, id).await
+}
+
+pub async fn validate_delete_build_matrix_snapshot_request(
+    register: &mut Register,
+    request: &models::DeleteBuildMatrixSnapshotRequest,
+) -> Result<()> {
+    let namespace = request.namespace.as_str();
+    validate_namespace(register, namespace).await?;
+    let id = request.id.as_str();
+    validate_project(register, namespace, id).await?;
+    match is_build_matrix_metadata_exist(register, namespace, id).await? {
+        true => Err(invalid_api_request(format!(
+            "can not delete build matrix snapshot (namespace = {}, id = {}), build matrices found",
+            namespace, id
+        ))),
+        false => Ok(()),
+    }
 }
 
 pub async fn validate_get_build_request(
@@ -87,7 +176,8 @@ pub async fn validate_post_manifest_request(
     let namespace = request.namespace.as_str();
     validate_namespace(register, namespace).await?;
     let id = request.id.as_str();
-    validate_project(register, namespace, id).await
+    validate_project(register, namespace, id).await?;
+    validate_dependency_policy(register, namespace, request.buffer.as_slice()).await
 }
 
 pub async fn validate_get_manifest_request(
@@ -295,6 +385,16 @@ pub async fn validate_get_app_request(
     validate_project(register, namespace, id).await
 }
 
+pub async fn validate_get_source_request(
+    register: &mut Register,
+    request: &models::GetSourceRequest,
+) -> Result<()> {
+    let namespace = request.namespace.as_str();
+    validate_namespace(register, namespace).await?;
+    let id = request.id.as_str();
+    validate_project(register, namespace, id).await
+}
+
 pub async fn validate_delete_app_request(
     register: &mut Register,
     request: &models::DeleteAppRequest,
@@ -329,6 +429,15 @@ pub async fn validate_delete_build_snapshot_request(
     validate_namespace(register, namespace).await?;
     let id = request.id.as_str();
     validate_project(register, namespace, id).await?;
+    match is_pending_build_exist(register, namespace, id).await? {
+        true => {
+            return Err(invalid_api_request(format!(
+                "can not delete build snapshot (namespace = {}, id = {}), pending builds found",
+                namespace, id
+            )))
+        }
+        false => (),
+    };
     match is_build_metadata_exist(register, namespace, id).await? {
         true => Err(invalid_api_request(format!(
             "can not delete build snapshot (namespace = {}, id = {}), builds found",
@@ -346,6 +455,15 @@ pub async fn validate_delete_project_request(
     validate_namespace(register, namespace).await?;
     let id = request.id.as_str();
     validate_project(register, namespace, id).await?;
+    match is_pending_build_exist(register, namespace, id).await? {
+        true => {
+            return Err(invalid_api_request(format!(
+                "can not delete project (namespace = {}, id = {}), pending builds found.",
+                namespace, id
+            )))
+        }
+        false => (),
+    };
     match is_build_snapshot_exist(register, namespace, id).await? {
         true => {
             return Err(invalid_api_request(format!(
@@ -355,6 +473,15 @@ pub async fn validate_delete_project_request(
         }
         false => (),
     };
+    match is_build_matrix_snapshot_exist(register, namespace, id).await? {
+        true => {
+            return Err(invalid_api_request(format!(
+                "can not delete project (namespace = {}, id = {}), build matrix snapshot found.",
+                namespace, id
+            )))
+        }
+        false => (),
+    };
     match is_manifest_snapshot_exist(register, namespace, id).await? {
         true => {
             return Err(invalid_api_request(format!(
@@ -374,8 +501,17 @@ pub async fn validate_delete_project_request(
         false => (),
     };
     match is_app_metadata_exist(register, namespace, id).await? {
+        true => {
+            return Err(invalid_api_request(format!(
+                "can not delete project (namespace = {}, id = {}), app metadata found.",
+                namespace, id
+            )))
+        }
+        false => (),
+    };
+    match is_source_metadata_exist(register, namespace, id).await? {
         true => Err(invalid_api_request(format!(
-            "can not delete project (namespace = {}, id = {}), app metadata found.",
+            "can not delete project (namespace = {}, id = {}), source metadata found.",
             namespace, id
         ))),
         false => Ok(()),
@@ -405,6 +541,67 @@ pub async fn validate_delete_namespace_request(
     }
 }
 
+pub async fn validate_put_dependency_policy_request(
+    register: &mut Register,
+    request: &models::UpdateDependencyPolicyRequest,
+) -> Result<()> {
+    let namespace = request.namespace.as_str();
+    validate_namespace(register, namespace).await
+}
+
+pub async fn validate_get_dependency_policy_request(
+    register: &mut Register,
+    request: &models::GetDependencyPolicyRequest,
+) -> Result<()> {
+    let namespace = request.namespace.as_str();
+    validate_namespace(register, namespace).await
+}
+
+pub async fn validate_delete_dependency_policy_request(
+    register: &mut Register,
+    request: &models::DeleteDependencyPolicyRequest,
+) -> Result<()> {
+    let namespace = request.namespace.as_str();
+    validate_namespace(register, namespace).await
+}
+
+pub async fn validate_put_lint_policy_request(
+    register: &mut Register,
+    request: &models::UpdateLintPolicyRequest,
+) -> Result<()> {
+    let namespace = request.namespace.as_str();
+    validate_lint_policy_target(register, namespace, request.id.as_deref()).await
+}
+
+pub async fn validate_get_lint_policy_request(
+    register: &mut Register,
+    request: &models::GetLintPolicyRequest,
+) -> Result<()> {
+    let namespace = request.namespace.as_str();
+    validate_lint_policy_target(register, namespace, request.id.as_deref()).await
+}
+
+pub async fn validate_delete_lint_policy_request(
+    register: &mut Register,
+    request: &models::DeleteLintPolicyRequest,
+) -> Result<()> {
+    let namespace = request.namespace.as_str();
+    validate_lint_policy_target(register, namespace, request.id.as_deref()).await
+}
+
+// lint policy applies to namespace, or project if id provided
+async fn validate_lint_policy_target(
+    register: &mut Register,
+    namespace: &str,
+    id: Option<&str>,
+) -> Result<()> {
+    validate_namespace(register, namespace).await?;
+    match id {
+        Some(id) => validate_project(register, namespace, id).await,
+        None => Ok(()),
+    }
+}
+
 pub fn validate_list_node_state_request(request: &models::ListNodeStateRequest) -> Result<()> {
     let role = request.role.as_ref();
     let role = match role {
@@ -433,13 +630,71 @@ pub fn validate_node_state(state: &NodeState, expected_role: &NodeRole) -> Resul
     Ok(())
 }
 
-fn validate_target_platform(target_platform: &str) -> Result<()> {
-    if !Build::is_target_platform_support(target_platform) {
+// target platform declared by any builder
+// test and smoke run execute app binary, require builder host of the target platform
+async fn validate_target_platform(
+    register: &mut Register,
+    target_platform: &str,
+    is_test_required: bool,
+) -> Result<()> {
+    let node_states = register.list_resource::<NodeState>(None, None).await?;
+    let builders = node_states
+        .iter()
+        .filter(|(_, state)| {
+            state.role == NodeRole::Builder && state.accept_target_platform(target_platform)
+        })
+        .collect::<Vec<_>>();
+    if builders.is_empty() {
         return Err(invalid_api_request(format!(
             "target platform '{}' not support",
             target_platform
         )));
     }
+    if is_test_required
+        && !builders
+            .iter()
+            .any(|(_, state)| state.is_host_target_platform(target_platform))
+    {
+        return Err(invalid_api_request(format!(
+            "can not test or smoke run target platform '{}', no builder host of the platform",
+            target_platform
+        )));
+    }
+    Ok(())
+}
+
+// resolved selector, project selector overridden by request selector per key,
+// must match labels of at least one builder
+async fn validate_selector(
+    register: &mut Register,
+    namespace: &str,
+    id: &str,
+    selector: &HashMap<String, String>,
+) -> Result<()> {
+    let key = ResourceKeyBuilder::new()
+        .resource(ResourceType::Project)
+        .namespace(namespace)
+        .id(id)
+        .build();
+    let mut resolved = register
+        .get_json_value::<String, Project>(key, None)
+        .await?
+        .map(|project| project.selector)
+        .unwrap_or_default();
+    resolved.extend(selector.clone());
+    if resolved.is_empty() {
+        return Ok(());
+    }
+    let node_states = register.list_resource::<NodeState>(None, None).await?;
+    let matched = node_states
+        .iter()
+        .any(|(_, state)| state.role == NodeRole::Builder && state.match_labels(&resolved));
+    if !matched {
+        return Err(invalid_api_request(format!(
+            "no builder matches selector '{}'",
+            models::format_labels(&resolved)
+        )));
+    }
     Ok(())
 }
 
@@ -474,6 +729,141 @@ async fn validate_project(register: &mut Register, namespace: &str, id: &str) ->
     }
 }
 
+// pinned Cargo.lock should be published by prior build of the same manifest
+async fn validate_build_options(
+    register: &mut Register,
+    namespace: &str,
+    id: &str,
+    manifest_version: u64,
+    options: Option<&BuildOptions>,
+) -> Result<()> {
+    if let Some(options) = options {
+        for key in options.env.keys() {
+            validate_build_env(key)?;
+        }
+        if let Some(rustflags) = options.rustflags.as_deref() {
+            validate_build_rustflags(rustflags)?;
+        }
+    }
+    let lock_version = match options.and_then(|options| options.lock_version) {
+        Some(lock_version) => lock_version,
+        None => return Ok(()),
+    };
+    let key = ResourceKeyBuilder::new()
+        .resource(ResourceType::SourceMetadata)
+        .namespace(namespace)
+        .id(id)
+        .version(lock_version)
+        .build();
+    let is_exist = register.is_exist(key).await?;
+    if !is_exist {
+        return Err(invalid_api_request(format!(
+            "source of build version {} not found, can not pin Cargo.lock",
+            lock_version
+        )));
+    }
+    let key = ResourceKeyBuilder::new()
+        .resource(ResourceType::BuildMetadata)
+        .namespace(namespace)
+        .id(id)
+        .version(lock_version)
+        .build();
+    let build_metadata = match register
+        .get_json_value::<_, BuildMetadata>(key, None)
+        .await?
+    {
+        Some(build_metadata) => build_metadata,
+        None => {
+            return Err(invalid_api_request(format!(
+                "build version {} not found, can not pin Cargo.lock",
+                lock_version
+            )))
+        }
+    };
+    match build_metadata.manifest_version == Some(manifest_version) {
+        true => Ok(()),
+        false => Err(invalid_api_request(format!(
+            "build version {} not built from manifest version {}, can not pin Cargo.lock",
+            lock_version, manifest_version
+        ))),
+    }
+}
+
+// build options are trusted input, env and rustflags can still run programs on builder, e.g build scripts.
+// denied envs only keep the toolchain, cargo config and linker chosen by builder in effect
+const DENIED_BUILD_ENVS: &[&str] = &[
+    "RUSTC",
+    "RUSTC_WRAPPER",
+    "RUSTC_WORKSPACE_WRAPPER",
+    "RUSTDOC",
+    "RUSTFLAGS",
+    "RUSTDOCFLAGS",
+    "PATH",
+    "HOME",
+    "LD_PRELOAD",
+    "LD_LIBRARY_PATH",
+];
+const DENIED_BUILD_ENV_PREFIXES: &[&str] = &["CARGO_", "RUSTUP_", "DYLD_"];
+
+fn validate_build_env(key: &str) -> Result<()> {
+    if key.is_empty() || key.contains('=') || key.contains('\0') {
+        return Err(invalid_api_request(format!("invalid build env '{}'", key)));
+    }
+    let upper = key.to_uppercase();
+    if DENIED_BUILD_ENVS.contains(&upper.as_str())
+        || DENIED_BUILD_ENV_PREFIXES
+            .iter()
+            .any(|prefix| upper.starts_with(prefix))
+    {
+        return Err(invalid_api_request(format!(
+            "build env '{}' not allowed",
+            key
+        )));
+    }
+    Ok(())
+}
+
+// linker configured per target platform by builder
+fn validate_build_rustflags(rustflags: &str) -> Result<()> {
+    let mut flags = rustflags.split_whitespace();
+    while let Some(flag) = flags.next() {
+        let codegen = match flag {
+            "-C" | "--codegen" => flags.next().unwrap_or_default(),
+            _ => flag
+                .strip_prefix("-C")
+                .or_else(|| flag.strip_prefix("--codegen="))
+                .unwrap_or_default(),
+        };
+        if codegen.split('=').next() == Some("linker") {
+            return Err(invalid_api_request(format!(
+                "rustflags '{}' not allowed, linker configured by builder",
+                flag
+            )));
+        }
+    }
+    Ok(())
+}
+
+// manifest dependencies should satisfy dependency policy of namespace if any
+async fn validate_dependency_policy(
+    register: &mut Register,
+    namespace: &str,
+    buffer: &[u8],
+) -> Result<()> {
+    let key = ResourceKeyBuilder::new()
+        .resource(ResourceType::DependencyPolicy)
+        .id(namespace)
+        .build();
+    let dependency_policy = match register
+        .get_json_value::<_, DependencyPolicy>(key, None)
+        .await?
+    {
+        Some(dependency_policy) => dependency_policy,
+        None => return Ok(()),
+    };
+    dependency_policy.validate_manifest(buffer)
+}
+
 async fn is_manifest_metadata_exist(
     register: &mut Register,
     namespace: &str,
@@ -495,6 +885,16 @@ async fn is_build_metadata_exist(
         .await
 }
 
+async fn is_pending_build_exist(
+    register: &mut Register,
+    namespace: &str,
+    id: &str,
+) -> Result<bool> {
+    register
+        .is_resource_exist::<PendingBuild>(namespace, Some(id))
+        .await
+}
+
 async fn is_catalog_schema_metadata_exist(
     register: &mut Register,
     namespace: &str,
@@ -515,6 +915,26 @@ async fn is_catalogs_metadata_exist(
         .await
 }
 
+async fn is_build_matrix_metadata_exist(
+    register: &mut Register,
+    namespace: &str,
+    id: &str,
+) -> Result<bool> {
+    register
+        .is_resource_exist::<BuildMatrixMetadata>(namespace, Some(id))
+        .await
+}
+
+async fn is_build_matrix_snapshot_exist(
+    register: &mut Register,
+    namespace: &str,
+    id: &str,
+) -> Result<bool> {
+    register
+        .is_resource_exist::<BuildMatrixSnapshot>(namespace, Some(id))
+        .await
+}
+
 async fn is_build_snapshot_exist(
     register: &mut Register,
     namespace: &str,
@@ -560,6 +980,16 @@ async fn is_app_metadata_exist(register: &mut Register, namespace: &str, id: &st
         .await
 }
 
+async fn is_source_metadata_exist(
+    register: &mut Register,
+    namespace: &str,
+    id: &str,
+) -> Result<bool> {
+    register
+        .is_resource_exist::<SourceMetadata>(namespace, Some(id))
+        .await
+}
+
 async fn is_project_exist(register: &mut Register, namespace: &str) -> Result<bool> {
     register.is_resource_exist::<Project>(namespace, None).await
 }
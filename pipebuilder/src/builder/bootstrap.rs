@@ -1,18 +1,24 @@
 use crate::{
-    build::{BuildManager, BuilderService},
-    config::BuilderConfig,
+    build::{BuildManager, BuildRetryPolicy, BuilderService},
+    cache::BuildCachePolicy,
+    config::{BuildCacheConfig, BuildRetryConfig, BuilderConfig},
 };
 use pipebuilder_common::{
-    grpc::client::RepositoryClientBuilder, LocalBuildContextBuilder, Register, Result,
+    build_error, grpc::client::RepositoryClientBuilder, BuildStatus, ErrorKind,
+    LocalBuildContextBuilder, NodeLoadGauge, Period, Register, Result,
+    DEFAULT_BUILD_CACHE_EVICTION_INTERVAL, DEFAULT_BUILD_RETRY_BACKOFF,
+    DEFAULT_BUILD_RETRY_MAX_ATTEMPTS, DEFAULT_MAX_PARALLEL_BUILDS,
 };
 use tracing::info;
 
 pub async fn bootstrap(
     node_id: String,
     external_address: String,
+    linkers: std::collections::HashMap<String, String>,
     config: BuilderConfig,
     lease_id: i64,
     register: Register,
+    load_gauge: NodeLoadGauge,
 ) -> Result<BuilderService> {
     let repository_client_config = config.repository_client;
     let protocol = repository_client_config.protocol;
@@ -31,19 +37,79 @@ pub async fn bootstrap(
     let restore_directory = config.restore_directory;
     let log_directory = config.log_directory;
     let reset = config.reset.unwrap_or(true);
+    let max_parallel_builds = config
+        .max_parallel_builds
+        .unwrap_or(DEFAULT_MAX_PARALLEL_BUILDS);
+    // no build would ever start from local queue
+    if max_parallel_builds == 0 {
+        return Err(build_error(
+            String::from("bootstrap builder"),
+            String::from("max_parallel_builds must be at least 1"),
+        ));
+    }
+    let step_timeout = config.step_timeout.map(Period::into);
+    let build_timeout = config.build_timeout.map(Period::into);
+    let retry_policy = build_retry_policy(config.retry)?;
+    let cache_policy = config.cache.map(build_cache_policy);
+    load_gauge.set_capacity(max_parallel_builds, workspace.clone());
     let build_context = LocalBuildContextBuilder::default()
         .id(node_id)
         .address(external_address)
         .workspace(workspace)
         .restore_directory(restore_directory)
         .log_directory(log_directory)
+        .linkers(linkers)
         .build();
     let manager = BuildManager::builder()
         .lease_id(lease_id)
         .register(register)
         .repository_client(repository_client)
         .context(build_context)
+        .max_parallel_builds(max_parallel_builds)
+        .step_timeout(step_timeout)
+        .build_timeout(build_timeout)
+        .retry_policy(retry_policy)
+        .cache_policy(cache_policy)
+        .load_gauge(load_gauge)
         .build();
     manager.init(reset).await?;
     Ok(BuilderService::new(manager))
 }
+
+fn build_retry_policy(config: Option<BuildRetryConfig>) -> Result<BuildRetryPolicy> {
+    let (max_attempts, backoff, steps, errors) = match config {
+        Some(config) => (
+            config.max_attempts,
+            config.backoff,
+            config.steps,
+            config.errors,
+        ),
+        None => (None, None, None, None),
+    };
+    let errors = errors.unwrap_or_else(|| vec![ErrorKind::Rpc]);
+    // timed out step fails the build, killed processes may still write workspace
+    if errors.contains(&ErrorKind::Timeout) {
+        return Err(build_error(
+            String::from("bootstrap builder"),
+            String::from("timeout errors are not retryable"),
+        ));
+    }
+    Ok(BuildRetryPolicy {
+        max_attempts: max_attempts.unwrap_or(DEFAULT_BUILD_RETRY_MAX_ATTEMPTS),
+        backoff: backoff.unwrap_or(DEFAULT_BUILD_RETRY_BACKOFF).into(),
+        steps: steps.unwrap_or_else(|| vec![BuildStatus::Pull, BuildStatus::Publish]),
+        errors,
+    })
+}
+
+fn build_cache_policy(config: BuildCacheConfig) -> BuildCachePolicy {
+    BuildCachePolicy {
+        max_size: config.max_size,
+        max_entries: config.max_entries,
+        max_idle: config.max_idle.map(Period::into),
+        interval: config
+            .interval
+            .unwrap_or(DEFAULT_BUILD_CACHE_EVICTION_INTERVAL)
+            .into(),
+    }
+}
@@ -1,31 +1,184 @@
+use crate::cache::{BuildCacheManager, BuildCachePolicy};
 use chrono::Utc;
 use flurry::HashMap;
 use pipebuilder_common::{
-    self, build_error, datetime_utc_to_prost_timestamp,
+    self, build_error, get_build_lint,
     grpc::{
         build::{
             builder_server::Builder, BuildCacheMetadata as RpcBuildCacheMetadata, BuildMetadataKey,
             BuildResponse, CancelBuildResponse, DeleteBuildCacheResponse, GetBuildLogResponse,
-            ScanBuildCacheResponse, ScanBuildResponse,
+            ScanBuildCacheResponse, ScanBuildResponse, StreamBuildLogResponse,
         },
         repository::repository_client::RepositoryClient,
     },
-    remove_directory, reset_directory, Build, BuildCacheMetadata, BuildMetadata, BuildSnapshot,
-    BuildStatus, LocalBuildContext, PathBuilder, Register, Snapshot, PATH_APP,
+    remove_directory, remove_resource, reset_directory, timeout_error, Build, BuildMetadata,
+    BuildOptions, BuildSnapshot, BuildStatus, BuildStep, BuildStepOutcome, CatalogsSnapshot,
+    DependencyPolicy, Error, ErrorKind, LocalBuildContext, NodeLoadGauge, PathBuilder,
+    ProcessGroup, Register, Snapshot, DEFAULT_BUILD_LOG_STREAM_PERIOD, PATH_APP,
 };
-use std::sync::Arc;
+use std::{
+    collections::VecDeque,
+    convert::TryFrom,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+use tokio::{fs::File, io::AsyncReadExt, sync::mpsc};
+use tokio_stream::wrappers::ReceiverStream;
 use tonic::{transport::Channel, Response};
 use tracing::{error, info, warn};
 
+const BUILD_LOG_STREAM_CHUNK_SIZE: usize = 4096;
+const BUILD_LOG_STREAM_CHANNEL_SIZE: usize = 16;
+const BUILD_RETRY_MAX_BACKOFF: Duration = Duration::from_secs(600);
+// period to check whether aborted local build thread stopped
+const LOCAL_BUILD_EXIT_CHECK_PERIOD: Duration = Duration::from_millis(100);
+
+// build time limits, no limit if undefined
+#[derive(Clone, Copy)]
+pub struct BuildTimeout {
+    step: Option<Duration>,
+    build: Option<Duration>,
+}
+
+impl BuildTimeout {
+    // time limit of next step given build elapsed time, with the timeout kind
+    fn next_step(&self, elapsed: Duration) -> Option<(Duration, &'static str)> {
+        let step = self.step.map(|step| (step, "step"));
+        let build = self
+            .build
+            .map(|build| (build.saturating_sub(elapsed), "build"));
+        match (step, build) {
+            (Some(step), Some(build)) if build.0 < step.0 => Some(build),
+            (Some(step), _) => Some(step),
+            (None, build) => build,
+        }
+    }
+}
+
+// retry failed build steps
+#[derive(Clone)]
+pub struct BuildRetryPolicy {
+    // max attempts per step including the first run
+    pub max_attempts: u32,
+    // delay before the first retry, doubled per retry
+    pub backoff: Duration,
+    // retryable steps
+    pub steps: Vec<BuildStatus>,
+    // retryable error kinds
+    pub errors: Vec<ErrorKind>,
+}
+
+impl BuildRetryPolicy {
+    fn is_retryable(&self, status: &BuildStatus, attempt: u32, err: &Error) -> bool {
+        if attempt >= self.max_attempts || !self.steps.contains(status) {
+            return false;
+        }
+        // timed out step leaves killed processes writing workspace, never retried
+        if err.is_timeout() {
+            return false;
+        }
+        match err.kind() {
+            Some(kind) => self.errors.contains(&kind),
+            None => false,
+        }
+    }
+
+    // delay before next attempt, given the failed attempt starting from 1
+    fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.backoff
+            .saturating_mul(factor)
+            .min(BUILD_RETRY_MAX_BACKOFF)
+    }
+}
+
+struct QueuedBuild {
+    build: Build,
+    timeline: Vec<BuildStep>,
+    timeout: BuildTimeout,
+    retry_policy: BuildRetryPolicy,
+}
+
+// build in progress
+struct LocalBuild {
+    // build thread abort handle
+    handle: tokio::task::AbortHandle,
+    // process group of cargo build, killed on cancel
+    process_group: ProcessGroup,
+}
+
+// local builds wait for a free slot, in FIFO order
+struct BuildQueue {
+    // max number of builds run in parallel
+    max_parallel_builds: usize,
+    builds: Mutex<VecDeque<QueuedBuild>>,
+    // running and queued builds reported in node heartbeat
+    load_gauge: NodeLoadGauge,
+}
+
+impl BuildQueue {
+    fn new(max_parallel_builds: usize, load_gauge: NodeLoadGauge) -> Self {
+        BuildQueue {
+            max_parallel_builds,
+            builds: Mutex::new(VecDeque::new()),
+            load_gauge,
+        }
+    }
+
+    fn report(&self, running_builds: usize) {
+        let builds = self.builds.lock().expect("lock build queue");
+        self.load_gauge.set_builds(running_builds, builds.len());
+    }
+
+    fn push(&self, build: QueuedBuild) {
+        let mut builds = self.builds.lock().expect("lock build queue");
+        builds.push_back(build);
+    }
+
+    fn contains(&self, key_tuple: &(String, String, u64)) -> bool {
+        let builds = self.builds.lock().expect("lock build queue");
+        builds
+            .iter()
+            .any(|queued| &queued.build.get_build_key_tuple() == key_tuple)
+    }
+
+    // remove queued build, return true if found
+    fn remove(&self, key_tuple: &(String, String, u64)) -> bool {
+        let mut builds = self.builds.lock().expect("lock build queue");
+        let position = builds
+            .iter()
+            .position(|queued| &queued.build.get_build_key_tuple() == key_tuple);
+        match position {
+            Some(position) => builds.remove(position).is_some(),
+            None => false,
+        }
+    }
+
+    fn keys(&self) -> Vec<(String, String, u64)> {
+        let builds = self.builds.lock().expect("lock build queue");
+        builds
+            .iter()
+            .map(|queued| queued.build.get_build_key_tuple())
+            .collect()
+    }
+}
+
 pub struct BuildManager {
     lease_id: i64,
     register: Register,
     repository_client: RepositoryClient<Channel>,
     context: LocalBuildContext,
-    // builds in progress, (namespace, id, version) -> build thread join handle
-    builds: Arc<HashMap<(String, String, u64), tokio::task::JoinHandle<()>>>,
+    // builds in progress, (namespace, id, version) -> local build
+    builds: Arc<HashMap<(String, String, u64), LocalBuild>>,
+    // builds wait to start
+    queue: Arc<BuildQueue>,
     // pre-build caches, (namespace, id, target_platform)
-    caches: Arc<HashMap<(String, String, String), BuildCacheMetadata>>,
+    caches: Arc<BuildCacheManager>,
+    // default time limits, overridable per build
+    step_timeout: Option<Duration>,
+    build_timeout: Option<Duration>,
+    retry_policy: BuildRetryPolicy,
 }
 
 #[derive(Default)]
@@ -34,6 +187,12 @@ pub struct BuildManagerBuilder {
     register: Option<Register>,
     repository_client: Option<RepositoryClient<Channel>>,
     context: Option<LocalBuildContext>,
+    max_parallel_builds: Option<usize>,
+    step_timeout: Option<Duration>,
+    build_timeout: Option<Duration>,
+    retry_policy: Option<BuildRetryPolicy>,
+    cache_policy: Option<BuildCachePolicy>,
+    load_gauge: Option<NodeLoadGauge>,
 }
 
 impl BuildManagerBuilder {
@@ -57,14 +216,56 @@ impl BuildManagerBuilder {
         self
     }
 
+    pub fn max_parallel_builds(mut self, max_parallel_builds: usize) -> Self {
+        self.max_parallel_builds = Some(max_parallel_builds);
+        self
+    }
+
+    pub fn step_timeout(mut self, step_timeout: Option<Duration>) -> Self {
+        self.step_timeout = step_timeout;
+        self
+    }
+
+    pub fn build_timeout(mut self, build_timeout: Option<Duration>) -> Self {
+        self.build_timeout = build_timeout;
+        self
+    }
+
+    pub fn retry_policy(mut self, retry_policy: BuildRetryPolicy) -> Self {
+        self.retry_policy = Some(retry_policy);
+        self
+    }
+
+    pub fn cache_policy(mut self, cache_policy: Option<BuildCachePolicy>) -> Self {
+        self.cache_policy = cache_policy;
+        self
+    }
+
+    pub fn load_gauge(mut self, load_gauge: NodeLoadGauge) -> Self {
+        self.load_gauge = Some(load_gauge);
+        self
+    }
+
     pub fn build(self) -> BuildManager {
+        let max_parallel_builds = self
+            .max_parallel_builds
+            .expect("max parallel builds undefined");
+        let context = self.context.expect("local build context undefined");
+        let caches = BuildCacheManager::new(context.restore_directory.clone(), self.cache_policy);
         BuildManager {
             lease_id: self.lease_id.expect("lease id undefined"),
             register: self.register.expect("register undefined"),
             repository_client: self.repository_client.expect("repository client undefined"),
-            context: self.context.expect("local build context undefined"),
+            context,
             builds: Arc::new(HashMap::new()),
-            caches: Arc::new(HashMap::new()),
+            queue: Arc::new(BuildQueue::new(
+                max_parallel_builds,
+                self.load_gauge.unwrap_or_default(),
+            )),
+            caches: Arc::new(caches),
+            step_timeout: self.step_timeout,
+            build_timeout: self.build_timeout,
+            retry_policy: self.retry_policy.expect("retry policy undefined"),
         }
     }
 }
@@ -86,39 +287,136 @@ impl BuildManager {
             info!(path = log_directory.as_str(), "reset log directory");
             reset_directory(log_directory).await?;
         }
+        self.recover_orphan_builds().await?;
+        // rebuild build cache index from restore directory
+        self.caches.restore().await?;
+        BuildCacheManager::start_eviction(self.caches.clone());
+        Ok(())
+    }
+
+    // fail builds left unfinished by previous run of this builder
+    async fn recover_orphan_builds(&self) -> pipebuilder_common::Result<()> {
+        let mut register = self.register.clone();
+        let lease_id = self.lease_id;
+        let builder_id = self.context.id.as_str();
+        let workspace = self.context.workspace.as_str();
+        let build_metadatas = register.list_resource::<BuildMetadata>(None, None).await?;
+        for (key, mut build_metadata) in build_metadatas {
+            if build_metadata.builder_id != builder_id || build_metadata.is_stopped() {
+                continue;
+            }
+            // namespace/id/version
+            let namespace_id_version = remove_resource::<BuildMetadata>(key.as_str())
+                .split('/')
+                .collect::<Vec<&str>>();
+            let (namespace, id, version) = match namespace_id_version.as_slice() {
+                [namespace, id, version] => match version.parse::<u64>() {
+                    Ok(version) => (*namespace, *id, version),
+                    Err(_) => continue,
+                },
+                _ => continue,
+            };
+            warn!(
+                namespace = namespace,
+                id = id,
+                build_version = version,
+                "fail orphaned build"
+            );
+            build_metadata.end_timeline(BuildStepOutcome::Fail);
+            build_metadata.status = BuildStatus::Fail;
+            build_metadata.message = Some(String::from("builder restarted"));
+            register
+                .put_resource(Some(namespace), id, Some(version), build_metadata, lease_id)
+                .await?;
+            Self::remove_build_workspace(workspace, namespace, id, version).await;
+        }
         Ok(())
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub async fn build(
         &self,
         namespace: &str,
         id: &str,
         manifest_version: u64,
         target_platform: &str,
+        options: BuildOptions,
+        timeout: BuildTimeout,
+        build_version: Option<u64>,
+        selector: std::collections::HashMap<String, String>,
     ) -> pipebuilder_common::Result<u64> {
-        // lock build snapshot with manifest id
-        // update latest build version
         let mut register = self.register.clone();
         let lease_id = self.lease_id;
-        let snapshot = match register
-            .update_snapshot_resource::<BuildSnapshot>(namespace, id, lease_id)
-            .await {
-                Ok((_, snapshot)) => snapshot,
-                Err(err) => return Err(build_error(String::from("update snapshot"), format!("update snapshot failed for (namespace = {}, id = {}, manifest_version = {}, error: {:#?})", namespace, id, manifest_version, err))),
-            };
+        let is_pending = build_version.is_some();
+        let build_version = match build_version {
+            // version allocated while build pending in cluster queue
+            Some(build_version) => build_version,
+            // lock build snapshot with manifest id
+            // update latest build version
+            None => match register
+                .update_snapshot_resource::<BuildSnapshot>(namespace, id, lease_id)
+                .await {
+                    Ok((_, snapshot)) => snapshot.get_version(),
+                    Err(err) => return Err(build_error(String::from("update snapshot"), format!("update snapshot failed for (namespace = {}, id = {}, manifest_version = {}, error: {:#?})", namespace, id, manifest_version, err))),
+                },
+        };
+        // record latest catalogs version as build input
+        let catalogs_version = match register
+            .get_resource::<CatalogsSnapshot>(Some(namespace), id, None, lease_id)
+            .await
+        {
+            Ok(snapshot) => snapshot.map(|snapshot| snapshot.get_version()),
+            Err(err) => {
+                return Err(build_error(
+                    String::from("get catalogs snapshot"),
+                    format!(
+                        "get catalogs snapshot failed for (namespace = {}, id = {}, error: {:#?})",
+                        namespace, id, err
+                    ),
+                ))
+            }
+        };
+        // record dependency policy of namespace
+        let dependency_policy = match register
+            .get_resource::<DependencyPolicy>(None, namespace, None, lease_id)
+            .await
+        {
+            Ok(dependency_policy) => dependency_policy,
+            Err(err) => {
+                return Err(build_error(
+                    String::from("get dependency policy"),
+                    format!(
+                        "get dependency policy failed for (namespace = {}, error: {:#?})",
+                        namespace, err
+                    ),
+                ))
+            }
+        };
+        // lint rule of project, or namespace if project has none
+        let lint = match get_build_lint(&mut register, lease_id, namespace, id).await {
+            Ok(lint) => lint,
+            Err(err) => {
+                return Err(build_error(
+                    String::from("get lint policy"),
+                    format!(
+                        "get lint policy failed for (namespace = {}, id = {}, error: {:#?})",
+                        namespace, id, err
+                    ),
+                ))
+            }
+        };
         // prepare build contexts
-        let build_version = snapshot.get_version();
         let manifest_client = self.repository_client.clone();
         let build_context = self.context.to_owned();
-        // start build
+        // queue build
         info!(
             namespace = namespace,
             id = id,
             manifest_version = manifest_version,
             build_version = build_version,
-            "start build"
+            "queue build"
         );
-        let build = Build::new(
+        let mut build = Build::new(
             namespace.to_owned(),
             id.to_owned(),
             manifest_version,
@@ -127,50 +425,121 @@ impl BuildManager {
             build_context,
             target_platform.to_owned(),
         );
-        let lease_id = self.lease_id;
+        build.set_options(options);
+        build.set_catalogs_version(catalogs_version);
+        build.set_lint(lint);
+        build.set_dependency_policy(dependency_policy);
+        build.set_selector(selector);
+        // keep pending step recorded while build waited in cluster queue
+        let mut timeline = match is_pending {
+            true => match register
+                .get_resource::<BuildMetadata>(Some(namespace), id, Some(build_version), lease_id)
+                .await
+            {
+                Ok(Some(mut build_metadata)) => {
+                    build_metadata.end_timeline(BuildStepOutcome::Succeed);
+                    build_metadata.timeline
+                }
+                Ok(None) => vec![],
+                Err(err) => {
+                    return Err(build_error(
+                        String::from("get build metadata"),
+                        format!(
+                            "get build metadata failed for (namespace = {}, id = {}, version = {}), error: {:#?}",
+                            namespace, id, build_version, err
+                        ),
+                    ))
+                }
+            },
+            false => vec![],
+        };
+        timeline.push(BuildStep::start(BuildStatus::Queued));
+        match Self::update(
+            &mut register,
+            lease_id,
+            &build,
+            BuildStatus::Queued,
+            None,
+            &timeline,
+        )
+        .await
+        {
+            Ok(_) => (),
+            Err(err) => return Err(build_error(String::from("update build metadata"), format!("update build metadata failed for (namespace = {}, id = {}, version = {}), error: {:#?}", namespace, id, build_version, err))),
+        };
+        self.queue.push(QueuedBuild {
+            build,
+            timeline,
+            timeout,
+            retry_policy: self.retry_policy.clone(),
+        });
         let register = self.register.to_owned();
         let builds = self.builds.clone();
         let caches = self.caches.clone();
-        Self::start_build(lease_id, register, builds, build, caches);
+        let queue = self.queue.clone();
+        Self::dispatch(lease_id, register, builds, caches, queue);
         Ok(build_version)
     }
 
+    // request timeout in seconds override builder default
+    pub fn timeout(&self, step_timeout: Option<u64>, build_timeout: Option<u64>) -> BuildTimeout {
+        BuildTimeout {
+            step: step_timeout.map(Duration::from_secs).or(self.step_timeout),
+            build: build_timeout
+                .map(Duration::from_secs)
+                .or(self.build_timeout),
+        }
+    }
+
     pub async fn cancel_build(
         &self,
         namespace: &str,
         id: &str,
         build_version: u64,
     ) -> pipebuilder_common::Result<()> {
-        let builds = self.builds.clone();
+        let mut register = self.register.clone();
+        let lease_id = self.lease_id;
+        let key_tuple = (namespace.to_owned(), id.to_owned(), build_version);
+        // dequeue build not started yet
+        if self.queue.remove(&key_tuple) {
+            self.queue.report(self.builds.pin().len());
+            return match Self::cancel_build_metadata(&mut register, lease_id, namespace, id, build_version).await {
+                Ok(_) => Ok(()),
+                Err(err) => Err(build_error(String::from("update build metadata"), format!("update build metadata failed for (namespace = {}, id = {}, version = {}), error: {:#?}", namespace, id, build_version, err))),
+            };
+        }
         let workspace = self.context.workspace.as_str();
-        // stop local build thread
-        if !Self::cancel_local_build(builds, namespace, id, build_version) {
-            return Err(build_error(
-                String::from("cancel build"),
-                format!(
-                    "local build not found for (namespace = {}, id = {}, build_version = {})",
-                    namespace, id, build_version
-                ),
-            ));
+        // take local build out before kill, so that build thread never complete it or start next queued build
+        let (handle, process_group) =
+            match Self::take_local_build(self.builds.clone(), namespace, id, build_version) {
+                Some(local_build) => local_build,
+                None => {
+                    return Err(build_error(
+                        String::from("cancel build"),
+                        format!(
+                        "local build not found for (namespace = {}, id = {}, build_version = {})",
+                        namespace, id, build_version
+                    ),
+                    ))
+                }
+            };
+        // kill cargo build and wait until exit, so that workspace no longer written
+        process_group.cancel().await;
+        // stop local build thread and wait until stopped, so that cancel is the last status written
+        handle.abort();
+        while !handle.is_finished() {
+            tokio::time::sleep(LOCAL_BUILD_EXIT_CHECK_PERIOD).await;
         }
+        // start next queued build
+        Self::dispatch(
+            lease_id,
+            register.clone(),
+            self.builds.clone(),
+            self.caches.clone(),
+            self.queue.clone(),
+        );
         // cleanup local build workspace
-        let app_path = PathBuilder::default()
-            .push(workspace)
-            .push(namespace)
-            .push(id)
-            .push(build_version.to_string())
-            .push(PATH_APP)
-            .build();
-        if remove_directory(app_path.as_path()).await.is_err() {
-            error!(
-                namespace = namespace,
-                id = id,
-                build_version = build_version,
-                "clean app directory failed"
-            )
-        };
-        let mut register = self.register.clone();
-        let lease_id = self.lease_id;
+        Self::cleanup_workspace(workspace, namespace, id, build_version).await;
         // update metadata
         match Self::cancel_build_metadata(&mut register, lease_id, namespace, id, build_version).await {
             Ok(_) => Ok(()),
@@ -180,15 +549,25 @@ impl BuildManager {
 
     pub fn scan_build(&self) -> Vec<BuildMetadataKey> {
         let builds_ref = self.builds.pin();
-        builds_ref
+        let mut builds = builds_ref
             .keys()
-            .into_iter()
             .map(|(namespace, id, build_version)| BuildMetadataKey {
                 namespace: namespace.to_owned(),
                 id: id.to_owned(),
                 version: build_version.to_owned(),
+                queue_position: None,
             })
-            .collect::<Vec<BuildMetadataKey>>()
+            .collect::<Vec<BuildMetadataKey>>();
+        let queued_builds = self.queue.keys().into_iter().enumerate().map(
+            |(position, (namespace, id, build_version))| BuildMetadataKey {
+                namespace,
+                id,
+                version: build_version,
+                queue_position: Some(position as u64 + 1),
+            },
+        );
+        builds.extend(queued_builds);
+        builds
     }
 
     pub async fn get_build_log(
@@ -204,27 +583,87 @@ impl BuildManager {
         }
     }
 
+    // tail build log until local build stopped
+    pub fn stream_build_log(
+        &self,
+        namespace: &str,
+        id: &str,
+        build_version: u64,
+    ) -> mpsc::Receiver<Result<StreamBuildLogResponse, tonic::Status>> {
+        let log_directory = self.context.log_directory.as_str();
+        let log_path = Build::get_log_path(log_directory, namespace, id, build_version);
+        let key_tuple = (namespace.to_owned(), id.to_owned(), build_version);
+        let builds = self.builds.clone();
+        let queue = self.queue.clone();
+        let (tx, rx) = mpsc::channel(BUILD_LOG_STREAM_CHANNEL_SIZE);
+        tokio::spawn(async move {
+            if let Err(status) = Self::tail_build_log(builds, queue, key_tuple, log_path, &tx).await
+            {
+                let _ = tx.send(Err(status)).await;
+            }
+        });
+        rx
+    }
+
+    async fn tail_build_log(
+        builds: Arc<HashMap<(String, String, u64), LocalBuild>>,
+        queue: Arc<BuildQueue>,
+        key_tuple: (String, String, u64),
+        log_path: PathBuf,
+        tx: &mpsc::Sender<Result<StreamBuildLogResponse, tonic::Status>>,
+    ) -> Result<(), tonic::Status> {
+        let period: Duration = DEFAULT_BUILD_LOG_STREAM_PERIOD.into();
+        let mut file: Option<File> = None;
+        let mut buffer = vec![0; BUILD_LOG_STREAM_CHUNK_SIZE];
+        loop {
+            // check build state before read, so that the last read drain the log
+            let in_progress = builds.pin().contains_key(&key_tuple) || queue.contains(&key_tuple);
+            // build log created once cargo build started
+            if file.is_none() {
+                file = File::open(log_path.as_path()).await.ok();
+            }
+            match file {
+                Some(ref mut file) => loop {
+                    let n = match file.read(&mut buffer).await {
+                        Ok(n) => n,
+                        Err(err) => {
+                            return Err(tonic::Status::internal(format!(
+                                "read build log failed, error: '{}'",
+                                err
+                            )))
+                        }
+                    };
+                    if n == 0 {
+                        break;
+                    }
+                    let response = StreamBuildLogResponse {
+                        buffer: buffer[..n].to_vec(),
+                    };
+                    if tx.send(Ok(response)).await.is_err() {
+                        // client disconnected
+                        return Ok(());
+                    }
+                },
+                None if !in_progress => {
+                    return Err(tonic::Status::not_found("build log not found"));
+                }
+                None => (),
+            };
+            if !in_progress {
+                return Ok(());
+            }
+            tokio::time::sleep(period).await;
+        }
+    }
+
     pub async fn delete_build_cache(
         &self,
         namespace: &str,
         id: &str,
         target_platform: &str,
     ) -> pipebuilder_common::Result<()> {
-        let restore_directory = self.context.restore_directory.as_str();
-        match Build::delete_build_cache(
-            restore_directory,
-            namespace,
-            id,
-            target_platform,
-        )
-        .await
-        {
-            Ok(_) => {
-                // cleanup build cache key set
-                let caches = self.caches.pin();
-                caches.remove(&(namespace.to_owned(), id.to_owned(), target_platform.to_owned()));
-                Ok(())
-            }
+        match self.caches.delete(namespace, id, target_platform).await {
+            Ok(_) => Ok(()),
             Err(err) => {
                 Err(build_error(
                     String::from("delete build cache"),
@@ -236,34 +675,78 @@ impl BuildManager {
     }
 
     pub fn scan_build_cache(&self) -> Vec<RpcBuildCacheMetadata> {
-        let caches = self.caches.pin();
-        caches
-            .into_iter()
-            .map(
-                |((namespace, id, target_platform), metadata)| RpcBuildCacheMetadata {
-                    namespace: namespace.to_owned(),
-                    id: id.to_owned(),
-                    target_platform: target_platform.to_owned(),
-                    timestamp: Some(datetime_utc_to_prost_timestamp(metadata.get_timestamp())),
-                },
-            )
-            .collect::<Vec<RpcBuildCacheMetadata>>()
+        self.caches.scan()
+    }
+
+    // start queued builds while build slot available
+    fn dispatch(
+        lease_id: i64,
+        register: Register,
+        builds: Arc<HashMap<(String, String, u64), LocalBuild>>,
+        caches: Arc<BuildCacheManager>,
+        queue: Arc<BuildQueue>,
+    ) {
+        let mut queued_builds = queue.builds.lock().expect("lock build queue");
+        while builds.pin().len() < queue.max_parallel_builds {
+            let queued_build = match queued_builds.pop_front() {
+                Some(queued_build) => queued_build,
+                None => break,
+            };
+            Self::start_build(
+                lease_id,
+                register.clone(),
+                builds.clone(),
+                queued_build,
+                caches.clone(),
+                queue.clone(),
+            );
+        }
+        queue
+            .load_gauge
+            .set_builds(builds.pin().len(), queued_builds.len());
     }
 
     fn start_build(
         lease_id: i64,
         mut register: Register,
-        builds: Arc<HashMap<(String, String, u64), tokio::task::JoinHandle<()>>>,
-        mut build: Build,
-        caches: Arc<HashMap<(String, String, String), BuildCacheMetadata>>,
+        builds: Arc<HashMap<(String, String, u64), LocalBuild>>,
+        queued_build: QueuedBuild,
+        caches: Arc<BuildCacheManager>,
+        queue: Arc<BuildQueue>,
     ) {
         let builds_clone = builds.clone();
+        let QueuedBuild {
+            mut build,
+            mut timeline,
+            timeout,
+            retry_policy,
+        } = queued_build;
         let key_tuple = build.get_build_key_tuple();
+        let process_group = build.get_process_group();
         let jh = tokio::spawn(async move {
+            // cancelled build left to canceller, never write status or publish anything
+            let group = build.get_process_group();
             let mut status = BuildStatus::Pull;
+            let started_at = Instant::now();
+            let mut attempt = 1;
+            // leave build queue
+            Self::end_step(&mut timeline, BuildStepOutcome::Succeed);
             loop {
+                if group.is_cancelled() {
+                    return;
+                }
+                timeline.push(BuildStep::start_attempt(status.clone(), attempt));
                 // update build status in register
-                match Self::update(&mut register, lease_id, &build, status.clone(), None).await {
+                match Self::update(
+                    &mut register,
+                    lease_id,
+                    &build,
+                    status.clone(),
+                    None,
+                    &timeline,
+                )
+                .await
+                {
                     Ok(()) => (),
                     Err(err) => {
                         let (namespace, id, manifest_version, build_version, target_platform) =
@@ -280,10 +763,55 @@ impl BuildManager {
                         break;
                     }
                 };
-                // run current build state
-                let result = build.run(status.clone()).await;
+                // run current build state, child process killed once timeout
+                let result = match timeout.next_step(started_at.elapsed()) {
+                    Some((limit, kind)) => {
+                        match tokio::time::timeout(limit, build.run(status.clone())).await {
+                            Ok(result) => result,
+                            Err(_) => Err(timeout_error(
+                                format!("run build status '{}'", status.to_string()),
+                                format!("{} timeout after {}s", kind, limit.as_secs()),
+                            )),
+                        }
+                    }
+                    None => build.run(status.clone()).await,
+                };
                 let next_status = match result {
-                    Ok(next_status) => next_status,
+                    Ok(next_status) => {
+                        Self::end_step(&mut timeline, BuildStepOutcome::Succeed);
+                        attempt = 1;
+                        // build cache restored and touched, refresh its recency
+                        if matches!(status, BuildStatus::Create) {
+                            let (namespace, id, target_platform) =
+                                build.get_build_cache_key_tuple();
+                            caches
+                                .refresh(namespace.as_str(), id.as_str(), target_platform.as_str())
+                                .await;
+                        }
+                        next_status
+                    }
+                    Err(_) if group.is_cancelled() => return,
+                    Err(err) if retry_policy.is_retryable(&status, attempt, &err) => {
+                        let (namespace, id, manifest_version, build_version, target_platform) =
+                            build.get_build_meta();
+                        let backoff = retry_policy.backoff(attempt);
+                        warn!(
+                            namespace = namespace.as_str(),
+                            id = id.as_str(),
+                            manifest_version = manifest_version,
+                            build_version = build_version,
+                            target_platform = target_platform.as_str(),
+                            attempt = attempt,
+                            "retry build in {}ms, status: '{}', error: '{:#?}'",
+                            backoff.as_millis(),
+                            status.to_string(),
+                            err
+                        );
+                        Self::end_step(&mut timeline, BuildStepOutcome::Fail);
+                        tokio::time::sleep(backoff).await;
+                        attempt += 1;
+                        continue;
+                    }
                     Err(err) => {
                         let (namespace, id, manifest_version, build_version, target_platform) =
                             build.get_build_meta();
@@ -297,12 +825,48 @@ impl BuildManager {
                             status.to_string(),
                             err
                         );
+                        Self::end_step(&mut timeline, BuildStepOutcome::Fail);
+                        // step dropped by timeout, wait killed processes exit before workspace read or removed
+                        if err.is_timeout() {
+                            build.get_process_group().kill().await;
+                        }
+                        // publish sources generated so far, so failed build can be inspected
+                        if matches!(
+                            status,
+                            BuildStatus::Generate
+                                | BuildStatus::Build
+                                | BuildStatus::Lint
+                                | BuildStatus::Test
+                                | BuildStatus::Publish
+                        ) {
+                            if let Err(err) = build.publish_source().await {
+                                let (namespace, id, _, build_version, _) = build.get_build_meta();
+                                warn!(
+                                    namespace = namespace.as_str(),
+                                    id = id.as_str(),
+                                    build_version = build_version,
+                                    "publish source of failed build fail, error: '{:#?}'",
+                                    err
+                                );
+                            }
+                        }
+                        if err.is_timeout() {
+                            let (namespace, id, _, build_version, _) = build.get_build_meta();
+                            Self::cleanup_workspace(
+                                build.get_workspace(),
+                                namespace,
+                                id,
+                                build_version,
+                            )
+                            .await;
+                        }
                         let _ = Self::update(
                             &mut register,
                             lease_id,
                             &build,
                             BuildStatus::Fail,
                             Some(format!("{}", err)),
+                            &timeline,
                         )
                         .await;
                         break;
@@ -311,23 +875,64 @@ impl BuildManager {
                 // continue next state or exit
                 match next_status {
                     Some(next_status) => status = next_status,
-                    None => break,
+                    None if group.is_cancelled() => return,
+                    None => {
+                        // persist the last step
+                        let _ = Self::update(
+                            &mut register,
+                            lease_id,
+                            &build,
+                            status.clone(),
+                            None,
+                            &timeline,
+                        )
+                        .await;
+                        break;
+                    }
                 }
             }
             let build_key_tuple = build.get_build_key_tuple();
-            // remove local build
-            builds_clone.pin().remove(&build_key_tuple);
             // update build cache key set if build succeed
             if matches!(status, BuildStatus::Succeed) {
-                let build_cache_key_tuple = build.get_build_cache_key_tuple();
-                let build_cache_metadata = BuildCacheMetadata::new();
+                let (namespace, id, target_platform) = build.get_build_cache_key_tuple();
                 caches
-                    .pin()
-                    .insert(build_cache_key_tuple, build_cache_metadata);
+                    .refresh(namespace.as_str(), id.as_str(), target_platform.as_str())
+                    .await;
             }
+            // remove local build and start next queued build
+            Self::complete_build(
+                lease_id,
+                register,
+                builds_clone,
+                caches,
+                queue,
+                &build_key_tuple,
+            );
         });
         // register local build
-        builds.pin().insert(key_tuple, jh);
+        builds.pin().insert(
+            key_tuple,
+            LocalBuild {
+                handle: jh.abort_handle(),
+                process_group,
+            },
+        );
+    }
+
+    fn complete_build(
+        lease_id: i64,
+        register: Register,
+        builds: Arc<HashMap<(String, String, u64), LocalBuild>>,
+        caches: Arc<BuildCacheManager>,
+        queue: Arc<BuildQueue>,
+        key_tuple: &(String, String, u64),
+    ) {
+        {
+            // hold queue lock so that removal never run before registration in dispatch
+            let _queued_builds = queue.builds.lock().expect("lock build queue");
+            builds.pin().remove(key_tuple);
+        }
+        Self::dispatch(lease_id, register, builds, caches, queue);
     }
 
     // update version build status
@@ -337,18 +942,25 @@ impl BuildManager {
         build: &Build,
         status: BuildStatus,
         message: Option<String>,
+        timeline: &[BuildStep],
     ) -> pipebuilder_common::Result<()> {
-        let (namespace, id, _, build_version, target_platform) = build.get_build_meta();
+        let (namespace, id, manifest_version, build_version, target_platform) =
+            build.get_build_meta();
         let (builder_id, builder_address) = build.get_builder_meta();
         let now = Utc::now();
-        let build_metadata = BuildMetadata::new(
+        let mut build_metadata = BuildMetadata::new(
             target_platform.to_owned(),
             status,
             now,
             builder_id.to_owned(),
             builder_address.to_owned(),
             message,
+            timeline.to_vec(),
         );
+        build_metadata.set_options(build.get_options().to_owned());
+        build_metadata.set_inputs(manifest_version, build.get_catalogs_version());
+        build_metadata.set_lint(build.get_lint_outcome());
+        build_metadata.set_policies(build.get_policies());
         register
             .put_resource(
                 Some(namespace.as_str()),
@@ -361,19 +973,72 @@ impl BuildManager {
         Ok(())
     }
 
-    fn cancel_local_build(
-        builds: Arc<HashMap<(String, String, u64), tokio::task::JoinHandle<()>>>,
+    // end the step in progress
+    fn end_step(timeline: &mut [BuildStep], outcome: BuildStepOutcome) {
+        if let Some(step) = timeline.last_mut() {
+            step.end(outcome)
+        }
+    }
+
+    // cleanup local build app directory
+    async fn cleanup_workspace(workspace: &str, namespace: &str, id: &str, build_version: u64) {
+        let app_path = PathBuilder::default()
+            .push(workspace)
+            .push(namespace)
+            .push(id)
+            .push(build_version.to_string())
+            .push(PATH_APP)
+            .build();
+        if remove_directory(app_path.as_path()).await.is_err() {
+            error!(
+                namespace = namespace,
+                id = id,
+                build_version = build_version,
+                "clean app directory failed"
+            )
+        };
+    }
+
+    // remove whole workspace of a build
+    async fn remove_build_workspace(
+        workspace: &str,
+        namespace: &str,
+        id: &str,
+        build_version: u64,
+    ) {
+        let build_workspace = PathBuilder::default()
+            .push(workspace)
+            .push(namespace)
+            .push(id)
+            .push(build_version.to_string())
+            .build();
+        if !build_workspace.exists() {
+            return;
+        }
+        if remove_directory(build_workspace.as_path()).await.is_err() {
+            error!(
+                namespace = namespace,
+                id = id,
+                build_version = build_version,
+                "clean build workspace failed"
+            )
+        };
+    }
+
+    // remove local build, so that build thread never complete it, return handles to stop it
+    fn take_local_build(
+        builds: Arc<HashMap<(String, String, u64), LocalBuild>>,
         namespace: &str,
         id: &str,
         version: u64,
-    ) -> bool {
+    ) -> Option<(tokio::task::AbortHandle, ProcessGroup)> {
         let key_tuple = (namespace.to_owned(), id.to_owned(), version);
         let builds_ref = builds.pin();
         match builds_ref.remove(&key_tuple) {
-            Some(jh) => {
-                jh.abort();
-                true
-            }
+            Some(local_build) => Some((
+                local_build.handle.clone(),
+                local_build.process_group.clone(),
+            )),
             None => {
                 warn!(
                     namespace = namespace,
@@ -381,7 +1046,7 @@ impl BuildManager {
                     build_version = version,
                     "cancel non-exists build"
                 );
-                false
+                None
             }
         }
     }
@@ -408,6 +1073,7 @@ impl BuildManager {
                 return Ok(());
             }
         };
+        build_metadata.end_timeline(BuildStepOutcome::Cancel);
         build_metadata.status = BuildStatus::Cancel;
         register
             .put_resource(Some(namespace), id, Some(version), build_metadata, lease_id)
@@ -438,6 +1104,13 @@ impl Builder for BuilderService {
         let id = request.id;
         let manifest_version = request.manifest_version;
         let target_platform = request.target_platform;
+        let timeout = self
+            .manager
+            .timeout(request.step_timeout, request.build_timeout);
+        let options = match request.options.map(BuildOptions::try_from).transpose() {
+            Ok(options) => options.unwrap_or_default(),
+            Err(err) => return Err(tonic::Status::invalid_argument(format!("{:#?}", err))),
+        };
         info!(
             namespace = namespace.as_str(),
             id = id.as_str(),
@@ -452,6 +1125,10 @@ impl Builder for BuilderService {
                 id.as_str(),
                 manifest_version,
                 target_platform.as_str(),
+                options,
+                timeout,
+                request.build_version,
+                request.selector,
             )
             .await
         {
@@ -538,6 +1215,28 @@ impl Builder for BuilderService {
         }
     }
 
+    type StreamBuildLogStream = ReceiverStream<Result<StreamBuildLogResponse, tonic::Status>>;
+
+    async fn stream_build_log(
+        &self,
+        request: tonic::Request<pipebuilder_common::grpc::build::StreamBuildLogRequest>,
+    ) -> Result<tonic::Response<Self::StreamBuildLogStream>, tonic::Status> {
+        let request = request.into_inner();
+        let namespace = request.namespace;
+        let id = request.id;
+        let version = request.build_version;
+        info!(
+            namespace = namespace.as_str(),
+            id = id.as_str(),
+            build_version = version,
+            "stream build log"
+        );
+        let rx = self
+            .manager
+            .stream_build_log(namespace.as_str(), id.as_str(), version);
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
+
     async fn delete_build_cache(
         &self,
         request: tonic::Request<pipebuilder_common::grpc::build::DeleteBuildCacheRequest>,
@@ -588,3 +1287,60 @@ impl Builder for BuilderService {
         Ok(Response::new(ScanBuildCacheResponse { caches }))
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use super::{BuildRetryPolicy, BUILD_RETRY_MAX_BACKOFF};
+    use pipebuilder_common::{build_error, cargo_error, timeout_error, BuildStatus, ErrorKind};
+    use std::time::Duration;
+
+    fn test_retry_policy() -> BuildRetryPolicy {
+        BuildRetryPolicy {
+            max_attempts: 3,
+            backoff: Duration::from_secs(1),
+            steps: vec![BuildStatus::Pull, BuildStatus::Build],
+            errors: vec![ErrorKind::Rpc, ErrorKind::Cargo],
+        }
+    }
+
+    #[test]
+    fn test_build_retry_policy_is_retryable() {
+        let policy = test_retry_policy();
+        let cargo = || cargo_error("build", 101, String::from("network unreachable"));
+        assert!(policy.is_retryable(&BuildStatus::Build, 1, &cargo()));
+        assert!(policy.is_retryable(&BuildStatus::Build, 2, &cargo()));
+        // max attempts reached
+        assert!(!policy.is_retryable(&BuildStatus::Build, 3, &cargo()));
+        // step not retryable
+        assert!(!policy.is_retryable(&BuildStatus::Publish, 1, &cargo()));
+        // error kind not retryable
+        let timeout = timeout_error(String::from("build"), String::from("step timeout"));
+        assert!(!policy.is_retryable(&BuildStatus::Build, 1, &timeout));
+        // timeout never retried even if configured
+        let mut policy = test_retry_policy();
+        policy.errors.push(ErrorKind::Timeout);
+        assert!(!policy.is_retryable(&BuildStatus::Build, 1, &timeout));
+        // error without kind never retried
+        let build = build_error(String::from("build"), String::from("invalid"));
+        assert!(!policy.is_retryable(&BuildStatus::Build, 1, &build));
+    }
+
+    #[test]
+    fn test_build_retry_policy_backoff() {
+        let policy = test_retry_policy();
+        assert_eq!(Duration::from_secs(1), policy.backoff(0));
+        assert_eq!(Duration::from_secs(1), policy.backoff(1));
+        assert_eq!(Duration::from_secs(2), policy.backoff(2));
+        assert_eq!(Duration::from_secs(8), policy.backoff(4));
+        // capped instead of overflow
+        assert_eq!(BUILD_RETRY_MAX_BACKOFF, policy.backoff(11));
+        assert_eq!(BUILD_RETRY_MAX_BACKOFF, policy.backoff(64));
+        assert_eq!(BUILD_RETRY_MAX_BACKOFF, policy.backoff(u32::MAX));
+        let policy = BuildRetryPolicy {
+            backoff: Duration::MAX,
+            ..test_retry_policy()
+        };
+        assert_eq!(BUILD_RETRY_MAX_BACKOFF, policy.backoff(2));
+    }
+}
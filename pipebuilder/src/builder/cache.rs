@@ -0,0 +1,358 @@
+use chrono::{DateTime, Utc};
+use flurry::HashMap;
+use pipebuilder_common::{
+    datetime_utc_to_prost_timestamp, grpc::build::BuildCacheMetadata as RpcBuildCacheMetadata,
+    Build, BuildCacheMetadata, DEFAULT_BUILD_CACHE_EVICTION_HISTORY,
+};
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tracing::{info, warn};
+
+// (namespace, id, target_platform)
+pub type BuildCacheKey = (String, String, String);
+
+// build cache limits, no limit if not provided
+#[derive(Clone)]
+pub struct BuildCachePolicy {
+    // max total size in byte
+    pub max_size: Option<u64>,
+    // max number of caches
+    pub max_entries: Option<usize>,
+    // max duration since last hit
+    pub max_idle: Option<Duration>,
+    // eviction check interval
+    pub interval: Duration,
+}
+
+impl BuildCachePolicy {
+    // select caches to evict with reason, least recently used first
+    pub fn select(
+        &self,
+        mut caches: Vec<(BuildCacheKey, DateTime<Utc>, u64)>,
+        now: DateTime<Utc>,
+    ) -> Vec<(BuildCacheKey, &'static str)> {
+        caches.sort_by_key(|(_, timestamp, _)| *timestamp);
+        let mut entries = caches.len();
+        let mut size: u64 = caches.iter().map(|(_, _, size)| size).sum();
+        let mut evictions: Vec<(BuildCacheKey, &'static str)> = vec![];
+        for (key, timestamp, cache_size) in caches {
+            let idle = now
+                .signed_duration_since(timestamp)
+                .to_std()
+                .unwrap_or_default();
+            let reason = if self.max_idle.is_some_and(|max_idle| idle > max_idle) {
+                "idle"
+            } else if self
+                .max_entries
+                .is_some_and(|max_entries| entries > max_entries)
+            {
+                "entries"
+            } else if self.max_size.is_some_and(|max_size| size > max_size) {
+                "size"
+            } else {
+                // remaining caches are more recent and within limits
+                break;
+            };
+            entries -= 1;
+            size -= cache_size;
+            evictions.push((key, reason));
+        }
+        evictions
+    }
+}
+
+struct BuildCacheEviction {
+    key: BuildCacheKey,
+    size: u64,
+    timestamp: DateTime<Utc>,
+    reason: String,
+}
+
+// index and evict app build caches in restore directory
+pub struct BuildCacheManager {
+    restore_directory: String,
+    caches: HashMap<BuildCacheKey, BuildCacheMetadata>,
+    // recent evictions, the oldest first
+    evictions: Mutex<VecDeque<BuildCacheEviction>>,
+    policy: Option<BuildCachePolicy>,
+}
+
+impl BuildCacheManager {
+    pub fn new(restore_directory: String, policy: Option<BuildCachePolicy>) -> Self {
+        BuildCacheManager {
+            restore_directory,
+            caches: HashMap::new(),
+            evictions: Mutex::new(VecDeque::new()),
+            policy,
+        }
+    }
+
+    // rebuild cache index from restore directory
+    pub async fn restore(&self) -> pipebuilder_common::Result<()> {
+        let scanned = Build::scan_build_cache(self.restore_directory.as_str()).await?;
+        let caches = self.caches.pin();
+        for (key, metadata) in scanned {
+            caches.insert(key, metadata);
+        }
+        info!(count = caches.len(), "restore build cache index");
+        Ok(())
+    }
+
+    // refresh cache metadata after app restored or stored
+    pub async fn refresh(&self, namespace: &str, id: &str, target_platform: &str) {
+        let key = (
+            namespace.to_owned(),
+            id.to_owned(),
+            target_platform.to_owned(),
+        );
+        match Build::get_build_cache_metadata(
+            self.restore_directory.as_str(),
+            namespace,
+            id,
+            target_platform,
+        )
+        .await
+        {
+            Ok(Some(metadata)) => {
+                self.caches.pin().insert(key, metadata);
+            }
+            Ok(None) => {
+                self.caches.pin().remove(&key);
+            }
+            Err(err) => warn!(
+                namespace = namespace,
+                id = id,
+                target_platform = target_platform,
+                "read build cache metadata failed, error: '{:#?}'",
+                err
+            ),
+        }
+    }
+
+    // delete cache if not locked by build, return whether cache deleted
+    pub async fn delete(
+        &self,
+        namespace: &str,
+        id: &str,
+        target_platform: &str,
+    ) -> pipebuilder_common::Result<bool> {
+        let deleted = Build::delete_build_cache(
+            self.restore_directory.as_str(),
+            namespace,
+            id,
+            target_platform,
+        )
+        .await?;
+        if deleted {
+            self.caches.pin().remove(&(
+                namespace.to_owned(),
+                id.to_owned(),
+                target_platform.to_owned(),
+            ));
+        }
+        Ok(deleted)
+    }
+
+    // caches followed by recent evictions
+    pub fn scan(&self) -> Vec<RpcBuildCacheMetadata> {
+        let caches = self.caches.pin();
+        let mut metadatas = caches
+            .into_iter()
+            .map(
+                |((namespace, id, target_platform), metadata)| RpcBuildCacheMetadata {
+                    namespace: namespace.to_owned(),
+                    id: id.to_owned(),
+                    target_platform: target_platform.to_owned(),
+                    timestamp: Some(datetime_utc_to_prost_timestamp(metadata.get_timestamp())),
+                    size: metadata.get_size(),
+                    eviction: None,
+                },
+            )
+            .collect::<Vec<RpcBuildCacheMetadata>>();
+        let evictions = self.evictions.lock().expect("lock build cache evictions");
+        metadatas.extend(evictions.iter().map(|eviction| {
+            let (namespace, id, target_platform) = &eviction.key;
+            RpcBuildCacheMetadata {
+                namespace: namespace.to_owned(),
+                id: id.to_owned(),
+                target_platform: target_platform.to_owned(),
+                timestamp: Some(datetime_utc_to_prost_timestamp(eviction.timestamp)),
+                size: eviction.size,
+                eviction: Some(eviction.reason.to_owned()),
+            }
+        }));
+        metadatas
+    }
+
+    // evict caches exceeding limits
+    pub async fn evict(&self) {
+        let policy = match self.policy.as_ref() {
+            Some(policy) => policy,
+            None => return,
+        };
+        let caches = self
+            .caches
+            .pin()
+            .iter()
+            .map(|(key, metadata)| {
+                (
+                    key.to_owned(),
+                    metadata.get_timestamp(),
+                    metadata.get_size(),
+                )
+            })
+            .collect::<Vec<(BuildCacheKey, DateTime<Utc>, u64)>>();
+        for (key, reason) in policy.select(caches, Utc::now()) {
+            let size = match self.caches.pin().get(&key) {
+                Some(metadata) => metadata.get_size(),
+                None => continue,
+            };
+            let (namespace, id, target_platform) = &key;
+            match self.delete(namespace, id, target_platform).await {
+                Ok(true) => (),
+                // cache in use, retry next round
+                Ok(false) => continue,
+                Err(err) => {
+                    warn!(
+                        namespace = namespace.as_str(),
+                        id = id.as_str(),
+                        target_platform = target_platform.as_str(),
+                        "evict build cache failed, error: '{:#?}'",
+                        err
+                    );
+                    continue;
+                }
+            };
+            info!(
+                namespace = namespace.as_str(),
+                id = id.as_str(),
+                target_platform = target_platform.as_str(),
+                size = size,
+                reason = reason,
+                "evict build cache"
+            );
+            let mut evictions = self.evictions.lock().expect("lock build cache evictions");
+            if evictions.len() >= DEFAULT_BUILD_CACHE_EVICTION_HISTORY {
+                evictions.pop_front();
+            }
+            evictions.push_back(BuildCacheEviction {
+                key,
+                size,
+                timestamp: Utc::now(),
+                reason: String::from(reason),
+            });
+        }
+    }
+
+    // periodically evict caches if policy provided
+    pub fn start_eviction(manager: Arc<BuildCacheManager>) {
+        let interval = match manager.policy.as_ref() {
+            Some(policy) => policy.interval,
+            None => return,
+        };
+        tokio::spawn(async move {
+            loop {
+                manager.evict().await;
+                tokio::time::sleep(interval).await;
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{BuildCacheKey, BuildCachePolicy};
+    use chrono::{DateTime, Duration, Utc};
+
+    fn test_key(id: &str) -> BuildCacheKey {
+        (
+            String::from("dev"),
+            String::from(id),
+            String::from("x86_64-unknown-linux-gnu"),
+        )
+    }
+
+    fn test_policy(
+        max_size: Option<u64>,
+        max_entries: Option<usize>,
+        max_idle_secs: Option<u64>,
+    ) -> BuildCachePolicy {
+        BuildCachePolicy {
+            max_size,
+            max_entries,
+            max_idle: max_idle_secs.map(std::time::Duration::from_secs),
+            interval: std::time::Duration::from_secs(60),
+        }
+    }
+
+    // caches (id, last hit seconds ago, size) in random order
+    fn test_caches(
+        now: DateTime<Utc>,
+        caches: &[(&str, i64, u64)],
+    ) -> Vec<(BuildCacheKey, DateTime<Utc>, u64)> {
+        caches
+            .iter()
+            .map(|(id, secs_ago, size)| (test_key(id), now - Duration::seconds(*secs_ago), *size))
+            .collect()
+    }
+
+    fn select(
+        policy: &BuildCachePolicy,
+        caches: &[(&str, i64, u64)],
+    ) -> Vec<(String, &'static str)> {
+        let now = Utc::now();
+        policy
+            .select(test_caches(now, caches), now)
+            .into_iter()
+            .map(|((_, id, _), reason)| (id, reason))
+            .collect()
+    }
+
+    #[test]
+    fn test_select_least_recently_used_first() {
+        let caches = [("app0", 10, 100), ("app1", 30, 100), ("app2", 20, 100)];
+        // within limits
+        assert!(select(&test_policy(Some(300), Some(3), Some(60)), &caches).is_empty());
+        assert!(select(&test_policy(None, None, None), &caches).is_empty());
+        // evict until entries within limit
+        assert_eq!(
+            vec![
+                (String::from("app1"), "entries"),
+                (String::from("app2"), "entries")
+            ],
+            select(&test_policy(None, Some(1), None), &caches)
+        );
+        // evict until size within limit
+        assert_eq!(
+            vec![(String::from("app1"), "size")],
+            select(&test_policy(Some(200), None, None), &caches)
+        );
+        // size at threshold kept
+        assert!(select(&test_policy(Some(300), None, None), &caches).is_empty());
+    }
+
+    #[test]
+    fn test_select_idle() {
+        let caches = [("app0", 10, 100), ("app1", 30, 100), ("app2", 20, 100)];
+        // idle caches evicted even within other limits
+        assert_eq!(
+            vec![
+                (String::from("app1"), "idle"),
+                (String::from("app2"), "idle")
+            ],
+            select(&test_policy(Some(300), Some(3), Some(15)), &caches)
+        );
+        // idle preferred as reason, then entries
+        assert_eq!(
+            vec![
+                (String::from("app1"), "idle"),
+                (String::from("app2"), "entries")
+            ],
+            select(&test_policy(None, Some(1), Some(25)), &caches)
+        );
+    }
+}
@@ -1,4 +1,6 @@
-use pipebuilder_common::{grpc::client::RpcClientConfig, BaseConfig};
+use pipebuilder_common::{
+    grpc::client::RpcClientConfig, BaseConfig, BuildStatus, ErrorKind, Period,
+};
 use serde::Deserialize;
 
 #[derive(Deserialize)]
@@ -9,6 +11,40 @@ pub struct BuilderConfig {
     pub log_directory: String,
     // reset directory when bootstrap, default as true if not provided
     pub reset: Option<bool>,
+    // max number of builds run in parallel, queue the rest, default as 2 if not provided
+    pub max_parallel_builds: Option<usize>,
+    // build step timeout, no timeout if not provided
+    pub step_timeout: Option<Period>,
+    // overall build timeout, no timeout if not provided
+    pub build_timeout: Option<Period>,
+    // retry policy of failed build steps
+    pub retry: Option<BuildRetryConfig>,
+    // build cache limits, no eviction if not provided
+    pub cache: Option<BuildCacheConfig>,
+}
+
+#[derive(Deserialize)]
+pub struct BuildCacheConfig {
+    // max total size of build caches in byte
+    pub max_size: Option<u64>,
+    // max number of build caches
+    pub max_entries: Option<usize>,
+    // evict build cache not hit within the period
+    pub max_idle: Option<Period>,
+    // eviction check interval, default as 60 seconds
+    pub interval: Option<Period>,
+}
+
+#[derive(Deserialize)]
+pub struct BuildRetryConfig {
+    // max attempts per step including the first run
+    pub max_attempts: Option<u32>,
+    // delay before the first retry, doubled per retry
+    pub backoff: Option<Period>,
+    // retryable steps, default as pull and publish
+    pub steps: Option<Vec<BuildStatus>>,
+    // retryable error kinds, default as rpc, timeout rejected
+    pub errors: Option<Vec<ErrorKind>>,
 }
 
 #[derive(Deserialize)]
@@ -1,5 +1,6 @@
 mod bootstrap;
 mod build;
+mod cache;
 mod config;
 
 use bootstrap::bootstrap;
@@ -33,9 +34,11 @@ async fn main() -> Result<()> {
     let builder_svc = bootstrap(
         node_id.clone(),
         external_address,
+        node_svc.get_linkers(),
         config.builder,
         lease_id,
         register,
+        node_svc.get_load_gauge(),
     )
     .await?;
     // bootstrap server
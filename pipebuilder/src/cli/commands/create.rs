@@ -1,18 +1,35 @@
 use super::Cmd;
 use crate::ops::{
     do_build,
-    do_namespace::create_namespace,
+    do_namespace::{create_dependency_policy, create_lint_policy, create_namespace},
     do_project::create_project,
     print::{print_record, print_records},
 };
-use pipebuilder_common::{api::client::ApiClient, Result};
+use pipebuilder_common::{
+    api::{
+        client::ApiClient,
+        models::{
+            BuildMatrixRequest, BuildRequest, UpdateDependencyPolicyRequest,
+            UpdateLintPolicyRequest,
+        },
+    },
+    BuildOptions, Result,
+};
 
 use clap::Arg;
+use std::collections::HashMap;
 
 pub fn cmd() -> Cmd {
     Cmd::new("create")
         .about("Create resource")
-        .subcommands(vec![build(), namespace(), project()])
+        .subcommands(vec![
+            build(),
+            build_matrix(),
+            namespace(),
+            project(),
+            dependency_policy(),
+            lint_policy(),
+        ])
 }
 
 pub fn build() -> Cmd {
@@ -39,6 +56,91 @@ pub fn build() -> Cmd {
                 .help("Specify target platform, checkout https://doc.rust-lang.org/nightly/rustc/platform-support.html")
                 .takes_value(true),
         ])
+        .args(build_option_args())
+}
+
+// option args shared by build and build matrix
+fn build_option_args() -> Vec<Arg<'static>> {
+    vec![
+        Arg::new("step-timeout")
+            .long("step-timeout")
+            .help("Specify build step timeout in seconds")
+            .takes_value(true),
+        Arg::new("build-timeout")
+            .long("build-timeout")
+            .help("Specify overall build timeout in seconds")
+            .takes_value(true),
+        Arg::new("profile")
+            .long("profile")
+            .help("Specify cargo profile, release if not provided")
+            .takes_value(true),
+        Arg::new("features")
+            .long("features")
+            .help("Specify comma separated cargo features")
+            .takes_value(true),
+        Arg::new("env")
+            .short('e')
+            .long("env")
+            .help("Specify cargo build environment variable as KEY=VALUE")
+            .takes_value(true)
+            .multiple_occurrences(true),
+        Arg::new("rustflags")
+            .long("rustflags")
+            .help("Specify RUSTFLAGS, e.g '-C target-cpu=native'")
+            .takes_value(true)
+            .allow_hyphen_values(true),
+        Arg::new("locked")
+            .long("locked")
+            .help("Require Cargo.lock up to date"),
+        Arg::new("test")
+            .long("test")
+            .help("Run cargo test before publish"),
+        Arg::new("smoke-run")
+            .long("smoke-run")
+            .help("Specify seconds to run app with latest catalogs before publish")
+            .takes_value(true),
+        Arg::new("strip")
+            .long("strip")
+            .help("Strip symbols of published app"),
+        Arg::new("compression")
+            .long("compression")
+            .help("Specify compression of published app")
+            .takes_value(true)
+            .possible_values(["Zstd", "Gzip"]),
+        Arg::new("lock-from")
+            .long("lock-from")
+            .help("Specify prior build version whose Cargo.lock reused, implies --locked")
+            .takes_value(true),
+        Arg::new("force")
+            .long("force")
+            .help("Rebuild even if an identical build succeeded"),
+        selector_arg(),
+    ]
+}
+
+fn selector_arg() -> Arg<'static> {
+    Arg::new("selector")
+        .short('l')
+        .long("selector")
+        .help("Specify builder label required as KEY=VALUE")
+        .takes_value(true)
+        .multiple_occurrences(true)
+}
+
+// KEY=VALUE pairs of multiple occurrences arg
+fn key_values(args: &clap::ArgMatches, name: &str) -> HashMap<String, String> {
+    args.values_of(name)
+        .map(|pairs| {
+            pairs
+                .map(|pair| {
+                    let (key, value) = pair
+                        .split_once('=')
+                        .unwrap_or_else(|| panic!("invalid {}, expect KEY=VALUE", name));
+                    (key.to_owned(), value.to_owned())
+                })
+                .collect()
+        })
+        .unwrap_or_default()
 }
 
 pub async fn exec_build(client: ApiClient, args: &clap::ArgMatches) -> Result<()> {
@@ -50,14 +152,110 @@ pub async fn exec_build(client: ApiClient, args: &clap::ArgMatches) -> Result<()
         .parse()
         .expect("invalid manifest version");
     let target_platform = args.value_of("target-platform").map(String::from);
-    let response = do_build::build(
-        &client,
-        namespace.to_owned(),
-        id.to_owned(),
+    let (step_timeout, build_timeout, options) = build_options(args);
+    let request = BuildRequest {
+        namespace: namespace.to_owned(),
+        id: id.to_owned(),
         manifest_version,
         target_platform,
-    )
-    .await?;
+        step_timeout,
+        build_timeout,
+        options: Some(options),
+        force: args.is_present("force"),
+        selector: key_values(args, "selector"),
+    };
+    let response = do_build::build(&client, request).await?;
+    print_record(&response);
+    Ok(())
+}
+
+// parse timeouts and cargo build options
+fn build_options(args: &clap::ArgMatches) -> (Option<u64>, Option<u64>, BuildOptions) {
+    let step_timeout = args
+        .value_of("step-timeout")
+        .map(|timeout| timeout.parse().expect("invalid step timeout"));
+    let build_timeout = args
+        .value_of("build-timeout")
+        .map(|timeout| timeout.parse().expect("invalid build timeout"));
+    let options = BuildOptions {
+        profile: args.value_of("profile").map(String::from),
+        features: args
+            .value_of("features")
+            .map(|features| features.split(',').map(String::from).collect())
+            .unwrap_or_default(),
+        env: key_values(args, "env"),
+        rustflags: args.value_of("rustflags").map(String::from),
+        locked: args.is_present("locked"),
+        test: args.is_present("test"),
+        smoke_run: args
+            .value_of("smoke-run")
+            .map(|duration| duration.parse().expect("invalid smoke run duration")),
+        strip: args.is_present("strip"),
+        compression: args
+            .value_of("compression")
+            .map(|compression| compression.parse().expect("invalid compression")),
+        lock_version: args
+            .value_of("lock-from")
+            .map(|version| version.parse().expect("invalid lock build version")),
+    };
+    (step_timeout, build_timeout, options)
+}
+
+pub fn build_matrix() -> Cmd {
+    Cmd::new("matrix")
+        .about("Create build matrix given namespace, project id and target platforms")
+        .args(vec![
+            Arg::new("namespace")
+                .short('n')
+                .help("Specify namespace")
+                .required(true)
+                .takes_value(true),
+            Arg::new("id")
+                .short('i')
+                .help("Specify project id")
+                .required(true)
+                .takes_value(true),
+            Arg::new("version")
+                .short('v')
+                .help("Specify manifest version")
+                .required(true)
+                .takes_value(true),
+            Arg::new("target-platforms")
+                .short('t')
+                .help("Specify comma separated target platforms")
+                .required(true)
+                .takes_value(true),
+        ])
+        .args(build_option_args())
+}
+
+pub async fn exec_build_matrix(client: ApiClient, args: &clap::ArgMatches) -> Result<()> {
+    let namespace = args.value_of("namespace").unwrap();
+    let id = args.value_of("id").unwrap();
+    let manifest_version = args
+        .value_of("version")
+        .unwrap()
+        .parse()
+        .expect("invalid manifest version");
+    let target_platforms = args
+        .value_of("target-platforms")
+        .unwrap()
+        .split(',')
+        .map(String::from)
+        .collect();
+    let (step_timeout, build_timeout, options) = build_options(args);
+    let request = BuildMatrixRequest {
+        namespace: namespace.to_owned(),
+        id: id.to_owned(),
+        manifest_version,
+        target_platforms,
+        step_timeout,
+        build_timeout,
+        options: Some(options),
+        force: args.is_present("force"),
+        selector: key_values(args, "selector"),
+    };
+    let response = do_build::build_matrix(&client, request).await?;
     print_record(&response);
     Ok(())
 }
@@ -80,6 +278,106 @@ pub async fn exec_namespace(client: ApiClient, args: &clap::ArgMatches) -> Resul
     Ok(())
 }
 
+pub fn dependency_policy() -> Cmd {
+    Cmd::new("dependency-policy")
+        .about("Create or replace dependency policy given namespace")
+        .args(vec![
+            Arg::new("namespace")
+                .short('n')
+                .help("Specify namespace")
+                .required(true)
+                .takes_value(true),
+            Arg::new("registries")
+                .long("registries")
+                .help("Specify comma separated allowed registries, e.g crates-io, any registry if not provided")
+                .takes_value(true)
+                .min_values(0),
+            Arg::new("git-hosts")
+                .long("git-hosts")
+                .help("Specify comma separated allowed git hosts, e.g github.com, any host if not provided")
+                .takes_value(true)
+                .min_values(0),
+            Arg::new("banned-crates")
+                .long("banned-crates")
+                .help("Specify comma separated banned crates")
+                .takes_value(true),
+            Arg::new("allow-path")
+                .long("allow-path")
+                .help("Allow path dependencies on builder filesystem"),
+        ])
+}
+
+pub async fn exec_dependency_policy(client: ApiClient, args: &clap::ArgMatches) -> Result<()> {
+    let namespace = args.value_of("namespace").unwrap();
+    let request = UpdateDependencyPolicyRequest {
+        namespace: namespace.to_owned(),
+        registries: comma_separated_values(args, "registries"),
+        git_hosts: comma_separated_values(args, "git-hosts"),
+        banned_crates: comma_separated_values(args, "banned-crates").unwrap_or_default(),
+        allow_path: args.is_present("allow-path"),
+    };
+    let dependency_policy = create_dependency_policy(&client, request).await?;
+    let dependency_policies = vec![dependency_policy];
+    print_records(dependency_policies.as_slice());
+    Ok(())
+}
+
+pub fn lint_policy() -> Cmd {
+    Cmd::new("lint-policy")
+        .about("Create or replace clippy lint policy given namespace or project")
+        .args(vec![
+            Arg::new("namespace")
+                .short('n')
+                .help("Specify namespace")
+                .required(true)
+                .takes_value(true),
+            Arg::new("id")
+                .short('i')
+                .help("Specify project id, apply to all projects in namespace if not provided")
+                .takes_value(true),
+            Arg::new("lints")
+                .long("lints")
+                .help("Specify comma separated clippy lint args, default as -D,warnings")
+                .takes_value(true)
+                .allow_hyphen_values(true),
+            Arg::new("no-block")
+                .long("no-block")
+                .help("Warn only if lint failed, build fails by default"),
+        ])
+}
+
+pub async fn exec_lint_policy(client: ApiClient, args: &clap::ArgMatches) -> Result<()> {
+    let namespace = args.value_of("namespace").unwrap();
+    let request = UpdateLintPolicyRequest {
+        namespace: namespace.to_owned(),
+        id: args.value_of("id").map(String::from),
+        lints: comma_separated_values(args, "lints"),
+        block: args.is_present("no-block").then_some(false),
+    };
+    let lint_policy = create_lint_policy(&client, request).await?;
+    let lint_policies = vec![lint_policy];
+    print_records(lint_policies.as_slice());
+    Ok(())
+}
+
+// values of comma separated arg, empty if arg present without value
+fn comma_separated_values(args: &clap::ArgMatches, name: &str) -> Option<Vec<String>> {
+    if !args.is_present(name) {
+        return None;
+    }
+    let values = args
+        .value_of(name)
+        .map(|values| {
+            values
+                .split(',')
+                .filter(|value| !value.is_empty())
+                .map(String::from)
+                .collect()
+        })
+        .unwrap_or_default();
+    Some(values)
+}
+
 pub fn project() -> Cmd {
     Cmd::new("project")
         .about("Create project given namespace and project id")
@@ -94,13 +392,18 @@ pub fn project() -> Cmd {
                 .help("Specify project id")
                 .required(true)
                 .takes_value(true),
+            selector_arg(),
         ])
 }
 
 pub async fn exec_project(client: ApiClient, args: &clap::ArgMatches) -> Result<()> {
     let namespace = args.value_of("namespace").unwrap();
     let id = args.value_of("id").unwrap();
-    let project = create_project(&client, namespace.to_owned(), id.to_owned()).await?;
+    let selector = match args.is_present("selector") {
+        true => Some(key_values(args, "selector")),
+        false => None,
+    };
+    let project = create_project(&client, namespace.to_owned(), id.to_owned(), selector).await?;
     let projects = vec![project];
     print_records(projects.as_slice());
     Ok(())
@@ -1,11 +1,14 @@
 use super::Cmd;
 use crate::ops::{
     do_app::{delete_app, delete_app_all},
-    do_build::{delete_build, delete_build_all, delete_build_cache},
+    do_build::{
+        delete_build, delete_build_all, delete_build_cache, delete_build_matrix,
+        delete_build_matrix_all,
+    },
     do_catalog_schema::{delete_catalog_schema, delete_catalog_schema_all},
     do_catalogs::{delete_catalogs, delete_catalogs_all},
     do_manifest::{delete_manifest, delete_manifest_all},
-    do_namespace::delete_namespace,
+    do_namespace::{delete_dependency_policy, delete_lint_policy, delete_namespace},
     do_project::delete_project,
 };
 use pipebuilder_common::{api::client::ApiClient, Result};
@@ -18,11 +21,14 @@ pub fn cmd() -> Cmd {
         .subcommands(vec![
             manifest(),
             build(),
+            build_matrix(),
             catalogs(),
             catalog_schema(),
             app(),
             project(),
             namespace(),
+            dependency_policy(),
+            lint_policy(),
             build_cache(),
         ])
 }
@@ -171,6 +177,37 @@ pub async fn exec_build(client: ApiClient, args: &clap::ArgMatches) -> Result<()
     delete_build(&client, namespace.to_owned(), id.to_owned(), build_version).await
 }
 
+pub fn build_matrix() -> Cmd {
+    Cmd::new("matrix")
+        .about("Delete build matrix given namespace, project id and matrix version, if no matrix version provide, all build matrices deleted")
+        .args(vec![
+            Arg::new("namespace")
+                .short('n')
+                .help("Specify namespace")
+                .takes_value(true)
+                .required(true),
+            Arg::new("id")
+                .short('i')
+                .help("Specify project id")
+                .takes_value(true)
+                .required(true),
+            Arg::new("version")
+                .short('v')
+                .help("Specify build matrix version")
+                .takes_value(true),
+        ])
+}
+
+pub async fn exec_build_matrix(client: ApiClient, args: &clap::ArgMatches) -> Result<()> {
+    let namespace = args.value_of("namespace").unwrap();
+    let id = args.value_of("id").unwrap();
+    let matrix_version: u64 = match args.value_of("version") {
+        Some(version) => version.parse().expect("invalid build matrix version"),
+        None => return delete_build_matrix_all(&client, namespace.to_owned(), id.to_owned()).await,
+    };
+    delete_build_matrix(&client, namespace.to_owned(), id.to_owned(), matrix_version).await
+}
+
 pub fn app() -> Cmd {
     Cmd::new("app")
         .about("Delete app binary given namespace, project id and build version, if no build version provide, all app deleted")
@@ -217,6 +254,43 @@ pub async fn exec_namespace(client: ApiClient, args: &clap::ArgMatches) -> Resul
     delete_namespace(&client, id.to_owned()).await
 }
 
+pub fn dependency_policy() -> Cmd {
+    Cmd::new("dependency-policy")
+        .about("Delete dependency policy given namespace")
+        .args(vec![Arg::new("namespace")
+            .short('n')
+            .help("Specify namespace")
+            .required(true)
+            .takes_value(true)])
+}
+
+pub async fn exec_dependency_policy(client: ApiClient, args: &clap::ArgMatches) -> Result<()> {
+    let namespace = args.value_of("namespace").unwrap();
+    delete_dependency_policy(&client, namespace.to_owned()).await
+}
+
+pub fn lint_policy() -> Cmd {
+    Cmd::new("lint-policy")
+        .about("Delete clippy lint policy given namespace or project")
+        .args(vec![
+            Arg::new("namespace")
+                .short('n')
+                .help("Specify namespace")
+                .required(true)
+                .takes_value(true),
+            Arg::new("id")
+                .short('i')
+                .help("Specify project id, namespace policy if not provided")
+                .takes_value(true),
+        ])
+}
+
+pub async fn exec_lint_policy(client: ApiClient, args: &clap::ArgMatches) -> Result<()> {
+    let namespace = args.value_of("namespace").unwrap();
+    let id = args.value_of("id").map(String::from);
+    delete_lint_policy(&client, namespace.to_owned(), id).await
+}
+
 pub fn project() -> Cmd {
     Cmd::new("project")
         .about("Delete project given namespace, project id")
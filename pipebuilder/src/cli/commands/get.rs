@@ -1,13 +1,20 @@
 use super::Cmd;
-use crate::ops::{do_build::get_build_metadata, print::print_records};
+use crate::ops::{
+    do_build::{get_build_matrix, get_build_metadata},
+    do_namespace::{get_dependency_policy, get_lint_policy},
+    print::print_records,
+};
 use pipebuilder_common::{api::client::ApiClient, Result};
 
 use clap::Arg;
 
 pub fn cmd() -> Cmd {
-    Cmd::new("get")
-        .about("Get resource")
-        .subcommands(vec![build()])
+    Cmd::new("get").about("Get resource").subcommands(vec![
+        build(),
+        build_matrix(),
+        dependency_policy(),
+        lint_policy(),
+    ])
 }
 
 pub fn build() -> Cmd {
@@ -42,6 +49,91 @@ pub async fn exec_build(client: ApiClient, args: &clap::ArgMatches) -> Result<()
         .expect("invalid build version");
     let response =
         get_build_metadata(&client, namespace.to_owned(), id.to_owned(), build_version).await?;
+    let timeline = response.timeline.clone();
+    let responses = vec![response];
+    print_records(responses.as_slice());
+    println!();
+    print_records(timeline.as_slice());
+    Ok(())
+}
+
+pub fn build_matrix() -> Cmd {
+    Cmd::new("matrix")
+        .about("Get build matrix given namespace, project id and build matrix version")
+        .args(vec![
+            Arg::new("namespace")
+                .short('n')
+                .help("Specify namespace")
+                .takes_value(true)
+                .required(true),
+            Arg::new("id")
+                .short('i')
+                .help("Specify project id")
+                .takes_value(true)
+                .required(true),
+            Arg::new("version")
+                .short('v')
+                .help("Specify build matrix version")
+                .takes_value(true)
+                .required(true),
+        ])
+}
+
+pub async fn exec_build_matrix(client: ApiClient, args: &clap::ArgMatches) -> Result<()> {
+    let namespace = args.value_of("namespace").unwrap();
+    let id = args.value_of("id").unwrap();
+    let version = args
+        .value_of("version")
+        .unwrap()
+        .parse()
+        .expect("invalid build matrix version");
+    let response = get_build_matrix(&client, namespace.to_owned(), id.to_owned(), version).await?;
+    let builds = response.builds.clone();
+    let responses = vec![response];
+    print_records(responses.as_slice());
+    println!();
+    print_records(builds.as_slice());
+    Ok(())
+}
+
+pub fn dependency_policy() -> Cmd {
+    Cmd::new("dependency-policy")
+        .about("Get dependency policy given namespace")
+        .args(vec![Arg::new("namespace")
+            .short('n')
+            .help("Specify namespace")
+            .takes_value(true)
+            .required(true)])
+}
+
+pub async fn exec_dependency_policy(client: ApiClient, args: &clap::ArgMatches) -> Result<()> {
+    let namespace = args.value_of("namespace").unwrap();
+    let response = get_dependency_policy(&client, namespace.to_owned()).await?;
+    let responses = vec![response];
+    print_records(responses.as_slice());
+    Ok(())
+}
+
+pub fn lint_policy() -> Cmd {
+    Cmd::new("lint-policy")
+        .about("Get clippy lint policy given namespace or project")
+        .args(vec![
+            Arg::new("namespace")
+                .short('n')
+                .help("Specify namespace")
+                .takes_value(true)
+                .required(true),
+            Arg::new("id")
+                .short('i')
+                .help("Specify project id, namespace policy if not provided")
+                .takes_value(true),
+        ])
+}
+
+pub async fn exec_lint_policy(client: ApiClient, args: &clap::ArgMatches) -> Result<()> {
+    let namespace = args.value_of("namespace").unwrap();
+    let id = args.value_of("id").map(String::from);
+    let response = get_lint_policy(&client, namespace.to_owned(), id).await?;
     let responses = vec![response];
     print_records(responses.as_slice());
     Ok(())
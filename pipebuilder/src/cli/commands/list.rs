@@ -1,7 +1,7 @@
 use super::Cmd;
 use crate::ops::{
     do_app::list_app_metadata,
-    do_build::{list_build_metadata, list_build_snapshot},
+    do_build::{list_build_metadata, list_build_snapshot, list_pending_build},
     do_catalog_schema::{list_catalog_schema_metadata, list_catalog_schema_snapshot},
     do_catalogs::{list_catalogs_metadata, list_catalogs_snapshot},
     do_manifest::{list_manifest_metadata, list_manifest_snapshot},
@@ -43,6 +43,9 @@ pub fn build() -> Cmd {
             Arg::new("snapshot")
                 .short('s')
                 .help("Specify build snapshot per project id returned"),
+            Arg::new("pending")
+                .short('p')
+                .help("Specify builds waiting for an available builder returned"),
         ])
 }
 
@@ -61,11 +64,23 @@ async fn exec_build_metadata(client: ApiClient, args: &clap::ArgMatches) -> Resu
     Ok(())
 }
 
+async fn exec_pending_build(client: ApiClient, args: &clap::ArgMatches) -> Result<()> {
+    let namespace = args.value_of("namespace").unwrap();
+    let id = args.value_of("id").map(|id| id.to_owned());
+    let response = list_pending_build(&client, namespace.to_owned(), id).await?;
+    print_records(response.as_slice());
+    Ok(())
+}
+
 pub async fn exec_build(client: ApiClient, args: &clap::ArgMatches) -> Result<()> {
     let is_snapshot = args.is_present("snapshot");
     if is_snapshot {
         return exec_build_snapshot(client, args).await;
     }
+    let is_pending = args.is_present("pending");
+    if is_pending {
+        return exec_pending_build(client, args).await;
+    }
     return exec_build_metadata(client, args).await;
 }
 
@@ -42,17 +42,24 @@ pub async fn exec(
         ("deactivate", "node") => deactivate::exec_node(client, args).await,
         ("shutdown", "node") => shutdown::exec_node(client, args).await,
         ("get", "build") => get::exec_build(client, args).await,
+        ("get", "matrix") => get::exec_build_matrix(client, args).await,
+        ("get", "dependency-policy") => get::exec_dependency_policy(client, args).await,
+        ("get", "lint-policy") => get::exec_lint_policy(client, args).await,
         ("pull", "app") => pull::exec_app(client, args).await,
         ("pull", "catalogs") => pull::exec_catalogs(client, args).await,
         ("pull", "catalog-schema") => pull::exec_catalog_schema(client, args).await,
         ("pull", "manifest") => pull::exec_manifest(client, args).await,
         ("pull", "log") => pull::exec_build_log(client, args).await,
+        ("pull", "source") => pull::exec_source(client, args).await,
         ("push", "catalogs") => push::exec_catalogs(client, args).await,
         ("push", "catalog-schema") => push::exec_catalog_schema(client, args).await,
         ("push", "manifest") => push::exec_manifest(client, args).await,
         ("create", "build") => create::exec_build(client, args).await,
+        ("create", "matrix") => create::exec_build_matrix(client, args).await,
         ("create", "namespace") => create::exec_namespace(client, args).await,
         ("create", "project") => create::exec_project(client, args).await,
+        ("create", "dependency-policy") => create::exec_dependency_policy(client, args).await,
+        ("create", "lint-policy") => create::exec_lint_policy(client, args).await,
         ("list", "build") => list::exec_build(client, args).await,
         ("list", "manifest") => list::exec_manifest(client, args).await,
         ("list", "catalogs") => list::exec_catalogs(client, args).await,
@@ -69,9 +76,12 @@ pub async fn exec(
         ("delete", "build-cache") => delete::exec_build_cache(client, args).await,
         ("delete", "catalog-schema") => delete::exec_catalog_schema(client, args).await,
         ("delete", "catalogs") => delete::exec_catalogs(client, args).await,
+        ("delete", "matrix") => delete::exec_build_matrix(client, args).await,
         ("delete", "manifest") => delete::exec_manifest(client, args).await,
         ("delete", "project") => delete::exec_project(client, args).await,
         ("delete", "namespace") => delete::exec_namespace(client, args).await,
+        ("delete", "dependency-policy") => delete::exec_dependency_policy(client, args).await,
+        ("delete", "lint-policy") => delete::exec_lint_policy(client, args).await,
         _ => unreachable!("unknown cmd ({}, {})", action, resource),
     }
 }
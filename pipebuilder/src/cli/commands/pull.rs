@@ -1,13 +1,16 @@
 use super::Cmd;
 use crate::ops::{
     do_app::pull_app,
-    do_build::pull_build_log,
+    do_build::{get_build_matrix, pull_build_log, stream_build_log},
     do_catalog_schema::pull_catalog_schema,
     do_catalogs::{dump_catalogs, pull_catalogs},
     do_manifest::pull_manifest,
+    do_source::{dump_source, pull_source},
     print::print_utf8,
 };
-use pipebuilder_common::{api::client::ApiClient, write_file, Result};
+use pipebuilder_common::{
+    api::client::ApiClient, digest_error, invalid_api_request, sha256_digest, write_file, Result,
+};
 
 use clap::Arg;
 
@@ -20,6 +23,7 @@ pub fn cmd() -> Cmd {
         catalogs(),
         catalog_schema(),
         manifest(),
+        source(),
     ])
 }
 
@@ -171,7 +175,19 @@ pub fn app() -> Cmd {
                 .short('v')
                 .help("Specify app build version")
                 .takes_value(true)
-                .required(true),
+                .required_unless_present("matrix"),
+            Arg::new("matrix")
+                .short('m')
+                .long("matrix")
+                .help("Specify build matrix version, pull app of given target platform")
+                .takes_value(true)
+                .conflicts_with("version")
+                .requires("target-platform"),
+            Arg::new("target-platform")
+                .short('t')
+                .help("Specify target platform of build matrix")
+                .takes_value(true)
+                .requires("matrix"),
             Arg::new("path")
                 .short('p')
                 .help("Specify app download path")
@@ -180,6 +196,79 @@ pub fn app() -> Cmd {
 }
 
 pub async fn exec_app(client: ApiClient, args: &clap::ArgMatches) -> Result<()> {
+    let namespace = args.value_of("namespace").unwrap();
+    let id = args.value_of("id").unwrap();
+    let build_version = match args.value_of("matrix") {
+        Some(matrix_version) => {
+            let matrix_version = matrix_version
+                .parse()
+                .expect("invalid build matrix version");
+            let target_platform = args.value_of("target-platform").unwrap();
+            let matrix =
+                get_build_matrix(&client, namespace.to_owned(), id.to_owned(), matrix_version)
+                    .await?;
+            match matrix.get_build_version(target_platform) {
+                Some(build_version) => build_version,
+                None => {
+                    return Err(invalid_api_request(format!(
+                        "build not found for target platform '{}' in build matrix",
+                        target_platform
+                    )))
+                }
+            }
+        }
+        None => args
+            .value_of("version")
+            .unwrap()
+            .parse()
+            .expect("invalid build version"),
+    };
+    let path = args.value_of("path").unwrap_or(DEFAULT_APP_DOWNLOAD_PATH);
+    let response = pull_app(&client, namespace.to_owned(), id.to_owned(), build_version).await?;
+    let buffer = response.buffer;
+    // verify app published with digest
+    if let Some(digest) = response.digest {
+        let actual_digest = sha256_digest(buffer.as_slice());
+        if digest != actual_digest {
+            return Err(digest_error(digest, actual_digest));
+        }
+    }
+    let buffer = match response.compression {
+        Some(compression) => compression.decompress(buffer.as_slice())?,
+        None => buffer,
+    };
+    write_file(path, buffer.as_slice()).await?;
+    Ok(())
+}
+
+pub fn source() -> Cmd {
+    Cmd::new("source")
+        .about("Pull app source given namespace, project id and build version")
+        .args(vec![
+            Arg::new("namespace")
+                .short('n')
+                .help("Specify namespace")
+                .required(true)
+                .takes_value(true),
+            Arg::new("id")
+                .short('i')
+                .help("Specify project id")
+                .required(true)
+                .takes_value(true),
+            Arg::new("version")
+                .short('v')
+                .help("Specify app build version")
+                .required(true)
+                .takes_value(true),
+            Arg::new("directory")
+                .short('d')
+                .help("Specify directory where source written into")
+                .required(false)
+                .takes_value(true),
+        ])
+}
+
+pub async fn exec_source(client: ApiClient, args: &clap::ArgMatches) -> Result<()> {
     let namespace = args.value_of("namespace").unwrap();
     let id = args.value_of("id").unwrap();
     let build_version = args
@@ -187,11 +276,13 @@ pub async fn exec_app(client: ApiClient, args: &clap::ArgMatches) -> Result<()>
         .unwrap()
         .parse()
         .expect("invalid build version");
-    let path = args.value_of("").unwrap_or(DEFAULT_APP_DOWNLOAD_PATH);
-    let response = pull_app(&client, namespace.to_owned(), id.to_owned(), build_version).await?;
+    let directory = args.value_of("directory");
+    let response = pull_source(&client, namespace.to_owned(), id.to_owned(), build_version).await?;
     let buffer = response.buffer;
-    write_file(path, buffer.as_slice()).await?;
-    Ok(())
+    match directory {
+        Some(directory) => dump_source(buffer.as_slice(), directory).await,
+        None => print_utf8(buffer),
+    }
 }
 
 pub fn build_log() -> Cmd {
@@ -213,6 +304,10 @@ pub fn build_log() -> Cmd {
                 .help("Specify app build version")
                 .takes_value(true)
                 .required(true),
+            Arg::new("follow")
+                .short('f')
+                .long("follow")
+                .help("Follow build log until build stopped"),
         ])
 }
 
@@ -224,6 +319,9 @@ pub async fn exec_build_log(client: ApiClient, args: &clap::ArgMatches) -> Resul
         .unwrap()
         .parse()
         .expect("invalid build version");
+    if args.is_present("follow") {
+        return stream_build_log(&client, namespace.to_owned(), id.to_owned(), build_version).await;
+    }
     let response =
         pull_build_log(&client, namespace.to_owned(), id.to_owned(), build_version).await?;
     print_utf8(response.buffer)
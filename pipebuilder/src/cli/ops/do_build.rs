@@ -1,31 +1,42 @@
-use super::print::Printer;
+use super::print::{print_bytes, Printer};
 use pipebuilder_common::{
     api::{
         client::ApiClient,
         models::{
-            BuildMetadata, BuildRequest, BuildResponse, BuildSnapshot, CancelBuildRequest,
-            CancelBuildResponse, DeleteBuildCacheRequest, DeleteBuildRequest,
-            DeleteBuildSnapshotRequest, GetBuildLogRequest, GetBuildLogResponse, GetBuildRequest,
-            ListBuildRequest, ListBuildSnapshotRequest,
+            BuildMatrix, BuildMatrixRequest, BuildMatrixResponse, BuildMetadata, BuildRequest,
+            BuildResponse, BuildSnapshot, CancelBuildRequest, CancelBuildResponse,
+            DeleteBuildCacheRequest, DeleteBuildMatrixRequest, DeleteBuildMatrixSnapshotRequest,
+            DeleteBuildRequest, DeleteBuildSnapshotRequest, GetBuildLogRequest,
+            GetBuildLogResponse, GetBuildMatrixRequest, GetBuildRequest, ListBuildRequest,
+            ListBuildSnapshotRequest, PendingBuild,
         },
     },
     Result,
 };
 
-pub(crate) async fn build(
+pub(crate) async fn build(client: &ApiClient, request: BuildRequest) -> Result<BuildResponse> {
+    client.build(&request).await
+}
+
+pub(crate) async fn build_matrix(
+    client: &ApiClient,
+    request: BuildMatrixRequest,
+) -> Result<BuildMatrixResponse> {
+    client.build_matrix(&request).await
+}
+
+pub(crate) async fn get_build_matrix(
     client: &ApiClient,
     namespace: String,
     id: String,
-    manifest_version: u64,
-    target_platform: Option<String>,
-) -> Result<BuildResponse> {
-    let request = BuildRequest {
+    version: u64,
+) -> Result<BuildMatrix> {
+    let request = GetBuildMatrixRequest {
         namespace,
         id,
-        manifest_version,
-        target_platform,
+        version,
     };
-    client.build(&request).await
+    client.get_build_matrix(&request).await
 }
 
 pub(crate) async fn get_build_metadata(
@@ -51,6 +62,15 @@ pub(crate) async fn list_build_metadata(
     client.list_build_metadata(&request).await
 }
 
+pub(crate) async fn list_pending_build(
+    client: &ApiClient,
+    namespace: String,
+    id: Option<String>,
+) -> Result<Vec<PendingBuild>> {
+    let request = ListBuildRequest { namespace, id };
+    client.list_pending_build(&request).await
+}
+
 pub(crate) async fn list_build_snapshot(
     client: &ApiClient,
     namespace: String,
@@ -110,6 +130,24 @@ pub(crate) async fn pull_build_log(
     client.pull_build_log(&request).await
 }
 
+pub(crate) async fn stream_build_log(
+    client: &ApiClient,
+    namespace: String,
+    id: String,
+    version: u64,
+) -> Result<()> {
+    let request = GetBuildLogRequest {
+        namespace,
+        id,
+        version,
+    };
+    let mut response = client.stream_build_log(&request).await?;
+    while let Some(chunk) = response.chunk().await? {
+        print_bytes(chunk.as_ref())?;
+    }
+    Ok(())
+}
+
 pub(crate) async fn delete_build_all(
     client: &ApiClient,
     namespace: String,
@@ -137,6 +175,48 @@ pub(crate) async fn delete_build_all(
     Ok(())
 }
 
+pub(crate) async fn delete_build_matrix(
+    client: &ApiClient,
+    namespace: String,
+    id: String,
+    version: u64,
+) -> Result<()> {
+    let request = DeleteBuildMatrixRequest {
+        namespace,
+        id,
+        version: Some(version),
+    };
+    client.delete_build_matrix(&request).await
+}
+
+pub(crate) async fn delete_build_matrix_all(
+    client: &ApiClient,
+    namespace: String,
+    id: String,
+) -> Result<()> {
+    let mut printer = Printer::new();
+    printer.status(
+        "Deleting",
+        format!("build matrices (namespace = {}, id = {})", namespace, id),
+    )?;
+    let request = DeleteBuildMatrixRequest {
+        namespace: namespace.clone(),
+        id: id.clone(),
+        version: None,
+    };
+    client.delete_build_matrix(&request).await?;
+    // delete build matrix snapshot
+    printer.status(
+        "Deleting",
+        format!(
+            "build matrix snapshot (namespace = {}, id = {})",
+            namespace, id
+        ),
+    )?;
+    let request = DeleteBuildMatrixSnapshotRequest { namespace, id };
+    client.delete_build_matrix_snapshot(&request).await
+}
+
 pub(crate) async fn delete_build_cache(
     client: &ApiClient,
     builder_id: String,
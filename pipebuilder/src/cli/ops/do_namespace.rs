@@ -6,7 +6,12 @@ use super::{
 use pipebuilder_common::{
     api::{
         client::ApiClient,
-        models::{DeleteNamespaceRequest, ListNamespaceRequest, Namespace, UpdateNamespaceRequest},
+        models::{
+            DeleteDependencyPolicyRequest, DeleteLintPolicyRequest, DeleteNamespaceRequest,
+            DependencyPolicy, GetDependencyPolicyRequest, GetLintPolicyRequest, LintPolicy,
+            ListNamespaceRequest, Namespace, UpdateDependencyPolicyRequest,
+            UpdateLintPolicyRequest, UpdateNamespaceRequest,
+        },
     },
     Result,
 };
@@ -37,3 +42,60 @@ pub(crate) async fn delete_namespace(client: &ApiClient, namespace: String) -> R
     let request = DeleteNamespaceRequest { id: namespace };
     client.delete_namespace(&request).await
 }
+
+pub(crate) async fn create_dependency_policy(
+    client: &ApiClient,
+    request: UpdateDependencyPolicyRequest,
+) -> Result<DependencyPolicy> {
+    let dependency_policy = client.update_dependency_policy(&request).await?;
+    Ok(dependency_policy)
+}
+
+pub(crate) async fn get_dependency_policy(
+    client: &ApiClient,
+    namespace: String,
+) -> Result<DependencyPolicy> {
+    let request = GetDependencyPolicyRequest { namespace };
+    let dependency_policy = client.get_dependency_policy(&request).await?;
+    Ok(dependency_policy)
+}
+
+pub(crate) async fn delete_dependency_policy(client: &ApiClient, namespace: String) -> Result<()> {
+    let mut printer = Printer::new();
+    printer.status("Deleting", format!("dependency policy {}", namespace))?;
+    let request = DeleteDependencyPolicyRequest { namespace };
+    client.delete_dependency_policy(&request).await
+}
+
+pub(crate) async fn create_lint_policy(
+    client: &ApiClient,
+    request: UpdateLintPolicyRequest,
+) -> Result<LintPolicy> {
+    let lint_policy = client.update_lint_policy(&request).await?;
+    Ok(lint_policy)
+}
+
+pub(crate) async fn get_lint_policy(
+    client: &ApiClient,
+    namespace: String,
+    id: Option<String>,
+) -> Result<LintPolicy> {
+    let request = GetLintPolicyRequest { namespace, id };
+    let lint_policy = client.get_lint_policy(&request).await?;
+    Ok(lint_policy)
+}
+
+pub(crate) async fn delete_lint_policy(
+    client: &ApiClient,
+    namespace: String,
+    id: Option<String>,
+) -> Result<()> {
+    let mut printer = Printer::new();
+    let target = match id.as_ref() {
+        Some(id) => format!("{}/{}", namespace, id),
+        None => namespace.clone(),
+    };
+    printer.status("Deleting", format!("lint policy {}", target))?;
+    let request = DeleteLintPolicyRequest { namespace, id };
+    client.delete_lint_policy(&request).await
+}
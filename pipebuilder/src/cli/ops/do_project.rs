@@ -1,6 +1,9 @@
 use super::{
-    do_app::delete_app_all, do_build::delete_build_all, do_catalogs::delete_catalogs_all,
-    do_manifest::delete_manifest_all, print::Printer,
+    do_app::delete_app_all,
+    do_build::{delete_build_all, delete_build_matrix_all},
+    do_catalogs::delete_catalogs_all,
+    do_manifest::delete_manifest_all,
+    print::Printer,
 };
 use pipebuilder_common::{
     api::{
@@ -9,13 +12,19 @@ use pipebuilder_common::{
     },
     Result,
 };
+use std::collections::HashMap;
 
 pub(crate) async fn create_project(
     client: &ApiClient,
     namespace: String,
     id: String,
+    selector: Option<HashMap<String, String>>,
 ) -> Result<Project> {
-    let request = UpdateProjectRequest { namespace, id };
+    let request = UpdateProjectRequest {
+        namespace,
+        id,
+        selector,
+    };
     let project = client.update_project(&request).await?;
     Ok(project)
 }
@@ -37,6 +46,7 @@ pub(crate) async fn delete_project(
         format!("project (namespace = {}, id = {})", namespace, id),
     )?;
     delete_app_all(client, namespace.clone(), id.clone()).await?;
+    delete_build_matrix_all(client, namespace.clone(), id.clone()).await?;
     delete_build_all(client, namespace.clone(), id.clone()).await?;
     delete_manifest_all(client, namespace.clone(), id.clone()).await?;
     delete_catalogs_all(client, namespace.clone(), id.clone()).await?;
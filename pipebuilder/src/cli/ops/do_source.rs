@@ -0,0 +1,28 @@
+use pipebuilder_common::{
+    api::{
+        client::ApiClient,
+        models::{GetSourceRequest, GetSourceResponse},
+    },
+    Result, Source,
+};
+
+pub(crate) async fn pull_source(
+    client: &ApiClient,
+    namespace: String,
+    id: String,
+    build_version: u64,
+) -> Result<GetSourceResponse> {
+    let request = GetSourceRequest {
+        namespace,
+        id,
+        build_version,
+    };
+    client.pull_source(&request).await
+}
+
+pub(crate) async fn dump_source<P>(source: &[u8], directory: P) -> Result<()>
+where
+    P: AsRef<std::path::Path>,
+{
+    Source::dump_sources(source, directory).await
+}
@@ -7,4 +7,5 @@ pub(crate) mod do_manifest;
 pub(crate) mod do_namespace;
 pub(crate) mod do_node;
 pub(crate) mod do_project;
+pub(crate) mod do_source;
 pub(crate) mod print;
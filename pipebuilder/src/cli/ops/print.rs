@@ -25,6 +25,13 @@ pub(crate) fn print_utf8(buffer: Vec<u8>) -> Result<()> {
     println!("{}", text);
     Ok(())
 }
+pub(crate) fn print_bytes(buffer: &[u8]) -> Result<()> {
+    let mut stdout = std::io::stdout();
+    stdout.write_all(buffer)?;
+    stdout.flush()?;
+    Ok(())
+}
+
 pub(crate) struct Printer {
     stderr: StandardStream,
 }
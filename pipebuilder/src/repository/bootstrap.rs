@@ -1,4 +1,4 @@
-use pipebuilder_common::{Register, Result};
+use pipebuilder_common::{Register, Result, DEFAULT_REPOSITORY_SOURCE_DIRECTORY};
 
 use crate::config::RepositoryConfig;
 use crate::repository::{RepositoryManager, RepositoryService};
@@ -15,6 +15,11 @@ pub async fn bootstrap(
         .manifest_directory(config.manifest)
         .catalog_schema_directory(config.catalog_schema)
         .catalogs_directory(config.catalogs)
+        .source_directory(
+            config
+                .source
+                .unwrap_or_else(|| String::from(DEFAULT_REPOSITORY_SOURCE_DIRECTORY)),
+        )
         .build();
     let reset = config.reset.unwrap_or(false);
     manager.init(reset).await?;
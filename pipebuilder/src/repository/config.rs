@@ -7,6 +7,8 @@ pub struct RepositoryConfig {
     pub app: String,
     pub catalog_schema: String,
     pub catalogs: String,
+    // source bundle directory, default as /opt/repository/source
+    pub source: Option<String>,
     pub reset: Option<bool>,
 }
 
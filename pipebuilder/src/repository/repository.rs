@@ -1,15 +1,16 @@
 use pipebuilder_common::{
-    self, create_directory,
+    self, create_directory, digest_error,
     grpc::repository::{
         repository_server::Repository, DeleteAppResponse, DeleteCatalogSchemaResponse,
-        DeleteCatalogsResponse, DeleteManifestResponse, GetAppResponse, GetCatalogSchemaResponse,
-        GetCatalogsResponse, GetManifestResponse, PostAppResponse, PutCatalogSchemaResponse,
-        PutCatalogsResponse, PutManifestResponse,
+        DeleteCatalogsResponse, DeleteManifestResponse, DeleteSourceResponse, GetAppResponse,
+        GetCatalogSchemaResponse, GetCatalogsResponse, GetManifestResponse, GetSourceResponse,
+        PostAppResponse, PostSourceResponse, PutCatalogSchemaResponse, PutCatalogsResponse,
+        PutManifestResponse,
     },
-    read_file, repository_error, reset_directory, rpc_internal_error, write_file, AppMetadata,
-    BlobDescriptor, BlobResource, CatalogSchemaMetadata, CatalogSchemaSnapshot, CatalogsMetadata,
-    CatalogsSnapshot, ManifestMetadata, ManifestSnapshot, PathBuilder, Register, Resource,
-    Snapshot, SnapshotDescriptor,
+    read_file, repository_error, reset_directory, rpc_internal_error, sha256_digest, write_file,
+    AppCompression, AppMetadata, BlobDescriptor, BlobResource, CatalogSchemaMetadata,
+    CatalogSchemaSnapshot, CatalogsMetadata, CatalogsSnapshot, ManifestMetadata, ManifestSnapshot,
+    PathBuilder, Register, Resource, Snapshot, SnapshotDescriptor, SourceMetadata,
 };
 use serde::{de::DeserializeOwned, Serialize};
 use std::fs::remove_dir_all;
@@ -20,6 +21,7 @@ pub const TARGET_MANIFEST: &str = "pipe.yml";
 pub const TARGET_APP: &str = "app";
 pub const TARGET_CATALOG_SCHEMA: &str = "schema.json";
 pub const TARGET_CATALOGS: &str = "catalogs.yml";
+pub const TARGET_SOURCE: &str = "source.yml";
 
 #[derive(Default)]
 pub struct RepositoryManagerBuilder {
@@ -33,6 +35,8 @@ pub struct RepositoryManagerBuilder {
     catalog_schema_directory: Option<String>,
     // catalogs directory
     catalogs_directory: Option<String>,
+    // app source bundle directory
+    source_directory: Option<String>,
 }
 
 impl RepositoryManagerBuilder {
@@ -66,6 +70,11 @@ impl RepositoryManagerBuilder {
         self
     }
 
+    pub fn source_directory(mut self, source_directory: String) -> Self {
+        self.source_directory = Some(source_directory);
+        self
+    }
+
     pub fn build(self) -> RepositoryManager {
         RepositoryManager {
             register: self.register.expect("register undefined"),
@@ -80,6 +89,7 @@ impl RepositoryManagerBuilder {
             catalogs_directory: self
                 .catalogs_directory
                 .expect("catalogs directory undefined"),
+            source_directory: self.source_directory.expect("source directory undefined"),
         }
     }
 }
@@ -96,6 +106,8 @@ pub struct RepositoryManager {
     catalog_schema_directory: String,
     // catalogs repository
     catalogs_directory: String,
+    // app source bundle repository
+    source_directory: String,
 }
 
 impl RepositoryManager {
@@ -108,6 +120,7 @@ impl RepositoryManager {
         let manifest_directory = &self.manifest_directory;
         let catalog_schema_directory = &self.catalog_schema_directory;
         let catalogs_directory = &self.catalogs_directory;
+        let source_directory = &self.source_directory;
         if reset {
             info!(path = app_directory.as_str(), "reset app directory");
             reset_directory(app_directory).await?;
@@ -126,6 +139,8 @@ impl RepositoryManager {
                 "reset catalogs directory"
             );
             reset_directory(catalogs_directory).await?;
+            info!(path = source_directory.as_str(), "reset source directory");
+            reset_directory(source_directory).await?;
         }
         Ok(())
     }
@@ -164,6 +179,17 @@ impl RepositoryManager {
         .await
     }
 
+    pub async fn get_app_metadata(
+        &self,
+        resource: BlobDescriptor<'_>,
+    ) -> pipebuilder_common::Result<Option<AppMetadata>> {
+        let mut register = self.register.clone();
+        let (namespace, id, version) = resource.into_tuple();
+        register
+            .get_resource::<AppMetadata>(Some(namespace), id, Some(version), self.lease_id)
+            .await
+    }
+
     pub async fn get_catalog_schema(
         &self,
         resource: BlobDescriptor<'_>,
@@ -198,6 +224,23 @@ impl RepositoryManager {
         .await
     }
 
+    pub async fn get_source(
+        &self,
+        resource: BlobDescriptor<'_>,
+    ) -> pipebuilder_common::Result<Vec<u8>> {
+        let repository = self.source_directory.as_str();
+        let mut register = self.register.clone();
+        let lease_id = self.lease_id;
+        Self::read_resource::<SourceMetadata>(
+            repository,
+            resource,
+            TARGET_SOURCE,
+            &mut register,
+            lease_id,
+        )
+        .await
+    }
+
     pub async fn put_manifest(
         &self,
         resource: SnapshotDescriptor<'_>,
@@ -226,15 +269,74 @@ impl RepositoryManager {
     pub async fn post_app(
         &self,
         resource: BlobDescriptor<'_>,
-        buffer: &[u8],
+        buffer: Vec<u8>,
+        digest: &str,
+        compression: Option<AppCompression>,
     ) -> pipebuilder_common::Result<()> {
+        // verify app transferred intact, digest off runtime workers
+        let (buffer, actual_digest) = tokio::task::spawn_blocking(move || {
+            let digest = sha256_digest(buffer.as_slice());
+            (buffer, digest)
+        })
+        .await
+        .map_err(|err| repository_error(String::from("digest app"), err.to_string()))?;
+        let buffer = buffer.as_slice();
+        if !digest.is_empty() && digest != actual_digest {
+            return Err(digest_error(digest.to_owned(), actual_digest));
+        }
         let mut register = self.register.clone();
         let lease_id = self.lease_id;
         let repository = self.app_directory.as_str();
-        Self::write_resource::<AppMetadata>(
+        let (namespace, id, version) = resource.into_tuple();
+        if let Err(err) =
+            Self::write_target_into_repo(repository, resource, buffer, TARGET_APP).await
+        {
+            return Err(repository_error(
+                format!("write {}", AppMetadata::ty()),
+                format!(
+                    "write {} failed for (namespace = {}, id = {}, version = {}), error: {:#?}",
+                    AppMetadata::ty(),
+                    namespace,
+                    id,
+                    version,
+                    err
+                ),
+            ));
+        }
+        // digest and compression recorded with metadata at once, so pull always verifies
+        let mut app_metadata = AppMetadata::new(buffer.len());
+        app_metadata.set_publish(actual_digest, compression);
+        match register
+            .put_resource(Some(namespace), id, Some(version), app_metadata, lease_id)
+            .await
+        {
+            Ok(_) => Ok(()),
+            Err(err) => Err(repository_error(
+                format!("update {} metadata", AppMetadata::ty()),
+                format!(
+                    "update {} metadata failed for (namespace = {}, id = {}, version = {}), error: {:#?}",
+                    AppMetadata::ty(),
+                    namespace,
+                    id,
+                    version,
+                    err
+                ),
+            )),
+        }
+    }
+
+    pub async fn post_source(
+        &self,
+        resource: BlobDescriptor<'_>,
+        buffer: &[u8],
+    ) -> pipebuilder_common::Result<()> {
+        let mut register = self.register.clone();
+        let lease_id = self.lease_id;
+        let repository = self.source_directory.as_str();
+        Self::write_resource::<SourceMetadata>(
             repository,
             resource,
-            TARGET_APP,
+            TARGET_SOURCE,
             buffer,
             &mut register,
             lease_id,
@@ -310,6 +412,23 @@ impl RepositoryManager {
         Self::delete_resource::<AppMetadata>(repository, resource, &mut register).await
     }
 
+    pub async fn delete_source(
+        &self,
+        resource: BlobDescriptor<'_>,
+    ) -> pipebuilder_common::Result<()> {
+        let mut register = self.register.clone();
+        // none for builds failed before generate or published before
+        let (namespace, id, version) = resource.into_tuple();
+        let source_metadata = register
+            .get_resource::<SourceMetadata>(Some(namespace), id, Some(version), self.lease_id)
+            .await?;
+        if source_metadata.is_none() {
+            return Ok(());
+        }
+        let repository = self.source_directory.as_str();
+        Self::delete_resource::<SourceMetadata>(repository, resource, &mut register).await
+    }
+
     pub async fn delete_catalog_schema(
         &self,
         resource: BlobDescriptor<'_>,
@@ -667,8 +786,36 @@ impl Repository for RepositoryService {
             "get app"
         );
         let resource = BlobDescriptor(namespace.as_str(), id.as_str(), version);
-        match self.manager.get_app(resource).await {
-            Ok(buffer) => Ok(Response::new(GetAppResponse { buffer })),
+        let buffer = match self.manager.get_app(resource).await {
+            Ok(buffer) => buffer,
+            Err(err) => {
+                error!(
+                    namespace = namespace.as_str(),
+                    id = id.as_str(),
+                    build_version = version,
+                    "get app fail, error '{}'",
+                    err
+                );
+                return Err(rpc_internal_error(err));
+            }
+        };
+        match self.manager.get_app_metadata(resource).await {
+            Ok(app_metadata) => {
+                let (digest, compression) = match app_metadata {
+                    Some(app_metadata) => (
+                        app_metadata.digest,
+                        app_metadata
+                            .compression
+                            .map(|compression| compression.to_string()),
+                    ),
+                    None => (None, None),
+                };
+                Ok(Response::new(GetAppResponse {
+                    buffer,
+                    digest,
+                    compression,
+                }))
+            }
             Err(err) => {
                 error!(
                     namespace = namespace.as_str(),
@@ -697,9 +844,18 @@ impl Repository for RepositoryService {
             build_version = version,
             "post app"
         );
-        let buffer = request.buffer.as_slice();
+        let buffer = request.buffer;
+        let digest = request.digest.as_str();
+        let compression = match request.compression.as_deref().map(str::parse).transpose() {
+            Ok(compression) => compression,
+            Err(err) => return Err(rpc_internal_error(err)),
+        };
         let resource = BlobDescriptor(namespace.as_str(), id.as_str(), version);
-        match self.manager.post_app(resource, buffer).await {
+        match self
+            .manager
+            .post_app(resource, buffer, digest, compression)
+            .await
+        {
             Ok(_) => Ok(Response::new(PostAppResponse {})),
             Err(err) => {
                 error!(
@@ -943,4 +1099,104 @@ impl Repository for RepositoryService {
         };
         Ok(Response::new(DeleteCatalogsResponse {}))
     }
+
+    async fn get_source(
+        &self,
+        request: tonic::Request<pipebuilder_common::grpc::repository::GetSourceRequest>,
+    ) -> Result<
+        tonic::Response<pipebuilder_common::grpc::repository::GetSourceResponse>,
+        tonic::Status,
+    > {
+        let request = request.into_inner();
+        let namespace = request.namespace;
+        let id = request.id;
+        let version = request.version;
+        info!(
+            namespace = namespace.as_str(),
+            id = id.as_str(),
+            build_version = version,
+            "get source"
+        );
+        let resource = BlobDescriptor(namespace.as_str(), id.as_str(), version);
+        match self.manager.get_source(resource).await {
+            Ok(buffer) => Ok(Response::new(GetSourceResponse { buffer })),
+            Err(err) => {
+                error!(
+                    namespace = namespace.as_str(),
+                    id = id.as_str(),
+                    build_version = version,
+                    "get source fail, error '{}'",
+                    err
+                );
+                Err(rpc_internal_error(err))
+            }
+        }
+    }
+
+    async fn post_source(
+        &self,
+        request: tonic::Request<pipebuilder_common::grpc::repository::PostSourceRequest>,
+    ) -> Result<
+        tonic::Response<pipebuilder_common::grpc::repository::PostSourceResponse>,
+        tonic::Status,
+    > {
+        let request = request.into_inner();
+        let namespace = request.namespace;
+        let id = request.id;
+        let version = request.version;
+        info!(
+            namespace = namespace.as_str(),
+            id = id.as_str(),
+            build_version = version,
+            "post source"
+        );
+        let buffer = request.buffer.as_slice();
+        let resource = BlobDescriptor(namespace.as_str(), id.as_str(), version);
+        match self.manager.post_source(resource, buffer).await {
+            Ok(_) => Ok(Response::new(PostSourceResponse {})),
+            Err(err) => {
+                error!(
+                    namespace = namespace.as_str(),
+                    id = id.as_str(),
+                    build_version = version,
+                    "post source fail, error '{:#?}'",
+                    err
+                );
+                Err(rpc_internal_error(err))
+            }
+        }
+    }
+
+    async fn delete_source(
+        &self,
+        request: tonic::Request<pipebuilder_common::grpc::repository::DeleteSourceRequest>,
+    ) -> Result<
+        tonic::Response<pipebuilder_common::grpc::repository::DeleteSourceResponse>,
+        tonic::Status,
+    > {
+        let request = request.into_inner();
+        let namespace = request.namespace;
+        let id = request.id;
+        let version = request.version;
+        info!(
+            namespace = namespace.as_str(),
+            id = id.as_str(),
+            build_version = version,
+            "delete source"
+        );
+        let resource = BlobDescriptor(namespace.as_str(), id.as_str(), version);
+        match self.manager.delete_source(resource).await {
+            Ok(_) => Ok(Response::new(DeleteSourceResponse {})),
+            Err(err) => {
+                error!(
+                    namespace = namespace.as_str(),
+                    id = id.as_str(),
+                    build_version = version,
+                    "delete source fail, error '{:#?}'",
+                    err
+                );
+                Err(rpc_internal_error(err))
+            }
+        }
+    }
 }
@@ -1,12 +1,47 @@
 use crate::{
     config::SchedulerConfig,
-    schedule::{ScheduleManager, SchedulerService},
+    schedule::{ScheduleLoadPolicy, ScheduleManager, SchedulerService},
+};
+use pipebuilder_common::{
+    Register, DEFAULT_SCHEDULE_LEADER_CHECK_PERIOD, DEFAULT_SCHEDULE_MAX_BUILD_LOAD,
+    DEFAULT_SCHEDULE_PENDING_BUILD_GRACE, DEFAULT_SCHEDULE_VIRTUAL_NODES,
 };
-use pipebuilder_common::Register;
 
-pub fn bootstrap(_config: SchedulerConfig, register: Register) -> SchedulerService {
-    let manager = ScheduleManager::builder().build();
-    // start builder watcher
-    manager.run(register);
-    SchedulerService::new(manager)
+pub fn bootstrap(
+    config: SchedulerConfig,
+    register: Register,
+    external_address: String,
+    lease_id: i64,
+) -> SchedulerService {
+    let load_policy = ScheduleLoadPolicy {
+        max_build_load: config
+            .max_build_load
+            .unwrap_or(DEFAULT_SCHEDULE_MAX_BUILD_LOAD),
+        max_cpu_load: config.max_cpu_load,
+        min_free_disk: config.min_free_disk,
+    };
+    let manager = ScheduleManager::builder()
+        .vnodes(config.vnodes.unwrap_or(DEFAULT_SCHEDULE_VIRTUAL_NODES))
+        .load_policy(load_policy)
+        .pending_build_grace(
+            config
+                .pending_build_grace
+                .unwrap_or(DEFAULT_SCHEDULE_PENDING_BUILD_GRACE)
+                .into(),
+        )
+        .build();
+    // start builder watcher, standby keeps builders up to date for failover
+    manager.run(register.clone());
+    let scheduler_svc = SchedulerService::new(manager);
+    // campaign for leader, the api talks to leader only
+    let leader_check_period = config
+        .leader_check_period
+        .unwrap_or(DEFAULT_SCHEDULE_LEADER_CHECK_PERIOD);
+    scheduler_svc.campaign(
+        register,
+        external_address,
+        lease_id,
+        leader_check_period.into(),
+    );
+    scheduler_svc
 }
@@ -1,11 +1,25 @@
-use pipebuilder_common::BaseConfig;
+use pipebuilder_common::{BaseConfig, Period};
 use serde::Deserialize;
 
-#[derive(Deserialize)]
-pub struct SchedulerConfig {}
+#[derive(Deserialize, Default)]
+pub struct SchedulerConfig {
+    // virtual nodes per builder on consistent hash ring
+    pub vnodes: Option<usize>,
+    // skip builder with (running + queued) builds per slot at or above, default 1.0
+    pub max_build_load: Option<f64>,
+    // skip builder with one minute load average per cpu above, unlimited if not provided
+    pub max_cpu_load: Option<f64>,
+    // skip builder with free workspace disk below, in bytes, unlimited if not provided
+    pub min_free_disk: Option<u64>,
+    // period to confirm leadership, stop scheduling once lost, default 5s
+    pub leader_check_period: Option<Period>,
+    // fail pending build left without queue entry and builder for longer, default 60s
+    pub pending_build_grace: Option<Period>,
+}
 
 #[derive(Deserialize)]
 pub struct Config {
     pub base: BaseConfig,
-    // pub scheduler: SchedulerConfig,
+    #[serde(default)]
+    pub scheduler: SchedulerConfig,
 }
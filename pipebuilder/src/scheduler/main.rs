@@ -16,8 +16,6 @@ use std::net::SocketAddr;
 use tonic::transport::Server;
 use tracing::{info, instrument};
 
-use crate::config::SchedulerConfig;
-
 #[tokio::main]
 #[instrument]
 async fn main() -> Result<()> {
@@ -25,7 +23,7 @@ async fn main() -> Result<()> {
     info!("read configuration ...");
     let file = open_file(std::env::var(ENV_PIPEBUILDER_CONFIG_FILE)?).await?;
     let config = parse_config::<Config>(file).await?;
-    let (register, node_svc, health_svc, _, shutdown_rx) = bootstrap(config.base).await?;
+    let (register, node_svc, health_svc, lease_svc, shutdown_rx) = bootstrap(config.base).await?;
     let node_id = node_svc.get_id();
     let internal_address = node_svc.get_internal_address();
     let addr: SocketAddr = internal_address.parse()?;
@@ -35,7 +33,12 @@ async fn main() -> Result<()> {
         "run scheduler server ..."
     );
     // bootstrap schedluer services
-    let scheduler_svc = bootstrap::bootstrap(SchedulerConfig {}, register);
+    let scheduler_svc = bootstrap::bootstrap(
+        config.scheduler,
+        register,
+        node_svc.get_external_address(),
+        lease_svc.get_lease_id(),
+    );
     Server::builder()
         .add_service(HealthServer::new(health_svc))
         .add_service(SchedulerServer::new(scheduler_svc))
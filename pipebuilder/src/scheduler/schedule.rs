@@ -2,27 +2,107 @@ use etcd_client::{EventType, WatchStream};
 use flurry::HashMap;
 use pipebuilder_common::{
     deserialize_event,
-    grpc::schedule::{scheduler_server::Scheduler, BuilderInfo, ScheduleResponse},
-    hash_distance, log_event, remove_resource, NodeRole, NodeState, Register, ScheduleDescriptor,
-    ScheduleHash,
+    grpc::{
+        build::BuildRequest,
+        client::{BuilderClientBuilder, RpcProtocolType},
+        schedule::{scheduler_server::Scheduler, BuilderInfo, ReleaseResponse, ScheduleResponse},
+    },
+    log_event, remove_resource, BuildMetadata, BuildStatus, BuildStepOutcome, HashRing, NodeLoad,
+    NodeRole, NodeState, PendingBuild, Register, ScheduleDescriptor, ScheduleHash,
+    ELECTION_SCHEDULER,
 };
-use std::sync::Arc;
+use std::{
+    collections::HashSet,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, RwLock,
+    },
+    time::{Duration, Instant},
+};
+use tokio::sync::Notify;
 use tonic::Response;
 use tracing::{error, info, warn};
 
+// (namespace, id, build version)
+type BuildKey = (String, String, u64);
+
+// thresholds of saturated builder, skipped unless all candidates saturated
+pub struct ScheduleLoadPolicy {
+    pub max_build_load: f64,
+    pub max_cpu_load: Option<f64>,
+    pub min_free_disk: Option<u64>,
+}
+
+impl ScheduleLoadPolicy {
+    fn is_saturated(&self, load: &NodeLoad) -> bool {
+        if load.get_build_load() >= self.max_build_load {
+            return true;
+        }
+        if let (Some(max_cpu_load), Some(cpu_load)) = (self.max_cpu_load, load.cpu_load) {
+            if cpu_load > max_cpu_load {
+                return true;
+            }
+        }
+        if let (Some(min_free_disk), Some(free_disk)) = (self.min_free_disk, load.free_disk) {
+            if free_disk < min_free_disk {
+                return true;
+            }
+        }
+        false
+    }
+}
+
 #[derive(Default)]
-pub struct ScheduleManagerBuilder {}
+pub struct ScheduleManagerBuilder {
+    vnodes: Option<usize>,
+    load_policy: Option<ScheduleLoadPolicy>,
+    pending_build_grace: Option<Duration>,
+}
 
 impl ScheduleManagerBuilder {
+    pub fn vnodes(mut self, vnodes: usize) -> Self {
+        self.vnodes = Some(vnodes);
+        self
+    }
+
+    pub fn load_policy(mut self, load_policy: ScheduleLoadPolicy) -> Self {
+        self.load_policy = Some(load_policy);
+        self
+    }
+
+    pub fn pending_build_grace(mut self, pending_build_grace: Duration) -> Self {
+        self.pending_build_grace = Some(pending_build_grace);
+        self
+    }
+
     pub fn build(self) -> ScheduleManager {
         ScheduleManager {
             builders: Arc::new(HashMap::new()),
+            ring: Arc::new(RwLock::new(HashRing::new(
+                self.vnodes.expect("virtual nodes undefined"),
+            ))),
+            load_policy: self.load_policy.expect("load policy undefined"),
+            builder_active: Arc::new(Notify::new()),
+            dispatched: Arc::new(Mutex::new(std::collections::HashMap::new())),
+            pending_build_grace: self
+                .pending_build_grace
+                .expect("pending build grace undefined"),
         }
     }
 }
 
 pub struct ScheduleManager {
+    // builder id -> node state
     builders: Arc<HashMap<String, NodeState>>,
+    // consistent hash ring of builder ids
+    ring: Arc<RwLock<HashRing>>,
+    load_policy: ScheduleLoadPolicy,
+    // notified once active builder state received
+    builder_active: Arc<Notify>,
+    // builder id -> builds scheduled since its last reported load
+    dispatched: Arc<Mutex<std::collections::HashMap<String, usize>>>,
+    // duration a pending build left without queue entry and builder before failed
+    pending_build_grace: Duration,
 }
 
 impl ScheduleManager {
@@ -34,12 +114,20 @@ impl ScheduleManager {
         &self,
         schedule: ScheduleDescriptor<'_>,
         target_platform: Option<&str>,
+        selector: &std::collections::HashMap<String, String>,
     ) -> Option<BuilderInfo> {
         let request_key = schedule.schedule_hash();
         let builders_ref = self.builders.pin();
-        let mut selected_builder_info: Option<BuilderInfo> = None;
-        let mut min_hash_distance: u64 = u64::MAX;
-        for (builder_key, builder) in builders_ref.iter() {
+        let ring = self.ring.read().expect("lock hash ring");
+        let mut dispatched = self.dispatched.lock().expect("lock dispatched builds");
+        // least loaded builder, if all saturated
+        let mut fallback: Option<(f64, BuilderInfo)> = None;
+        // walk ring clockwise, the first builder not saturated selected for compilation cache hit
+        for builder_id in ring.walk(request_key.as_str()) {
+            let builder = match builders_ref.get(builder_id) {
+                Some(builder) => builder,
+                None => continue,
+            };
             if !builder.is_active() {
                 continue;
             }
@@ -48,20 +136,59 @@ impl ScheduleManager {
                     continue;
                 }
             }
-            let builder_id = remove_resource::<NodeState>(builder_key).to_owned();
-            let distance = hash_distance(&request_key, &builder_id);
-            if distance < min_hash_distance {
-                selected_builder_info = Self::builder_info(builder_id, builder);
-                if selected_builder_info.is_some() {
-                    min_hash_distance = distance;
+            if !builder.match_labels(selector) {
+                continue;
+            }
+            let builder_info =
+                match Self::builder_info(builder_id.to_owned(), builder, target_platform) {
+                    Some(builder_info) => builder_info,
+                    None => continue,
+                };
+            // builder without load reported never saturated
+            let load = match builder.load.as_ref() {
+                Some(load) => Self::unreported_load(load, dispatched.get(builder_id)),
+                None => {
+                    *dispatched.entry(builder_id.to_owned()).or_default() += 1;
+                    return Some(builder_info);
                 }
+            };
+            if !self.load_policy.is_saturated(&load) {
+                *dispatched.entry(builder_id.to_owned()).or_default() += 1;
+                return Some(builder_info);
             }
+            let build_load = load.get_build_load();
+            if fallback
+                .as_ref()
+                .is_none_or(|(min_load, _)| build_load < *min_load)
+            {
+                fallback = Some((build_load, builder_info));
+            }
+        }
+        let (_, builder_info) = fallback?;
+        *dispatched.entry(builder_info.id.clone()).or_default() += 1;
+        Some(builder_info)
+    }
+
+    // undo a schedule whose build never reached the builder
+    fn release(&self, builder_id: &str) {
+        let mut dispatched = self.dispatched.lock().expect("lock dispatched builds");
+        if let Some(count) = dispatched.get_mut(builder_id) {
+            *count = count.saturating_sub(1);
         }
-        selected_builder_info
+    }
+
+    // reported load with builds scheduled after the report queued
+    fn unreported_load(load: &NodeLoad, dispatched: Option<&usize>) -> NodeLoad {
+        let mut load = load.clone();
+        load.queued_builds += dispatched.copied().unwrap_or_default();
+        load
     }
 
     pub fn run(&self, mut register: Register) {
         let builders = self.builders.clone();
+        let ring = self.ring.clone();
+        let builder_active = self.builder_active.clone();
+        let dispatched = self.dispatched.clone();
         let _ = tokio::spawn(async move {
             let (watcher, stream) = match register.watch_nodes().await {
                 Ok((watcher, stream)) => (watcher, stream),
@@ -72,7 +199,15 @@ impl ScheduleManager {
             };
             let watcher_id = watcher.watch_id();
             info!("create watcher {}", watcher_id);
-            match Self::watch(stream, builders.clone()).await {
+            match Self::watch(
+                stream,
+                builders.clone(),
+                ring.clone(),
+                dispatched.clone(),
+                builder_active,
+            )
+            .await
+            {
                 Ok(_) => {
                     info!(watcher_id = watcher_id, "watcher exit ...")
                 }
@@ -82,6 +217,8 @@ impl ScheduleManager {
             };
             // cleanup if stop watching
             builders.pin().clear();
+            ring.write().expect("lock hash ring").clear();
+            dispatched.lock().expect("lock dispatched builds").clear();
         });
     }
 
@@ -89,6 +226,9 @@ impl ScheduleManager {
     async fn watch(
         mut stream: WatchStream,
         builders: Arc<HashMap<String, NodeState>>,
+        ring: Arc<RwLock<HashRing>>,
+        dispatched: Arc<Mutex<std::collections::HashMap<String, usize>>>,
+        builder_active: Arc<Notify>,
     ) -> pipebuilder_common::Result<()> {
         while let Some(resp) = stream.message().await? {
             for event in resp.events() {
@@ -97,11 +237,17 @@ impl ScheduleManager {
                     deserialize_event::<NodeState>(event)?
                 {
                     let builders_ref = builders.pin();
+                    let node_id = remove_resource::<NodeState>(node_key.as_str()).to_owned();
                     let node_state = match event_ty {
                         EventType::Put => node_state,
                         EventType::Delete => {
                             // delete event return node_state as None, delete anyway
-                            builders_ref.remove(&node_key);
+                            builders_ref.remove(&node_id);
+                            ring.write().expect("lock hash ring").remove(&node_id);
+                            dispatched
+                                .lock()
+                                .expect("lock dispatched builds")
+                                .remove(&node_id);
                             continue;
                         }
                     };
@@ -117,7 +263,18 @@ impl ScheduleManager {
                     };
                     // collect builder only
                     if node_state.role == NodeRole::Builder {
-                        builders_ref.insert(node_key, node_state);
+                        let is_active = node_state.is_active();
+                        builders_ref.insert(node_id.clone(), node_state);
+                        ring.write().expect("lock hash ring").add(&node_id);
+                        // heartbeat load includes builds scheduled so far
+                        dispatched
+                            .lock()
+                            .expect("lock dispatched builds")
+                            .remove(&node_id);
+                        // builder heartbeat keep pending builds retried
+                        if is_active {
+                            builder_active.notify_one();
+                        }
                     }
                 }
             }
@@ -125,10 +282,256 @@ impl ScheduleManager {
         Ok(())
     }
 
-    fn builder_info(id: String, state: &NodeState) -> Option<BuilderInfo> {
+    // dispatch pending builds whenever builder active, until leadership lost
+    pub async fn run_dispatch(
+        &self,
+        mut register: Register,
+        lease_id: i64,
+        leader: &AtomicBool,
+        term_end: &Notify,
+    ) {
+        // pending builds found lost, with the time first found
+        let mut lost_builds: std::collections::HashMap<BuildKey, Instant> =
+            std::collections::HashMap::new();
+        while leader.load(Ordering::Acquire) {
+            if let Err(e) = self.dispatch(&mut register, lease_id, leader).await {
+                error!("dispatch pending builds fail, error '{}'", e)
+            }
+            if let Err(e) = self
+                .reconcile(&mut register, lease_id, &mut lost_builds)
+                .await
+            {
+                error!("reconcile pending builds fail, error '{}'", e)
+            }
+            tokio::select! {
+                _ = self.builder_active.notified() => (),
+                _ = tokio::time::sleep(self.pending_build_grace) => (),
+                _ = term_end.notified() => (),
+            }
+        }
+    }
+
+    // fail pending builds whose queue entry lost before dispatched to a builder,
+    // e.g scheduler or api crashed between the two writes
+    async fn reconcile(
+        &self,
+        register: &mut Register,
+        lease_id: i64,
+        lost_builds: &mut std::collections::HashMap<BuildKey, Instant>,
+    ) -> pipebuilder_common::Result<()> {
+        let queued =
+            Self::dispatch_order(register.list_resource::<PendingBuild>(None, None).await?)
+                .into_iter()
+                .map(|(key, _)| key)
+                .collect::<HashSet<BuildKey>>();
+        let builds = register
+            .list_resource::<BuildMetadata>(None, None)
+            .await?
+            .into_iter()
+            .filter_map(|(key, build_metadata)| {
+                let key = remove_resource::<BuildMetadata>(key.as_str());
+                let (namespace, id, version) = Self::parse_build_key(key)?;
+                Some((
+                    (namespace.to_owned(), id.to_owned(), version),
+                    build_metadata,
+                ))
+            })
+            .collect::<Vec<_>>();
+        let expired = Self::select_lost_builds(
+            builds,
+            &queued,
+            lost_builds,
+            Instant::now(),
+            self.pending_build_grace,
+        );
+        for (namespace, id, version) in expired {
+            let namespace = namespace.as_str();
+            let id = id.as_str();
+            // skip if dispatched or requeued since listed
+            let mut build_metadata = match register
+                .get_resource::<BuildMetadata>(Some(namespace), id, Some(version), lease_id)
+                .await?
+            {
+                Some(build_metadata) if Self::is_unassigned(&build_metadata) => build_metadata,
+                _ => continue,
+            };
+            if register
+                .get_resource::<PendingBuild>(Some(namespace), id, Some(version), lease_id)
+                .await?
+                .is_some()
+            {
+                continue;
+            }
+            build_metadata.status = BuildStatus::Fail;
+            build_metadata.timestamp = chrono::Utc::now();
+            build_metadata.message = Some(String::from("pending build lost before dispatched"));
+            build_metadata.end_timeline(BuildStepOutcome::Fail);
+            register
+                .put_resource(Some(namespace), id, Some(version), build_metadata, lease_id)
+                .await?;
+            warn!(
+                namespace = namespace,
+                id = id,
+                build_version = version,
+                "fail lost pending build"
+            );
+        }
+        Ok(())
+    }
+
+    // keep tracking unassigned pending builds without queue entry, return those lost beyond grace
+    fn select_lost_builds(
+        builds: Vec<(BuildKey, BuildMetadata)>,
+        queued: &HashSet<BuildKey>,
+        lost_builds: &mut std::collections::HashMap<BuildKey, Instant>,
+        now: Instant,
+        grace: Duration,
+    ) -> Vec<BuildKey> {
+        let mut tracked = std::collections::HashMap::new();
+        let mut expired = vec![];
+        for (key, build_metadata) in builds {
+            if !Self::is_unassigned(&build_metadata) || queued.contains(&key) {
+                continue;
+            }
+            let found_at = lost_builds.get(&key).copied().unwrap_or(now);
+            if now.duration_since(found_at) >= grace {
+                expired.push(key);
+            } else {
+                tracked.insert(key, found_at);
+            }
+        }
+        *lost_builds = tracked;
+        expired
+    }
+
+    fn is_unassigned(build_metadata: &BuildMetadata) -> bool {
+        build_metadata.status == BuildStatus::Pending && build_metadata.builder_id.is_empty()
+    }
+
+    async fn dispatch(
+        &self,
+        register: &mut Register,
+        lease_id: i64,
+        leader: &AtomicBool,
+    ) -> pipebuilder_common::Result<()> {
+        let pending_builds = register.list_resource::<PendingBuild>(None, None).await?;
+        for ((namespace, id, version), pending_build) in Self::dispatch_order(pending_builds) {
+            let namespace = namespace.as_str();
+            let id = id.as_str();
+            let schedule = ScheduleDescriptor(namespace, id);
+            let builder_info = match self.schedule(
+                schedule,
+                pending_build.target_platform.as_deref(),
+                &pending_build.selector,
+            ) {
+                Some(builder_info) => builder_info,
+                None => continue,
+            };
+            // stop claiming once leadership lost
+            if !leader.load(Ordering::Acquire) {
+                self.release(builder_info.id.as_str());
+                break;
+            }
+            // claim pending build, skip if cancelled
+            let pending_build = match register
+                .take_resource::<PendingBuild>(Some(namespace), id, Some(version))
+                .await
+            {
+                Ok(Some(pending_build)) => pending_build,
+                Ok(None) => {
+                    self.release(builder_info.id.as_str());
+                    continue;
+                }
+                Err(e) => {
+                    self.release(builder_info.id.as_str());
+                    return Err(e);
+                }
+            };
+            let request = pending_build.build_request(
+                namespace.to_owned(),
+                id.to_owned(),
+                version,
+                builder_info.target_platform,
+            );
+            match Self::trigger_build(builder_info.address.as_str(), request).await {
+                Ok(_) => info!(
+                    namespace = namespace,
+                    id = id,
+                    build_version = version,
+                    builder_id = builder_info.id.as_str(),
+                    "dispatch pending build"
+                ),
+                Err(e) => {
+                    error!(
+                        namespace = namespace,
+                        id = id,
+                        build_version = version,
+                        builder_id = builder_info.id.as_str(),
+                        "dispatch pending build fail, error '{}'",
+                        e
+                    );
+                    self.release(builder_info.id.as_str());
+                    // requeue for next dispatch
+                    register
+                        .put_resource(Some(namespace), id, Some(version), pending_build, lease_id)
+                        .await?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // pending builds with valid key, first in first out
+    fn dispatch_order(
+        pending_builds: Vec<(String, PendingBuild)>,
+    ) -> Vec<((String, String, u64), PendingBuild)> {
+        let mut pending_builds = pending_builds
+            .into_iter()
+            .filter_map(|(key, pending_build)| {
+                let key = remove_resource::<PendingBuild>(key.as_str());
+                match Self::parse_build_key(key) {
+                    Some((namespace, id, version)) => Some((
+                        (namespace.to_owned(), id.to_owned(), version),
+                        pending_build,
+                    )),
+                    None => {
+                        warn!(key = key, "invalid pending build key");
+                        None
+                    }
+                }
+            })
+            .collect::<Vec<_>>();
+        pending_builds.sort_by_key(|(_, pending_build)| pending_build.timestamp);
+        pending_builds
+    }
+
+    // namespace, id, version
+    fn parse_build_key(key: &str) -> Option<(&str, &str, u64)> {
+        let (namespace, id_version) = key.split_once('/')?;
+        let (id, version) = id_version.split_once('/')?;
+        Some((namespace, id, version.parse().ok()?))
+    }
+
+    async fn trigger_build(address: &str, request: BuildRequest) -> pipebuilder_common::Result<()> {
+        let mut client = BuilderClientBuilder::default()
+            .protocol(RpcProtocolType::Http)
+            .address(address)
+            .connect()
+            .await?;
+        client.build(request).await?;
+        Ok(())
+    }
+
+    // builder info with requested target platform, or builder default if not requested
+    fn builder_info(
+        id: String,
+        state: &NodeState,
+        target_platform: Option<&str>,
+    ) -> Option<BuilderInfo> {
         let address = state.external_address.to_owned();
-        state
-            .get_support_target_platform()
+        target_platform
+            .map(String::from)
+            .or_else(|| state.get_support_target_platform())
             .map(|target_platform| BuilderInfo {
                 id,
                 address,
@@ -138,7 +541,9 @@ impl ScheduleManager {
 }
 
 pub struct SchedulerService {
-    manager: ScheduleManager,
+    manager: Arc<ScheduleManager>,
+    // elected as leader, standby reject schedule requests
+    leader: Arc<AtomicBool>,
 }
 
 #[tonic::async_trait]
@@ -149,25 +554,404 @@ impl Scheduler for SchedulerService {
     ) -> Result<tonic::Response<pipebuilder_common::grpc::schedule::ScheduleResponse>, tonic::Status>
     {
         // select builder using consistent hash, build of same app (namespace, id) landed on same builder for compilcation cache hit
+        // unless the builder saturated
+        if !self.is_leader() {
+            return Err(tonic::Status::unavailable("scheduler is standby"));
+        }
         let request = request.into_inner();
         let namespace = request.namespace;
         let id = request.id;
         let target_platform = request.target_platform;
+        let selector = request.selector;
         info!(
             namespace = namespace.as_str(),
             id = id.as_str(),
             "schedule build"
         );
         let schedule = ScheduleDescriptor(namespace.as_str(), id.as_str());
-        let selected_builder_info = self.manager.schedule(schedule, target_platform.as_deref());
+        let selected_builder_info =
+            self.manager
+                .schedule(schedule, target_platform.as_deref(), &selector);
         Ok(Response::new(ScheduleResponse {
             builder_info: selected_builder_info,
         }))
     }
+
+    async fn release(
+        &self,
+        request: tonic::Request<pipebuilder_common::grpc::schedule::ReleaseRequest>,
+    ) -> Result<tonic::Response<pipebuilder_common::grpc::schedule::ReleaseResponse>, tonic::Status>
+    {
+        if !self.is_leader() {
+            return Err(tonic::Status::unavailable("scheduler is standby"));
+        }
+        let builder_id = request.into_inner().builder_id;
+        info!(builder_id = builder_id.as_str(), "release scheduled build");
+        self.manager.release(builder_id.as_str());
+        Ok(Response::new(ReleaseResponse {}))
+    }
 }
 
 impl SchedulerService {
     pub fn new(manager: ScheduleManager) -> Self {
-        SchedulerService { manager }
+        SchedulerService {
+            manager: Arc::new(manager),
+            leader: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    pub fn is_leader(&self) -> bool {
+        self.leader.load(Ordering::Acquire)
+    }
+
+    // campaign block until elected, lead until leadership lost then campaign again
+    pub fn campaign(
+        &self,
+        mut register: Register,
+        external_address: String,
+        lease_id: i64,
+        check_period: Duration,
+    ) {
+        let leader = self.leader.clone();
+        let manager = self.manager.clone();
+        tokio::spawn(async move {
+            loop {
+                let leader_key = match register
+                    .campaign(ELECTION_SCHEDULER, external_address.as_str(), lease_id)
+                    .await
+                {
+                    Ok(leader_key) => leader_key,
+                    Err(e) => {
+                        error!("scheduler campaign fail, error '{}'", e);
+                        tokio::time::sleep(check_period).await;
+                        continue;
+                    }
+                };
+                leader.store(true, Ordering::Release);
+                let term_end = Arc::new(Notify::new());
+                let dispatch = {
+                    let manager = manager.clone();
+                    let register = register.clone();
+                    let leader = leader.clone();
+                    let term_end = term_end.clone();
+                    tokio::spawn(async move {
+                        manager
+                            .run_dispatch(register, lease_id, &leader, &term_end)
+                            .await
+                    })
+                };
+                Self::hold_leadership(&mut register, &leader_key, check_period).await;
+                // standby until elected again
+                leader.store(false, Ordering::Release);
+                term_end.notify_one();
+                if let Err(e) = dispatch.await {
+                    error!("pending build dispatcher exit with error '{}'", e)
+                }
+            }
+        });
+    }
+
+    // return once leader key no longer leading or leadership not confirmed in time
+    async fn hold_leadership(register: &mut Register, leader_key: &[u8], check_period: Duration) {
+        let mut interval = tokio::time::interval(check_period);
+        loop {
+            interval.tick().await;
+            match tokio::time::timeout(
+                check_period,
+                register.is_leader(ELECTION_SCHEDULER, leader_key),
+            )
+            .await
+            {
+                Ok(Ok(true)) => continue,
+                Ok(Ok(false)) => warn!("scheduler leadership lost"),
+                Ok(Err(e)) => error!("scheduler leadership check fail, error '{}'", e),
+                Err(_) => error!("scheduler leadership check timeout"),
+            };
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{ScheduleLoadPolicy, ScheduleManager};
+    use chrono::{Duration, Utc};
+    use pipebuilder_common::{
+        BuildMetadata, BuildStatus, NodeArch, NodeLoad, NodeOS, NodeRole, NodeState, NodeStatus,
+        PendingBuild, ResourceKeyBuilder, ResourceType, ScheduleDescriptor, ScheduleHash,
+    };
+    use std::{
+        collections::{HashMap, HashSet},
+        time::Instant,
+    };
+
+    const TEST_TARGET_PLATFORM: &str = "x86_64-unknown-linux-gnu";
+
+    fn test_manager() -> ScheduleManager {
+        ScheduleManager::builder()
+            .vnodes(128)
+            .load_policy(ScheduleLoadPolicy {
+                max_build_load: 1.0,
+                max_cpu_load: None,
+                min_free_disk: None,
+            })
+            .pending_build_grace(std::time::Duration::from_secs(60))
+            .build()
+    }
+
+    fn test_builder(running_builds: usize, max_parallel_builds: usize) -> NodeState {
+        NodeState {
+            role: NodeRole::Builder,
+            arch: NodeArch::X86_64,
+            os: NodeOS::LINUX,
+            internal_address: String::from("127.0.0.1:16000"),
+            external_address: String::from("127.0.0.1:16000"),
+            status: NodeStatus::Active,
+            timestamp: Utc::now(),
+            target_platforms: vec![String::from(TEST_TARGET_PLATFORM)],
+            load: Some(NodeLoad {
+                running_builds,
+                queued_builds: 0,
+                max_parallel_builds,
+                free_disk: None,
+                cpu_load: None,
+            }),
+            labels: HashMap::new(),
+        }
+    }
+
+    fn add_builder(manager: &ScheduleManager, id: &str, state: NodeState) {
+        manager.builders.pin().insert(id.to_owned(), state);
+        manager.ring.write().expect("lock hash ring").add(id);
+    }
+
+    // builder id selected
+    fn schedule(manager: &ScheduleManager, id: &str) -> Option<String> {
+        manager
+            .schedule(ScheduleDescriptor("dev", id), None, &HashMap::new())
+            .map(|builder_info| builder_info.id)
+    }
+
+    fn ring_owner(manager: &ScheduleManager, id: &str) -> String {
+        let key = ScheduleDescriptor("dev", id).schedule_hash();
+        manager
+            .ring
+            .read()
+            .expect("lock hash ring")
+            .get(key.as_str())
+            .expect("empty ring")
+            .to_owned()
+    }
+
+    #[test]
+    fn test_schedule_ring_owner() {
+        let manager = test_manager();
+        add_builder(&manager, "builder0", test_builder(0, 4));
+        add_builder(&manager, "builder1", test_builder(0, 4));
+        let owner = ring_owner(&manager, "app");
+        assert_eq!(Some(owner.clone()), schedule(&manager, "app"));
+        assert_eq!(Some(owner), schedule(&manager, "app"));
+    }
+
+    #[test]
+    fn test_schedule_count_dispatched_builds() {
+        let manager = test_manager();
+        add_builder(&manager, "builder0", test_builder(0, 1));
+        add_builder(&manager, "builder1", test_builder(0, 1));
+        let owner = ring_owner(&manager, "app");
+        // owner saturated by build scheduled before next load report
+        let first = schedule(&manager, "app").expect("no builder scheduled");
+        let second = schedule(&manager, "app").expect("no builder scheduled");
+        assert_eq!(owner, first);
+        assert_ne!(first, second);
+        // load report includes scheduled builds
+        manager
+            .dispatched
+            .lock()
+            .expect("lock dispatched builds")
+            .remove(&owner);
+        assert_eq!(Some(owner), schedule(&manager, "app"));
+    }
+
+    #[test]
+    fn test_schedule_fallback_least_loaded() {
+        let manager = test_manager();
+        add_builder(&manager, "builder0", test_builder(9, 4));
+        add_builder(&manager, "builder1", test_builder(3, 2));
+        assert_eq!(Some(String::from("builder1")), schedule(&manager, "app"));
+        assert_eq!(Some(String::from("builder1")), schedule(&manager, "app"));
+        // builder1 load 2.5 after dispatches, builder0 least loaded
+        assert_eq!(Some(String::from("builder0")), schedule(&manager, "app"));
+    }
+
+    fn test_pending_build(key: String, enqueued_secs_ago: i64) -> (String, PendingBuild) {
+        let pending_build = PendingBuild {
+            manifest_version: 0,
+            target_platform: None,
+            step_timeout: None,
+            build_timeout: None,
+            options: Default::default(),
+            selector: HashMap::new(),
+            timestamp: Utc::now() - Duration::seconds(enqueued_secs_ago),
+        };
+        (key, pending_build)
+    }
+
+    fn pending_build_key(id: &str, version: u64) -> String {
+        ResourceKeyBuilder::new()
+            .resource(ResourceType::BuildPending)
+            .namespace("dev")
+            .id(id)
+            .version(version)
+            .build()
+    }
+
+    #[test]
+    fn test_dispatch_order() {
+        let pending_builds = vec![
+            test_pending_build(pending_build_key("app0", 1), 10),
+            test_pending_build(pending_build_key("app1", 0), 30),
+            test_pending_build(format!("{}/invalid", pending_build_key("app2", 0)), 40),
+            test_pending_build(pending_build_key("app0", 0), 20),
+        ];
+        let keys = ScheduleManager::dispatch_order(pending_builds)
+            .into_iter()
+            .map(|(key, _)| key)
+            .collect::<Vec<_>>();
+        let expected = vec![
+            (String::from("dev"), String::from("app1"), 0),
+            (String::from("dev"), String::from("app0"), 0),
+            (String::from("dev"), String::from("app0"), 1),
+        ];
+        assert_eq!(expected, keys);
+    }
+
+    fn test_build(
+        id: &str,
+        status: BuildStatus,
+        builder_id: &str,
+    ) -> ((String, String, u64), BuildMetadata) {
+        let build_metadata = BuildMetadata::new(
+            String::from(TEST_TARGET_PLATFORM),
+            status,
+            Utc::now(),
+            String::from(builder_id),
+            String::new(),
+            None,
+            vec![],
+        );
+        ((String::from("dev"), String::from(id), 0), build_metadata)
+    }
+
+    #[test]
+    fn test_select_lost_builds() {
+        let grace = std::time::Duration::from_secs(60);
+        let builds = || {
+            vec![
+                test_build("queued", BuildStatus::Pending, ""),
+                test_build("lost", BuildStatus::Pending, ""),
+                test_build("dispatched", BuildStatus::Queued, "builder0"),
+                test_build("assigned", BuildStatus::Pending, "builder0"),
+            ]
+        };
+        let queued = HashSet::from([(String::from("dev"), String::from("queued"), 0)]);
+        let lost_key = (String::from("dev"), String::from("lost"), 0);
+        let mut lost_builds = HashMap::new();
+        let found_at = Instant::now();
+        // tracked when first found
+        assert!(ScheduleManager::select_lost_builds(
+            builds(),
+            &queued,
+            &mut lost_builds,
+            found_at,
+            grace
+        )
+        .is_empty());
+        assert_eq!(Some(&found_at), lost_builds.get(&lost_key));
+        // still within grace
+        assert!(ScheduleManager::select_lost_builds(
+            builds(),
+            &queued,
+            &mut lost_builds,
+            found_at + grace / 2,
+            grace
+        )
+        .is_empty());
+        // lost beyond grace
+        assert_eq!(
+            vec![lost_key.clone()],
+            ScheduleManager::select_lost_builds(
+                builds(),
+                &queued,
+                &mut lost_builds,
+                found_at + grace,
+                grace
+            )
+        );
+        assert!(lost_builds.is_empty());
+        // untracked once requeued
+        ScheduleManager::select_lost_builds(builds(), &queued, &mut lost_builds, found_at, grace);
+        let queued = HashSet::from([
+            lost_key.clone(),
+            (String::from("dev"), String::from("queued"), 0),
+        ]);
+        assert!(ScheduleManager::select_lost_builds(
+            builds(),
+            &queued,
+            &mut lost_builds,
+            found_at + grace,
+            grace
+        )
+        .is_empty());
+        assert!(lost_builds.is_empty());
+    }
+
+    #[test]
+    fn test_release_cancelled_pending_build() {
+        let manager = test_manager();
+        add_builder(&manager, "builder0", test_builder(0, 1));
+        add_builder(&manager, "builder1", test_builder(0, 1));
+        let owner = ring_owner(&manager, "app");
+        // pending build cancelled before claimed, scheduled build never reach owner
+        assert_eq!(Some(owner.clone()), schedule(&manager, "app"));
+        manager.release(owner.as_str());
+        assert_eq!(Some(owner.clone()), schedule(&manager, "app"));
+        // owner saturated by the claimed one
+        assert_ne!(Some(owner.clone()), schedule(&manager, "app"));
+        // release never underflow
+        manager.release(owner.as_str());
+        manager.release(owner.as_str());
+        assert_eq!(Some(owner), schedule(&manager, "app"));
+    }
+
+    #[test]
+    fn test_schedule_filter_builders() {
+        let manager = test_manager();
+        let mut labeled = test_builder(0, 1);
+        labeled
+            .labels
+            .insert(String::from("gpu"), String::from("true"));
+        add_builder(&manager, "builder0", labeled);
+        let mut inactive = test_builder(0, 1);
+        inactive.status = NodeStatus::InActive;
+        add_builder(&manager, "builder1", inactive);
+        let selector = HashMap::from([(String::from("gpu"), String::from("true"))]);
+        let selected = manager
+            .schedule(ScheduleDescriptor("dev", "app"), None, &selector)
+            .expect("no builder scheduled");
+        assert_eq!("builder0", selected.id);
+        assert_eq!(TEST_TARGET_PLATFORM, selected.target_platform);
+        assert!(manager
+            .schedule(
+                ScheduleDescriptor("dev", "app"),
+                Some("aarch64-unknown-linux-gnu"),
+                &HashMap::new()
+            )
+            .is_none());
+        let selector = HashMap::from([(String::from("gpu"), String::from("false"))]);
+        assert!(manager
+            .schedule(ScheduleDescriptor("dev", "app"), None, &selector)
+            .is_none());
     }
 }
@@ -1,16 +1,17 @@
 use super::{
     constants::{
-        ACTIVATE_NODE, APP, APP_METADATA, BUILD, BUILD_CACHE, BUILD_LOG, BUILD_METADATA,
-        BUILD_SNAPSHOT, CANCEL_BUILD, CATALOGS, CATALOGS_METADATA, CATALOGS_SNAPSHOT,
-        CATALOG_SCHEMA, CATALOG_SCHEMA_METADATA, CATALOG_SCHEMA_SNAPSHOT, DEACTIVATE_NODE,
-        MANIFEST, MANIFEST_METADATA, MANIFEST_SNAPSHOT, NAMESPACE, NODE_STATE, PROJECT, SCAN_BUILD,
-        SCAN_BUILD_CACHE, SHUTDOWN, SHUTDOWN_NODE,
+        ACTIVATE_NODE, APP, APP_METADATA, BUILD, BUILD_CACHE, BUILD_LOG, BUILD_LOG_STREAM,
+        BUILD_MATRIX, BUILD_MATRIX_SNAPSHOT, BUILD_METADATA, BUILD_PENDING, BUILD_SNAPSHOT,
+        CANCEL_BUILD, CATALOGS, CATALOGS_METADATA, CATALOGS_SNAPSHOT, CATALOG_SCHEMA,
+        CATALOG_SCHEMA_METADATA, CATALOG_SCHEMA_SNAPSHOT, DEACTIVATE_NODE, DEPENDENCY_POLICY,
+        LINT_POLICY, MANIFEST, MANIFEST_METADATA, MANIFEST_SNAPSHOT, NAMESPACE, NODE_STATE,
+        PROJECT, SCAN_BUILD, SCAN_BUILD_CACHE, SHUTDOWN, SHUTDOWN_NODE, SOURCE,
     },
     models,
 };
 use crate::{
     api_client_error, api_server_error, Catalog, CatalogSchemaValidator, CatalogsNameValidator,
-    Result, ValidateCatalog,
+    Error, Result, ValidateCatalog,
 };
 use reqwest::{
     header::{HeaderMap, HeaderName},
@@ -166,6 +167,43 @@ impl ApiClient {
         Ok(response)
     }
 
+    pub async fn build_matrix(
+        &self,
+        request: &models::BuildMatrixRequest,
+    ) -> Result<models::BuildMatrixResponse> {
+        let request = Self::serialize_request(request)?;
+        let response = self.post(BUILD_MATRIX, request).await?;
+        let response = Self::get_response_body::<models::BuildMatrixResponse>(response).await?;
+        Ok(response)
+    }
+
+    pub async fn get_build_matrix(
+        &self,
+        request: &models::GetBuildMatrixRequest,
+    ) -> Result<models::BuildMatrix> {
+        let response = self.query(BUILD_MATRIX, request).await?;
+        let response = Self::get_response_body::<models::BuildMatrix>(response).await?;
+        Ok(response)
+    }
+
+    pub async fn delete_build_matrix(
+        &self,
+        request: &models::DeleteBuildMatrixRequest,
+    ) -> Result<()> {
+        let request = Self::serialize_request(request)?;
+        let _ = self.delete(BUILD_MATRIX, request).await?;
+        Ok(())
+    }
+
+    pub async fn delete_build_matrix_snapshot(
+        &self,
+        request: &models::DeleteBuildMatrixSnapshotRequest,
+    ) -> Result<()> {
+        let request = Self::serialize_request(request)?;
+        let _ = self.delete(BUILD_MATRIX_SNAPSHOT, request).await?;
+        Ok(())
+    }
+
     pub async fn get_build_metadata(
         &self,
         request: &models::GetBuildRequest,
@@ -184,6 +222,15 @@ impl ApiClient {
         Ok(response)
     }
 
+    pub async fn list_pending_build(
+        &self,
+        request: &models::ListBuildRequest,
+    ) -> Result<Vec<models::PendingBuild>> {
+        let response = self.query(BUILD_PENDING, request).await?;
+        let response = Self::get_response_body::<Vec<models::PendingBuild>>(response).await?;
+        Ok(response)
+    }
+
     pub async fn cancel_build(
         &self,
         request: &models::CancelBuildRequest,
@@ -203,6 +250,15 @@ impl ApiClient {
         Ok(response)
     }
 
+    // stream build log, return response with body in chunks
+    pub async fn stream_build_log(&self, request: &models::GetBuildLogRequest) -> Result<Response> {
+        let response = self.query(BUILD_LOG_STREAM, request).await?;
+        if response.status().is_success() {
+            return Ok(response);
+        }
+        Err(Self::get_response_error(response).await)
+    }
+
     pub async fn delete_build(&self, request: &models::DeleteBuildRequest) -> Result<()> {
         let request = Self::serialize_request(request)?;
         let _ = self.delete(BUILD, request).await?;
@@ -426,6 +482,15 @@ impl ApiClient {
         Ok(response)
     }
 
+    pub async fn pull_source(
+        &self,
+        request: &models::GetSourceRequest,
+    ) -> Result<models::GetSourceResponse> {
+        let response = self.query(SOURCE, request).await?;
+        let response = Self::get_response_body::<models::GetSourceResponse>(response).await?;
+        Ok(response)
+    }
+
     pub async fn list_app_metadata(
         &self,
         request: &models::ListAppMetadataRequest,
@@ -491,6 +556,62 @@ impl ApiClient {
         Ok(())
     }
 
+    pub async fn update_dependency_policy(
+        &self,
+        request: &models::UpdateDependencyPolicyRequest,
+    ) -> Result<models::DependencyPolicy> {
+        let request = Self::serialize_request(request)?;
+        let response = self.post(DEPENDENCY_POLICY, request).await?;
+        let response = Self::get_response_body::<models::DependencyPolicy>(response).await?;
+        Ok(response)
+    }
+
+    pub async fn get_dependency_policy(
+        &self,
+        request: &models::GetDependencyPolicyRequest,
+    ) -> Result<models::DependencyPolicy> {
+        let response = self.query(DEPENDENCY_POLICY, request).await?;
+        let response = Self::get_response_body::<models::DependencyPolicy>(response).await?;
+        Ok(response)
+    }
+
+    pub async fn delete_dependency_policy(
+        &self,
+        request: &models::DeleteDependencyPolicyRequest,
+    ) -> Result<()> {
+        let request = Self::serialize_request(request)?;
+        let _ = self.delete(DEPENDENCY_POLICY, request).await?;
+        Ok(())
+    }
+
+    pub async fn update_lint_policy(
+        &self,
+        request: &models::UpdateLintPolicyRequest,
+    ) -> Result<models::LintPolicy> {
+        let request = Self::serialize_request(request)?;
+        let response = self.post(LINT_POLICY, request).await?;
+        let response = Self::get_response_body::<models::LintPolicy>(response).await?;
+        Ok(response)
+    }
+
+    pub async fn get_lint_policy(
+        &self,
+        request: &models::GetLintPolicyRequest,
+    ) -> Result<models::LintPolicy> {
+        let response = self.query(LINT_POLICY, request).await?;
+        let response = Self::get_response_body::<models::LintPolicy>(response).await?;
+        Ok(response)
+    }
+
+    pub async fn delete_lint_policy(
+        &self,
+        request: &models::DeleteLintPolicyRequest,
+    ) -> Result<()> {
+        let request = Self::serialize_request(request)?;
+        let _ = self.delete(LINT_POLICY, request).await?;
+        Ok(())
+    }
+
     pub async fn list_node_state(
         &self,
         request: &models::ListNodeStateRequest,
@@ -561,20 +682,28 @@ impl ApiClient {
             let t = serde_json::from_slice::<T>(&buffer)?;
             return Ok(t);
         }
+        Err(Self::get_response_error(response).await)
+    }
+
+    async fn get_response_error(response: Response) -> Error {
+        let status = response.status();
         let status_code = status.as_u16();
         let reason = status.canonical_reason().map(String::from);
         // parse failure message
-        let buffer = response.bytes().await?;
+        let buffer = match response.bytes().await {
+            Ok(buffer) => buffer,
+            Err(err) => return err.into(),
+        };
         let buffer = buffer.to_vec();
         let message = match serde_json::from_slice::<models::Failure>(&buffer) {
             Ok(failure) => Some(failure.error),
             Err(_) => None,
         };
         if status.is_client_error() {
-            return Err(api_client_error(status_code, reason, message));
+            return api_client_error(status_code, reason, message);
         }
         if status.is_server_error() {
-            return Err(api_server_error(status_code, reason, message));
+            return api_server_error(status_code, reason, message);
         }
         unreachable!()
     }
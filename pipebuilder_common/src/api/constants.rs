@@ -5,7 +5,11 @@ pub(crate) const BUILD: &str = "/api/v1/build";
 pub(crate) const BUILD_CACHE: &str = "/api/v1/build-cache";
 pub(crate) const BUILD_METADATA: &str = "/api/v1/build/metadata";
 pub(crate) const BUILD_SNAPSHOT: &str = "/api/v1/build/snapshot";
+pub(crate) const BUILD_PENDING: &str = "/api/v1/build/pending";
 pub(crate) const BUILD_LOG: &str = "/api/v1/build/log";
+pub(crate) const BUILD_LOG_STREAM: &str = "/api/v1/build/log/stream";
+pub(crate) const BUILD_MATRIX: &str = "/api/v1/build/matrix";
+pub(crate) const BUILD_MATRIX_SNAPSHOT: &str = "/api/v1/build/matrix/snapshot";
 pub(crate) const CANCEL_BUILD: &str = "/api/v1/build/cancel";
 pub(crate) const MANIFEST: &str = "/api/v1/manifest";
 pub(crate) const MANIFEST_METADATA: &str = "/api/v1/manifest/metadata";
@@ -17,6 +21,7 @@ pub(crate) const CATALOGS: &str = "/api/v1/catalogs";
 pub(crate) const CATALOGS_METADATA: &str = "/api/v1/catalogs/metadata";
 pub(crate) const CATALOGS_SNAPSHOT: &str = "/api/v1/catalogs/snapshot";
 pub(crate) const NODE_STATE: &str = "/api/v1/node";
+pub(crate) const SOURCE: &str = "/api/v1/source";
 pub(crate) const SCAN_BUILD: &str = "/api/v1/build/scan";
 pub(crate) const SCAN_BUILD_CACHE: &str = "/api/v1/build-cache/scan";
 pub(crate) const ACTIVATE_NODE: &str = "/api/v1/node/activate";
@@ -24,15 +29,26 @@ pub(crate) const DEACTIVATE_NODE: &str = "/api/v1/node/deactivate";
 pub(crate) const SHUTDOWN_NODE: &str = "/api/v1/node/shutdown";
 pub(crate) const NAMESPACE: &str = "/api/v1/namespace";
 pub(crate) const PROJECT: &str = "/api/v1/project";
+pub(crate) const DEPENDENCY_POLICY: &str = "/api/v1/dependency-policy";
+pub(crate) const LINT_POLICY: &str = "/api/v1/lint-policy";
 
 pub(crate) const DISPLAY_BUILD_STATUS_WIDTH: usize = 12;
+pub(crate) const DISPLAY_BUILD_CACHE_EVICTION_WIDTH: usize = 12;
+pub(crate) const DISPLAY_BUILD_LINT_WIDTH: usize = 8;
+pub(crate) const DISPLAY_BUILD_LINT_ARGS_WIDTH: usize = 24;
+pub(crate) const DISPLAY_DEPENDENCY_ALLOW_PATH_WIDTH: usize = 8;
+pub(crate) const DISPLAY_DEPENDENCY_SOURCES_WIDTH: usize = 24;
 pub(crate) const DISPLAY_ID_WIDTH: usize = 12;
 pub(crate) const DISPLAY_COUNT_WIDTH: usize = 12;
+pub(crate) const DISPLAY_DURATION_WIDTH: usize = 12;
 pub(crate) const DISPLAY_NAMESPACE_WIDTH: usize = 12;
 pub(crate) const DISPLAY_NODE_STATUS_WIDTH: usize = 12;
 pub(crate) const DISPLAY_NODE_ROLE_WIDTH: usize = 12;
 pub(crate) const DISPLAY_NODE_ARCH_WIDTH: usize = 12;
 pub(crate) const DISPLAY_NODE_OS_WIDTH: usize = 12;
+pub(crate) const DISPLAY_NODE_BUILDS_WIDTH: usize = 12;
+pub(crate) const DISPLAY_NODE_CPU_LOAD_WIDTH: usize = 12;
+pub(crate) const DISPLAY_LABELS_WIDTH: usize = 24;
 pub(crate) const DISPLAY_SIZE_WIDTH: usize = 12;
 pub(crate) const DISPLAY_VERSION_WIDTH: usize = 12;
 pub(crate) const DISPLAY_ADDRESS_WIDTH: usize = 16;
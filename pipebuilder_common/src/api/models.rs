@@ -1,18 +1,22 @@
 use crate::{
     api::constants::{
-        DISPLAY_ADDRESS_WIDTH, DISPLAY_BUILD_STATUS_WIDTH, DISPLAY_BUILD_TARGET_PLATFORM_WIDTH,
-        DISPLAY_COUNT_WIDTH, DISPLAY_ID_WIDTH, DISPLAY_MESSAGE_WIDTH, DISPLAY_NAMESPACE_WIDTH,
-        DISPLAY_NODE_ARCH_WIDTH, DISPLAY_NODE_OS_WIDTH, DISPLAY_NODE_ROLE_WIDTH,
+        DISPLAY_ADDRESS_WIDTH, DISPLAY_BUILD_CACHE_EVICTION_WIDTH, DISPLAY_BUILD_LINT_ARGS_WIDTH,
+        DISPLAY_BUILD_LINT_WIDTH, DISPLAY_BUILD_STATUS_WIDTH, DISPLAY_BUILD_TARGET_PLATFORM_WIDTH,
+        DISPLAY_COUNT_WIDTH, DISPLAY_DEPENDENCY_ALLOW_PATH_WIDTH, DISPLAY_DEPENDENCY_SOURCES_WIDTH,
+        DISPLAY_DURATION_WIDTH, DISPLAY_ID_WIDTH, DISPLAY_LABELS_WIDTH, DISPLAY_MESSAGE_WIDTH,
+        DISPLAY_NAMESPACE_WIDTH, DISPLAY_NODE_ARCH_WIDTH, DISPLAY_NODE_BUILDS_WIDTH,
+        DISPLAY_NODE_CPU_LOAD_WIDTH, DISPLAY_NODE_OS_WIDTH, DISPLAY_NODE_ROLE_WIDTH,
         DISPLAY_NODE_STATUS_WIDTH, DISPLAY_SIZE_WIDTH, DISPLAY_TIMESTAMP_WIDTH,
         DISPLAY_VERSION_WIDTH,
     },
     grpc::{build, node, repository},
     utils::prost_timestamp_to_datetime_utc,
-    BuildStatus, Error, NodeArch, NodeOS, NodeRole, NodeStatus,
+    AppCompression, BuildLintOutcome, BuildMatrixEntry, BuildMatrixStatus, BuildOptions,
+    BuildStatus, BuildStep, Error, NodeArch, NodeLoad, NodeOS, NodeRole, NodeStatus,
 };
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::fmt::Display;
+use std::{collections::HashMap, fmt::Display};
 
 pub trait PrintHeader {
     fn print_header();
@@ -25,6 +29,18 @@ pub struct BuildRequest {
     pub id: String,
     pub manifest_version: u64,
     pub target_platform: Option<String>,
+    // step timeout in seconds
+    pub step_timeout: Option<u64>,
+    // overall build timeout in seconds
+    pub build_timeout: Option<u64>,
+    // cargo build options
+    pub options: Option<BuildOptions>,
+    // rebuild even if an identical build succeeded
+    #[serde(default)]
+    pub force: bool,
+    // builder labels required, override project selector per key
+    #[serde(default)]
+    pub selector: HashMap<String, String>,
 }
 
 impl BuildRequest {
@@ -36,11 +52,21 @@ impl BuildRequest {
 #[derive(Serialize, Deserialize)]
 pub struct BuildResponse {
     pub build_version: u64,
+    // whether an identical succeed build returned
+    #[serde(default)]
+    pub reused: bool,
+    // whether build queued until a builder available
+    #[serde(default)]
+    pub pending: bool,
 }
 
 impl Display for BuildResponse {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        writeln!(f, "build version: {}", self.build_version)
+        match (self.reused, self.pending) {
+            (true, _) => writeln!(f, "build version: {} (reused)", self.build_version),
+            (_, true) => writeln!(f, "build version: {} (pending)", self.build_version),
+            _ => writeln!(f, "build version: {}", self.build_version),
+        }
     }
 }
 
@@ -507,6 +533,216 @@ impl PrintHeader for BuildSnapshot {
     }
 }
 
+#[derive(Serialize, Deserialize)]
+pub struct BuildMatrixRequest {
+    pub namespace: String,
+    // project id
+    pub id: String,
+    pub manifest_version: u64,
+    // target platforms to build
+    pub target_platforms: Vec<String>,
+    // step timeout in seconds
+    pub step_timeout: Option<u64>,
+    // overall build timeout in seconds
+    pub build_timeout: Option<u64>,
+    // cargo build options
+    pub options: Option<BuildOptions>,
+    // rebuild even if an identical build succeeded
+    #[serde(default)]
+    pub force: bool,
+    // builder labels required, override project selector per key
+    #[serde(default)]
+    pub selector: HashMap<String, String>,
+}
+
+impl BuildMatrixRequest {
+    // build request of one target platform
+    pub fn build_request(&self, target_platform: String) -> BuildRequest {
+        BuildRequest {
+            namespace: self.namespace.clone(),
+            id: self.id.clone(),
+            manifest_version: self.manifest_version,
+            target_platform: Some(target_platform),
+            step_timeout: self.step_timeout,
+            build_timeout: self.build_timeout,
+            options: self.options.clone(),
+            force: self.force,
+            selector: self.selector.clone(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct BuildMatrixResponse {
+    // build matrix version
+    pub version: u64,
+    pub builds: Vec<BuildMatrixEntry>,
+}
+
+impl Display for BuildMatrixResponse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "build matrix version: {}", self.version)?;
+        for build in &self.builds {
+            match (build.build_version, build.message.as_ref()) {
+                (Some(build_version), _) => writeln!(
+                    f,
+                    "{}: build version {}",
+                    build.target_platform, build_version
+                )?,
+                (None, Some(message)) => writeln!(f, "{}: {}", build.target_platform, message)?,
+                (None, None) => writeln!(f, "{}: not triggered", build.target_platform)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct GetBuildMatrixRequest {
+    pub namespace: String,
+    // project id
+    pub id: String,
+    // build matrix version
+    pub version: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct DeleteBuildMatrixRequest {
+    pub namespace: String,
+    // project id
+    pub id: String,
+    // build matrix version, all versions if not provided
+    pub version: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct DeleteBuildMatrixResponse {}
+
+#[derive(Serialize, Deserialize)]
+pub struct DeleteBuildMatrixSnapshotRequest {
+    pub namespace: String,
+    // project id
+    pub id: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct DeleteBuildMatrixSnapshotResponse {}
+
+// build matrix model for rest api
+#[derive(Serialize, Deserialize)]
+pub struct BuildMatrix {
+    // project id
+    pub id: String,
+    // build matrix version
+    pub version: u64,
+    pub manifest_version: u64,
+    // aggregate status of builds
+    pub status: BuildMatrixStatus,
+    pub timestamp: DateTime<Utc>,
+    pub builds: Vec<BuildMatrixBuild>,
+}
+
+impl BuildMatrix {
+    pub fn get_build_version(&self, target_platform: &str) -> Option<u64> {
+        self.builds
+            .iter()
+            .find(|build| build.target_platform == target_platform)
+            .and_then(|build| build.build_version)
+    }
+}
+
+impl Display for BuildMatrix {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let status = self.status.to_string();
+        let timestamp = self.timestamp.to_string();
+        writeln!(f,
+                "{id:<id_width$}{version:<version_width$}{manifest_version:<version_width$}{status:<status_width$}{timestamp:<timestamp_width$}",
+                id = self.id,
+                version = self.version,
+                manifest_version = self.manifest_version,
+                status = status,
+                timestamp = timestamp,
+                id_width = DISPLAY_ID_WIDTH,
+                version_width = DISPLAY_VERSION_WIDTH,
+                status_width = DISPLAY_BUILD_STATUS_WIDTH,
+                timestamp_width = DISPLAY_TIMESTAMP_WIDTH,
+                )
+    }
+}
+
+impl PrintHeader for BuildMatrix {
+    fn print_header() {
+        println!(
+            "{col0:<col0_width$}{col1:<col1_width$}{col2:<col2_width$}{col3:<col3_width$}{col4:<col4_width$}",
+            col0 = "Id",
+            col1 = "Version",
+            col2 = "Manifest",
+            col3 = "Status",
+            col4 = "Timestamp",
+            col0_width = DISPLAY_ID_WIDTH,
+            col1_width = DISPLAY_VERSION_WIDTH,
+            col2_width = DISPLAY_VERSION_WIDTH,
+            col3_width = DISPLAY_BUILD_STATUS_WIDTH,
+            col4_width = DISPLAY_TIMESTAMP_WIDTH,
+        )
+    }
+}
+
+// build of one target platform in matrix
+#[derive(Serialize, Deserialize, Clone)]
+pub struct BuildMatrixBuild {
+    pub target_platform: String,
+    // build version, none if build not triggered
+    pub build_version: Option<u64>,
+    // build status, none if build not triggered or metadata not found
+    pub status: Option<BuildStatus>,
+    pub message: Option<String>,
+}
+
+impl Display for BuildMatrixBuild {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let build_version = match self.build_version {
+            Some(build_version) => build_version.to_string(),
+            None => String::from("-"),
+        };
+        let status = match self.status.as_ref() {
+            Some(status) => status.to_string(),
+            None => String::from("-"),
+        };
+        let message = match self.message.as_ref() {
+            Some(message) => message.as_str(),
+            None => "",
+        };
+        writeln!(f,
+                "{target_platform:<target_platform_width$}{build_version:<version_width$}{status:<status_width$}{message:<message_width$}",
+                target_platform = self.target_platform,
+                build_version = build_version,
+                status = status,
+                message = message,
+                target_platform_width = DISPLAY_BUILD_TARGET_PLATFORM_WIDTH,
+                version_width = DISPLAY_VERSION_WIDTH,
+                status_width = DISPLAY_BUILD_STATUS_WIDTH,
+                message_width = DISPLAY_MESSAGE_WIDTH,
+                )
+    }
+}
+
+impl PrintHeader for BuildMatrixBuild {
+    fn print_header() {
+        println!(
+            "{col0:<col0_width$}{col1:<col1_width$}{col2:<col2_width$}{col3:<col3_width$}",
+            col0 = "Target Platform",
+            col1 = "Build Version",
+            col2 = "Status",
+            col3 = "Message",
+            col0_width = DISPLAY_BUILD_TARGET_PLATFORM_WIDTH,
+            col1_width = DISPLAY_VERSION_WIDTH,
+            col2_width = DISPLAY_BUILD_STATUS_WIDTH,
+            col3_width = DISPLAY_MESSAGE_WIDTH,
+        )
+    }
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct DeleteBuildSnapshotRequest {
     pub namespace: String,
@@ -535,6 +771,15 @@ pub struct BuildMetadata {
     pub builder_address: String,
     // message
     pub message: Option<String>,
+    // build steps
+    #[serde(default)]
+    pub timeline: Vec<BuildStep>,
+    // cargo build options
+    #[serde(default)]
+    pub options: BuildOptions,
+    // lint outcome
+    #[serde(default)]
+    pub lint: Option<BuildLintOutcome>,
 }
 
 impl Display for BuildMetadata {
@@ -543,11 +788,15 @@ impl Display for BuildMetadata {
             Some(message) => message.as_str(),
             None => "",
         };
+        let lint = match self.lint.as_ref() {
+            Some(lint) => lint.to_string(),
+            None => String::new(),
+        };
         let target_platform = self.target_platform.to_owned();
         let status = self.status.to_string();
         let timestamp = self.timestamp.to_string();
         writeln!(f,
-                "{id:<id_width$}{version:<version_width$}{status:<status_width$}{builder_id:<id_width$}{builder_address:<address_width$}{target_platform:<target_platform_width$}{timestamp:<timestamp_width$}{message:<message_width$}",
+                "{id:<id_width$}{version:<version_width$}{status:<status_width$}{builder_id:<id_width$}{builder_address:<address_width$}{target_platform:<target_platform_width$}{timestamp:<timestamp_width$}{lint:<lint_width$}{message:<message_width$}",
                 id = self.id,
                 version = self.version,
                 status = status,
@@ -555,6 +804,7 @@ impl Display for BuildMetadata {
                 builder_address = self.builder_address,
                 target_platform = target_platform,
                 timestamp = timestamp,
+                lint = lint,
                 message = message,
                 id_width = DISPLAY_ID_WIDTH,
                 version_width = DISPLAY_VERSION_WIDTH,
@@ -562,6 +812,7 @@ impl Display for BuildMetadata {
                 target_platform_width = DISPLAY_BUILD_TARGET_PLATFORM_WIDTH,
                 timestamp_width = DISPLAY_TIMESTAMP_WIDTH,
                 address_width = DISPLAY_ADDRESS_WIDTH,
+                lint_width = DISPLAY_BUILD_LINT_WIDTH,
                 message_width = DISPLAY_MESSAGE_WIDTH,
                 )
     }
@@ -570,7 +821,7 @@ impl Display for BuildMetadata {
 impl PrintHeader for BuildMetadata {
     fn print_header() {
         println!(
-            "{col0:<col0_width$}{col1:<col1_width$}{col2:<col2_width$}{col3:<col3_width$}{col4:<col4_width$}{col5:<col5_width$}{col6:<col6_width$}{col7:<col7_width$}",
+            "{col0:<col0_width$}{col1:<col1_width$}{col2:<col2_width$}{col3:<col3_width$}{col4:<col4_width$}{col5:<col5_width$}{col6:<col6_width$}{col7:<col7_width$}{col8:<col8_width$}",
             col0 = "Id",
             col1 = "Version",
             col2 = "Status",
@@ -578,7 +829,8 @@ impl PrintHeader for BuildMetadata {
             col4 = "Builder Address",
             col5 = "Target Platform",
             col6 = "Timestamp",
-            col7 = "Message",
+            col7 = "Lint",
+            col8 = "Message",
             col0_width = DISPLAY_ID_WIDTH,
             col1_width = DISPLAY_VERSION_WIDTH,
             col2_width = DISPLAY_BUILD_STATUS_WIDTH,
@@ -586,7 +838,118 @@ impl PrintHeader for BuildMetadata {
             col4_width = DISPLAY_ADDRESS_WIDTH,
             col5_width = DISPLAY_BUILD_TARGET_PLATFORM_WIDTH,
             col6_width = DISPLAY_TIMESTAMP_WIDTH,
-            col7_width = DISPLAY_MESSAGE_WIDTH,
+            col7_width = DISPLAY_BUILD_LINT_WIDTH,
+            col8_width = DISPLAY_MESSAGE_WIDTH,
+        )
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct PendingBuild {
+    // id
+    pub id: String,
+    // version
+    pub version: u64,
+    pub manifest_version: u64,
+    // requested target platform
+    pub target_platform: Option<String>,
+    // enqueue timestamp
+    pub timestamp: DateTime<Utc>,
+}
+
+impl Display for PendingBuild {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let target_platform = match self.target_platform.as_ref() {
+            Some(target_platform) => target_platform.as_str(),
+            None => "",
+        };
+        let timestamp = self.timestamp.to_string();
+        writeln!(
+            f,
+            "{id:<id_width$}{version:<version_width$}{manifest_version:<version_width$}{target_platform:<target_platform_width$}{timestamp:<timestamp_width$}",
+            id = self.id,
+            version = self.version,
+            manifest_version = self.manifest_version,
+            target_platform = target_platform,
+            timestamp = timestamp,
+            id_width = DISPLAY_ID_WIDTH,
+            version_width = DISPLAY_VERSION_WIDTH,
+            target_platform_width = DISPLAY_BUILD_TARGET_PLATFORM_WIDTH,
+            timestamp_width = DISPLAY_TIMESTAMP_WIDTH,
+        )
+    }
+}
+
+impl PrintHeader for PendingBuild {
+    fn print_header() {
+        println!(
+            "{col0:<col0_width$}{col1:<col1_width$}{col2:<col2_width$}{col3:<col3_width$}{col4:<col4_width$}",
+            col0 = "Id",
+            col1 = "Version",
+            col2 = "Manifest",
+            col3 = "Target Platform",
+            col4 = "Timestamp",
+            col0_width = DISPLAY_ID_WIDTH,
+            col1_width = DISPLAY_VERSION_WIDTH,
+            col2_width = DISPLAY_VERSION_WIDTH,
+            col3_width = DISPLAY_BUILD_TARGET_PLATFORM_WIDTH,
+            col4_width = DISPLAY_TIMESTAMP_WIDTH,
+        )
+    }
+}
+
+impl Display for BuildStep {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let status = self.status.to_string();
+        let outcome = match self.outcome.as_ref() {
+            Some(outcome) => outcome.to_string(),
+            None => String::from("Running"),
+        };
+        let start_timestamp = self.start_timestamp.to_string();
+        let end_timestamp = match self.end_timestamp.as_ref() {
+            Some(end_timestamp) => end_timestamp.to_string(),
+            None => String::new(),
+        };
+        // duration in seconds
+        let duration = match self.end_timestamp.as_ref() {
+            Some(end_timestamp) => {
+                let duration = *end_timestamp - self.start_timestamp;
+                format!("{:.3}", duration.num_milliseconds() as f64 / 1000.0)
+            }
+            None => String::new(),
+        };
+        writeln!(f,
+                "{status:<status_width$}{attempt:<attempt_width$}{outcome:<status_width$}{start_timestamp:<timestamp_width$}{end_timestamp:<timestamp_width$}{duration:<duration_width$}",
+                status = status,
+                attempt = self.attempt,
+                outcome = outcome,
+                start_timestamp = start_timestamp,
+                end_timestamp = end_timestamp,
+                duration = duration,
+                status_width = DISPLAY_BUILD_STATUS_WIDTH,
+                attempt_width = DISPLAY_VERSION_WIDTH,
+                timestamp_width = DISPLAY_TIMESTAMP_WIDTH,
+                duration_width = DISPLAY_DURATION_WIDTH,
+                )
+    }
+}
+
+impl PrintHeader for BuildStep {
+    fn print_header() {
+        println!(
+            "{col0:<col0_width$}{col1:<col1_width$}{col2:<col2_width$}{col3:<col3_width$}{col4:<col4_width$}{col5:<col5_width$}",
+            col0 = "Step",
+            col1 = "Attempt",
+            col2 = "Outcome",
+            col3 = "Start",
+            col4 = "End",
+            col5 = "Duration(s)",
+            col0_width = DISPLAY_BUILD_STATUS_WIDTH,
+            col1_width = DISPLAY_VERSION_WIDTH,
+            col2_width = DISPLAY_BUILD_STATUS_WIDTH,
+            col3_width = DISPLAY_TIMESTAMP_WIDTH,
+            col4_width = DISPLAY_TIMESTAMP_WIDTH,
+            col5_width = DISPLAY_DURATION_WIDTH,
         )
     }
 }
@@ -638,6 +1001,25 @@ pub struct GetAppRequest {
 #[derive(Serialize, Deserialize)]
 pub struct GetAppResponse {
     pub buffer: Vec<u8>,
+    // sha256 digest of app binaries in hex
+    #[serde(default)]
+    pub digest: Option<String>,
+    // compression of app binaries
+    #[serde(default)]
+    pub compression: Option<AppCompression>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct GetSourceRequest {
+    pub namespace: String,
+    pub id: String,
+    pub build_version: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct GetSourceResponse {
+    // source bundle
+    pub buffer: Vec<u8>,
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -671,6 +1053,15 @@ pub struct NodeState {
     pub status: NodeStatus,
     // timestamp
     pub timestamp: DateTime<Utc>,
+    // target platforms the node can build
+    #[serde(default)]
+    pub target_platforms: Vec<String>,
+    // build load, builder only
+    #[serde(default)]
+    pub load: Option<NodeLoad>,
+    // key value labels
+    #[serde(default)]
+    pub labels: HashMap<String, String>,
 }
 
 impl Display for NodeState {
@@ -680,14 +1071,43 @@ impl Display for NodeState {
         let os = self.os.to_string();
         let status = self.status.to_string();
         let timestamp = self.timestamp.to_string();
+        let target_platforms = self.target_platforms.join(",");
+        let load = self.load.as_ref();
+        // running/max+queued
+        let builds = load.map_or_else(
+            || String::from("-"),
+            |load| {
+                format!(
+                    "{}/{}+{}",
+                    load.running_builds, load.max_parallel_builds, load.queued_builds
+                )
+            },
+        );
+        let cpu_load = load
+            .and_then(|load| load.cpu_load)
+            .map_or_else(|| String::from("-"), |cpu_load| format!("{:.2}", cpu_load));
+        let free_disk = load
+            .and_then(|load| load.free_disk)
+            .map_or_else(|| String::from("-"), |free_disk| free_disk.to_string());
+        let labels = format_labels(&self.labels);
         writeln!(f,
-                "{id:<id_width$}{role:<role_width$}{arch:<arch_width$}{os:<os_width$}{status:<status_width$}{timestamp:<timestamp_width$}",
+                "{id:<id_width$}{role:<role_width$}{arch:<arch_width$}{os:<os_width$}{status:<status_width$}{builds:<builds_width$}{cpu_load:<cpu_load_width$}{free_disk:<free_disk_width$}{timestamp:<timestamp_width$}{target_platforms:<target_platforms_width$}{labels:<labels_width$}",
                 id = self.id,
                 role = role,
                 status = status,
                 arch = arch,
                 os = os,
+                builds = builds,
+                cpu_load = cpu_load,
+                free_disk = free_disk,
+                builds_width = DISPLAY_NODE_BUILDS_WIDTH,
+                cpu_load_width = DISPLAY_NODE_CPU_LOAD_WIDTH,
+                free_disk_width = DISPLAY_SIZE_WIDTH,
                 timestamp = timestamp,
+                target_platforms = target_platforms,
+                target_platforms_width = DISPLAY_BUILD_TARGET_PLATFORM_WIDTH,
+                labels = labels,
+                labels_width = DISPLAY_LABELS_WIDTH,
                 id_width = DISPLAY_ID_WIDTH,
                 role_width = DISPLAY_NODE_ROLE_WIDTH,
                 arch_width = DISPLAY_NODE_ARCH_WIDTH,
@@ -701,19 +1121,29 @@ impl Display for NodeState {
 impl PrintHeader for NodeState {
     fn print_header() {
         println!(
-            "{col0:<col0_width$}{col1:<col1_width$}{col2:<col2_width$}{col3:<col3_width$}{col4:<col4_width$}{col5:<col5_width$}",
+            "{col0:<col0_width$}{col1:<col1_width$}{col2:<col2_width$}{col3:<col3_width$}{col4:<col4_width$}{col5:<col5_width$}{col6:<col6_width$}{col7:<col7_width$}{col8:<col8_width$}{col9:<col9_width$}{col10:<col10_width$}",
             col0 = "Id",
             col1 = "Role",
             col2 = "Arch",
             col3 = "OS",
             col4 = "Status",
-            col5 = "Timestamp",
+            col5 = "Builds",
+            col6 = "CPU Load",
+            col7 = "Free Disk",
+            col8 = "Timestamp",
+            col9 = "Target Platforms",
+            col10 = "Labels",
             col0_width = DISPLAY_ID_WIDTH,
             col1_width = DISPLAY_NODE_ROLE_WIDTH,
             col2_width = DISPLAY_NODE_ARCH_WIDTH,
             col3_width = DISPLAY_NODE_OS_WIDTH,
             col4_width = DISPLAY_NODE_STATUS_WIDTH,
-            col5_width = DISPLAY_TIMESTAMP_WIDTH,
+            col5_width = DISPLAY_NODE_BUILDS_WIDTH,
+            col6_width = DISPLAY_NODE_CPU_LOAD_WIDTH,
+            col7_width = DISPLAY_SIZE_WIDTH,
+            col8_width = DISPLAY_TIMESTAMP_WIDTH,
+            col9_width = DISPLAY_BUILD_TARGET_PLATFORM_WIDTH,
+            col10_width = DISPLAY_LABELS_WIDTH,
         )
     }
 }
@@ -728,19 +1158,28 @@ pub struct BuildMetadataKey {
     pub namespace: String,
     pub id: String,
     pub version: u64,
+    // position in builder local queue, none if build in progress
+    #[serde(default)]
+    pub queue_position: Option<u64>,
 }
 
 impl Display for BuildMetadataKey {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let queue_position = match self.queue_position {
+            Some(queue_position) => queue_position.to_string(),
+            None => String::from("-"),
+        };
         writeln!(
             f,
-            "{namespace:<namespace_width$}{id:<id_width$}{version:<version_width$}",
+            "{namespace:<namespace_width$}{id:<id_width$}{version:<version_width$}{queue_position:<count_width$}",
             namespace = self.namespace,
             id = self.id,
             version = self.version,
+            queue_position = queue_position,
             namespace_width = DISPLAY_NAMESPACE_WIDTH,
             id_width = DISPLAY_ID_WIDTH,
             version_width = DISPLAY_VERSION_WIDTH,
+            count_width = DISPLAY_COUNT_WIDTH,
         )
     }
 }
@@ -748,13 +1187,15 @@ impl Display for BuildMetadataKey {
 impl PrintHeader for BuildMetadataKey {
     fn print_header() {
         println!(
-            "{col0:<col0_width$}{col1:<col1_width$}{col2:<col2_width$}",
+            "{col0:<col0_width$}{col1:<col1_width$}{col2:<col2_width$}{col3:<col3_width$}",
             col0 = "Namespace",
             col1 = "Id",
             col2 = "Version",
+            col3 = "Queue",
             col0_width = DISPLAY_NAMESPACE_WIDTH,
             col1_width = DISPLAY_ID_WIDTH,
             col2_width = DISPLAY_VERSION_WIDTH,
+            col3_width = DISPLAY_COUNT_WIDTH,
         )
     }
 }
@@ -770,21 +1211,31 @@ pub struct BuildCacheMetadata {
     pub id: String,
     pub target_platform: String,
     pub timestamp: DateTime<Utc>,
+    // cache size in byte
+    #[serde(default)]
+    pub size: u64,
+    // eviction reason, none if cache present
+    #[serde(default)]
+    pub eviction: Option<String>,
 }
 
 impl Display for BuildCacheMetadata {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         writeln!(
             f,
-            "{namespace:<namespace_width$}{id:<id_width$}{target_platform:<target_platform_width$}{timestamp:<timestamp_width$}",
+            "{namespace:<namespace_width$}{id:<id_width$}{target_platform:<target_platform_width$}{size:<size_width$}{timestamp:<timestamp_width$}{eviction:<eviction_width$}",
             namespace = self.namespace,
             id = self.id,
             target_platform = self.target_platform,
+            size = self.size,
             timestamp = self.timestamp,
+            eviction = self.eviction.as_deref().unwrap_or("-"),
             namespace_width = DISPLAY_NAMESPACE_WIDTH,
             id_width = DISPLAY_ID_WIDTH,
             target_platform_width = DISPLAY_BUILD_TARGET_PLATFORM_WIDTH,
+            size_width = DISPLAY_SIZE_WIDTH,
             timestamp_width = DISPLAY_TIMESTAMP_WIDTH,
+            eviction_width = DISPLAY_BUILD_CACHE_EVICTION_WIDTH,
         )
     }
 }
@@ -792,15 +1243,19 @@ impl Display for BuildCacheMetadata {
 impl PrintHeader for BuildCacheMetadata {
     fn print_header() {
         println!(
-            "{col0:<col0_width$}{col1:<col1_width$}{col2:<col2_width$}{col3:<col3_width$}",
+            "{col0:<col0_width$}{col1:<col1_width$}{col2:<col2_width$}{col3:<col3_width$}{col4:<col4_width$}{col5:<col5_width$}",
             col0 = "Namespace",
             col1 = "Id",
             col2 = "Target Platform",
-            col3 = "Timestamp",
+            col3 = "Size",
+            col4 = "Timestamp",
+            col5 = "Evicted",
             col0_width = DISPLAY_NAMESPACE_WIDTH,
             col1_width = DISPLAY_ID_WIDTH,
             col2_width = DISPLAY_BUILD_TARGET_PLATFORM_WIDTH,
-            col3_width = DISPLAY_TIMESTAMP_WIDTH
+            col3_width = DISPLAY_SIZE_WIDTH,
+            col4_width = DISPLAY_TIMESTAMP_WIDTH,
+            col5_width = DISPLAY_BUILD_CACHE_EVICTION_WIDTH
         )
     }
 }
@@ -862,6 +1317,11 @@ pub struct AppMetadata {
     pub pulls: u64,
     pub size: usize,
     pub created: DateTime<Utc>,
+    // sha256 digest of app binaries in hex
+    #[serde(default)]
+    pub digest: Option<String>,
+    #[serde(default)]
+    pub compression: Option<AppCompression>,
 }
 
 impl Display for AppMetadata {
@@ -950,10 +1410,158 @@ impl PrintHeader for Namespace {
     }
 }
 
+#[derive(Serialize, Deserialize)]
+pub struct UpdateDependencyPolicyRequest {
+    pub namespace: String,
+    // allowed registries, any registry if not provided
+    pub registries: Option<Vec<String>>,
+    // allowed git hosts, any host if not provided
+    pub git_hosts: Option<Vec<String>>,
+    pub banned_crates: Vec<String>,
+    pub allow_path: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct GetDependencyPolicyRequest {
+    pub namespace: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct DeleteDependencyPolicyRequest {
+    pub namespace: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct DeleteDependencyPolicyResponse {}
+
+#[derive(Serialize, Deserialize)]
+pub struct DependencyPolicy {
+    pub namespace: String,
+    pub registries: Option<Vec<String>>,
+    pub git_hosts: Option<Vec<String>>,
+    pub banned_crates: Vec<String>,
+    pub allow_path: bool,
+}
+
+impl DependencyPolicy {
+    // comma separated sources, '*' if unrestricted
+    fn display_sources(sources: Option<&Vec<String>>) -> String {
+        match sources {
+            Some(sources) => sources.join(","),
+            None => String::from("*"),
+        }
+    }
+}
+
+impl Display for DependencyPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "{namespace:<namespace_width$}{registries:<sources_width$}{git_hosts:<sources_width$}{banned_crates:<sources_width$}{allow_path:<allow_path_width$}",
+            namespace = self.namespace,
+            registries = Self::display_sources(self.registries.as_ref()),
+            git_hosts = Self::display_sources(self.git_hosts.as_ref()),
+            banned_crates = self.banned_crates.join(","),
+            allow_path = self.allow_path,
+            namespace_width = DISPLAY_NAMESPACE_WIDTH,
+            sources_width = DISPLAY_DEPENDENCY_SOURCES_WIDTH,
+            allow_path_width = DISPLAY_DEPENDENCY_ALLOW_PATH_WIDTH,
+        )
+    }
+}
+
+impl PrintHeader for DependencyPolicy {
+    fn print_header() {
+        println!(
+            "{col0:<col0_width$}{col1:<col1_width$}{col2:<col2_width$}{col3:<col3_width$}{col4:<col4_width$}",
+            col0 = "Namespace",
+            col1 = "Registries",
+            col2 = "Git Hosts",
+            col3 = "Banned Crates",
+            col4 = "Path",
+            col0_width = DISPLAY_NAMESPACE_WIDTH,
+            col1_width = DISPLAY_DEPENDENCY_SOURCES_WIDTH,
+            col2_width = DISPLAY_DEPENDENCY_SOURCES_WIDTH,
+            col3_width = DISPLAY_DEPENDENCY_SOURCES_WIDTH,
+            col4_width = DISPLAY_DEPENDENCY_ALLOW_PATH_WIDTH,
+        )
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct UpdateLintPolicyRequest {
+    pub namespace: String,
+    // project id, apply to all projects in namespace if not provided
+    pub id: Option<String>,
+    // clippy lint args, default as -D warnings
+    pub lints: Option<Vec<String>>,
+    // fail build if lint failed, default as true
+    pub block: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct GetLintPolicyRequest {
+    pub namespace: String,
+    pub id: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct DeleteLintPolicyRequest {
+    pub namespace: String,
+    pub id: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct DeleteLintPolicyResponse {}
+
+#[derive(Serialize, Deserialize)]
+pub struct LintPolicy {
+    pub namespace: String,
+    pub id: Option<String>,
+    pub lints: Vec<String>,
+    pub block: bool,
+}
+
+impl Display for LintPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "{namespace:<namespace_width$}{id:<id_width$}{lints:<lints_width$}{block:<block_width$}",
+            namespace = self.namespace,
+            id = self.id.as_deref().unwrap_or("*"),
+            lints = self.lints.join(" "),
+            block = self.block,
+            namespace_width = DISPLAY_NAMESPACE_WIDTH,
+            id_width = DISPLAY_ID_WIDTH,
+            lints_width = DISPLAY_BUILD_LINT_ARGS_WIDTH,
+            block_width = DISPLAY_BUILD_LINT_WIDTH,
+        )
+    }
+}
+
+impl PrintHeader for LintPolicy {
+    fn print_header() {
+        println!(
+            "{col0:<col0_width$}{col1:<col1_width$}{col2:<col2_width$}{col3:<col3_width$}",
+            col0 = "Namespace",
+            col1 = "Id",
+            col2 = "Lints",
+            col3 = "Block",
+            col0_width = DISPLAY_NAMESPACE_WIDTH,
+            col1_width = DISPLAY_ID_WIDTH,
+            col2_width = DISPLAY_BUILD_LINT_ARGS_WIDTH,
+            col3_width = DISPLAY_BUILD_LINT_WIDTH,
+        )
+    }
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct UpdateProjectRequest {
     pub namespace: String,
     pub id: String,
+    // replace builder label selector if provided
+    #[serde(default)]
+    pub selector: Option<HashMap<String, String>>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -975,17 +1583,22 @@ pub struct Project {
     // project id
     pub id: String,
     pub created: DateTime<Utc>,
+    // builder label selector
+    #[serde(default)]
+    pub selector: HashMap<String, String>,
 }
 
 impl Display for Project {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         writeln!(
             f,
-            "{id:<id_width$}{created:<created_width$}",
+            "{id:<id_width$}{created:<created_width$}{selector:<selector_width$}",
             id = self.id,
             created = self.created,
+            selector = format_labels(&self.selector),
             id_width = DISPLAY_ID_WIDTH,
             created_width = DISPLAY_TIMESTAMP_WIDTH,
+            selector_width = DISPLAY_LABELS_WIDTH,
         )
     }
 }
@@ -993,11 +1606,13 @@ impl Display for Project {
 impl PrintHeader for Project {
     fn print_header() {
         println!(
-            "{col0:<col0_width$}{col1:<col1_width$}",
+            "{col0:<col0_width$}{col1:<col1_width$}{col2:<col2_width$}",
             col0 = "Id",
             col1 = "Created",
+            col2 = "Selector",
             col0_width = DISPLAY_ID_WIDTH,
             col1_width = DISPLAY_TIMESTAMP_WIDTH,
+            col2_width = DISPLAY_LABELS_WIDTH,
         )
     }
 }
@@ -1024,11 +1639,19 @@ impl From<BuildRequest> for build::BuildRequest {
                 namespace, id, manifest_version
             )
         });
+        let step_timeout = origin.step_timeout;
+        let build_timeout = origin.build_timeout;
+        let options = origin.options.map(Into::into);
         build::BuildRequest {
             namespace,
             id,
             manifest_version,
             target_platform,
+            step_timeout,
+            build_timeout,
+            options,
+            build_version: None,
+            selector: origin.selector,
         }
     }
 }
@@ -1036,7 +1659,11 @@ impl From<BuildRequest> for build::BuildRequest {
 impl From<build::BuildResponse> for BuildResponse {
     fn from(origin: build::BuildResponse) -> Self {
         let build_version = origin.version;
-        BuildResponse { build_version }
+        BuildResponse {
+            build_version,
+            reused: false,
+            pending: false,
+        }
     }
 }
 
@@ -1252,7 +1879,36 @@ impl From<GetAppRequest> for repository::GetAppRequest {
 impl From<repository::GetAppResponse> for GetAppResponse {
     fn from(origin: repository::GetAppResponse) -> Self {
         let buffer = origin.buffer;
-        GetAppResponse { buffer }
+        let digest = origin.digest;
+        // compression recorded by repository
+        let compression = origin
+            .compression
+            .and_then(|compression| compression.parse().ok());
+        GetAppResponse {
+            buffer,
+            digest,
+            compression,
+        }
+    }
+}
+
+impl From<GetSourceRequest> for repository::GetSourceRequest {
+    fn from(origin: GetSourceRequest) -> Self {
+        let namespace = origin.namespace;
+        let id = origin.id;
+        let version = origin.build_version;
+        repository::GetSourceRequest {
+            namespace,
+            id,
+            version,
+        }
+    }
+}
+
+impl From<repository::GetSourceResponse> for GetSourceResponse {
+    fn from(origin: repository::GetSourceResponse) -> Self {
+        let buffer = origin.buffer;
+        GetSourceResponse { buffer }
     }
 }
 
@@ -1276,15 +1932,30 @@ impl From<build::GetBuildLogResponse> for GetBuildLogResponse {
     }
 }
 
+impl From<GetBuildLogRequest> for build::StreamBuildLogRequest {
+    fn from(origin: GetBuildLogRequest) -> Self {
+        let namespace = origin.namespace;
+        let id = origin.id;
+        let build_version = origin.version;
+        build::StreamBuildLogRequest {
+            namespace,
+            id,
+            build_version,
+        }
+    }
+}
+
 impl From<build::BuildMetadataKey> for BuildMetadataKey {
     fn from(origin: build::BuildMetadataKey) -> Self {
         let namespace = origin.namespace;
         let id = origin.id;
         let version = origin.version;
+        let queue_position = origin.queue_position;
         BuildMetadataKey {
             namespace,
             id,
             version,
+            queue_position,
         }
     }
 }
@@ -1304,11 +1975,15 @@ impl From<build::BuildCacheMetadata> for BuildCacheMetadata {
             Some(timestamp) => prost_timestamp_to_datetime_utc(timestamp),
             None => Utc::now(),
         };
+        let size = origin.size;
+        let eviction = origin.eviction;
         BuildCacheMetadata {
             namespace,
             id,
             target_platform,
             timestamp,
+            size,
+            eviction,
         }
     }
 }
@@ -1389,3 +2064,13 @@ impl From<Error> for Failure {
         Failure::new(format!("{}", error))
     }
 }
+
+// sorted key=value pairs, comma separated
+pub fn format_labels(labels: &HashMap<String, String>) -> String {
+    let mut labels = labels
+        .iter()
+        .map(|(key, value)| format!("{}={}", key, value))
+        .collect::<Vec<String>>();
+    labels.sort();
+    labels.join(",")
+}
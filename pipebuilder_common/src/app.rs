@@ -1,6 +1,70 @@
-use crate::{BlobResource, Resource, ResourceType};
+use crate::{errors::invalid_api_request, BlobResource, Resource, ResourceType, Result};
 use chrono::{DateTime, Utc};
+use flate2::{read::GzDecoder, write::GzEncoder};
 use serde::{Deserialize, Serialize};
+use std::{
+    fmt::Display,
+    io::{Read, Write},
+    str::FromStr,
+};
+
+// compression of published app binary
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq)]
+pub enum AppCompression {
+    Zstd,
+    Gzip,
+}
+
+impl Display for AppCompression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let compression_text = match self {
+            Self::Zstd => "Zstd",
+            Self::Gzip => "Gzip",
+        };
+        write!(f, "{}", compression_text)
+    }
+}
+
+impl FromStr for AppCompression {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "Zstd" => Ok(Self::Zstd),
+            "Gzip" => Ok(Self::Gzip),
+            _ => Err(invalid_api_request(format!(
+                "unknown app compression '{}'",
+                s
+            ))),
+        }
+    }
+}
+
+impl AppCompression {
+    pub fn compress(&self, buffer: &[u8]) -> Result<Vec<u8>> {
+        let compressed = match self {
+            Self::Zstd => zstd::encode_all(buffer, zstd::DEFAULT_COMPRESSION_LEVEL)?,
+            Self::Gzip => {
+                let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder.write_all(buffer)?;
+                encoder.finish()?
+            }
+        };
+        Ok(compressed)
+    }
+
+    pub fn decompress(&self, buffer: &[u8]) -> Result<Vec<u8>> {
+        let decompressed = match self {
+            Self::Zstd => zstd::decode_all(buffer)?,
+            Self::Gzip => {
+                let mut decompressed = Vec::new();
+                GzDecoder::new(buffer).read_to_end(&mut decompressed)?;
+                decompressed
+            }
+        };
+        Ok(decompressed)
+    }
+}
 
 // metadata for app binary (namespace, id, version)
 #[derive(Deserialize, Serialize)]
@@ -11,6 +75,19 @@ pub struct AppMetadata {
     pub size: usize,
     // created timestamp
     pub created: DateTime<Utc>,
+    // sha256 digest of published app in hex, none for apps published before
+    #[serde(default)]
+    pub digest: Option<String>,
+    // compression of published app, none if not compressed
+    #[serde(default)]
+    pub compression: Option<AppCompression>,
+}
+
+impl AppMetadata {
+    pub fn set_publish(&mut self, digest: String, compression: Option<AppCompression>) {
+        self.digest = Some(digest);
+        self.compression = compression;
+    }
 }
 
 impl BlobResource for AppMetadata {
@@ -19,6 +96,8 @@ impl BlobResource for AppMetadata {
             pulls: 0,
             size,
             created: Utc::now(),
+            digest: None,
+            compression: None,
         }
     }
 
@@ -32,3 +111,21 @@ impl Resource for AppMetadata {
         ResourceType::AppMetadata
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use super::AppCompression;
+    use crate::Result;
+
+    #[test]
+    fn test_app_compression() -> Result<()> {
+        let buffer = b"aloha".repeat(64);
+        for compression in [AppCompression::Zstd, AppCompression::Gzip] {
+            let compressed = compression.compress(buffer.as_slice())?;
+            let decompressed = compression.decompress(compressed.as_slice())?;
+            assert_eq!(buffer, decompressed);
+        }
+        Ok(())
+    }
+}
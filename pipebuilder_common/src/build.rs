@@ -1,27 +1,41 @@
 use crate::{
     constants::{
-        PATH_APP, PATH_APP_BUILD_LOG, PATH_APP_LOCK, PATH_APP_MAIN, PATH_APP_RELEASE_BINARY,
-        PATH_APP_TARGET, PATH_APP_TOML_MANIFEST,
+        PATH_APP, PATH_APP_BINARY, PATH_APP_BUILD_LOG, PATH_APP_CARGO_LOCK, PATH_APP_CATALOGS,
+        PATH_APP_LOCK, PATH_APP_MAIN, PATH_APP_SMOKE, PATH_APP_STRIPPED, PATH_APP_TARGET,
+        PATH_APP_TOML_MANIFEST,
     },
-    errors::Result,
-    grpc::repository::{GetManifestRequest, PostAppRequest},
-    open_lock_file, read_file,
+    errors::{build_error, Result},
+    grpc::{
+        build,
+        repository::{
+            GetCatalogsRequest, GetManifestRequest, GetSourceRequest, PostAppRequest,
+            PostSourceRequest,
+        },
+    },
+    is_host_target_platform, open_lock_file, read_file,
     utils::{
-        cargo_build, cargo_fmt, cargo_init, copy_directory, create_directory, move_directory,
-        parse_toml, remove_directory, write_file, write_toml, PathBuilder, TomlManifest,
+        append_log_section, cargo_build, cargo_clippy, cargo_fmt, cargo_init, cargo_test,
+        copy_directory, create_directory, directory_size, list_sub_directories, modified_time,
+        move_directory, parse_toml, remove_directory, sha256_digest, smoke_run, strip_binary,
+        touch, write_file, write_toml, PathBuilder, ProcessGroup, TomlManifest,
     },
-    Resource, ResourceType, Snapshot,
+    AppCompression, Catalog, DependencyPolicy, Register, Resource, ResourceType, Snapshot, Source,
 };
 use chrono::{DateTime, Utc};
 use pipegen::models::App;
 use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, convert::TryFrom, fmt::Display, path::Path, time::Duration};
 use tonic::transport::Channel;
 use tracing::{info, warn};
 
 use crate::grpc::repository::repository_client::RepositoryClient;
 
-#[derive(Deserialize, Serialize, Clone)]
+#[derive(Deserialize, Serialize, Clone, PartialEq)]
 pub enum BuildStatus {
+    // wait in cluster pending queue for an available builder
+    Pending,
+    // wait in local build queue
+    Queued,
     // pull manifest
     Pull,
     // validate manifest
@@ -32,6 +46,10 @@ pub enum BuildStatus {
     Generate,
     // cargo build
     Build,
+    // cargo clippy if lint required
+    Lint,
+    // cargo test and smoke run app if required
+    Test,
     // publish app binary
     Publish,
     // store compiled results
@@ -47,11 +65,15 @@ pub enum BuildStatus {
 impl ToString for BuildStatus {
     fn to_string(&self) -> String {
         let status_text = match self {
+            Self::Pending => "Pending",
+            Self::Queued => "Queued",
             Self::Pull => "Pull",
             Self::Validate => "Validate",
             Self::Create => "Create",
             Self::Generate => "Generate",
             Self::Build => "Build",
+            Self::Lint => "Lint",
+            Self::Test => "Test",
             Self::Publish => "Publish",
             Self::Store => "Store",
             Self::Succeed => "Succeed",
@@ -62,6 +84,230 @@ impl ToString for BuildStatus {
     }
 }
 
+#[derive(Deserialize, Serialize, Clone)]
+pub enum BuildStepOutcome {
+    // step succeed
+    Succeed,
+    // step failed
+    Fail,
+    // build cancelled during step
+    Cancel,
+}
+
+impl Display for BuildStepOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let outcome_text = match self {
+            Self::Succeed => "Succeed",
+            Self::Fail => "Fail",
+            Self::Cancel => "Cancel",
+        };
+        write!(f, "{}", outcome_text)
+    }
+}
+
+// Build status transition, one step per status run
+#[derive(Deserialize, Serialize, Clone)]
+pub struct BuildStep {
+    // build status
+    pub status: BuildStatus,
+    // step start time
+    pub start_timestamp: DateTime<Utc>,
+    // step end time, none if step in progress
+    pub end_timestamp: Option<DateTime<Utc>>,
+    // step outcome, none if step in progress
+    pub outcome: Option<BuildStepOutcome>,
+    // attempt of the status run, starting from 1
+    #[serde(default = "BuildStep::first_attempt")]
+    pub attempt: u32,
+}
+
+impl BuildStep {
+    pub fn start(status: BuildStatus) -> Self {
+        Self::start_attempt(status, Self::first_attempt())
+    }
+
+    pub fn start_attempt(status: BuildStatus, attempt: u32) -> Self {
+        BuildStep {
+            status,
+            start_timestamp: Utc::now(),
+            end_timestamp: None,
+            outcome: None,
+            attempt,
+        }
+    }
+
+    fn first_attempt() -> u32 {
+        1
+    }
+
+    pub fn end(&mut self, outcome: BuildStepOutcome) {
+        self.end_timestamp = Some(Utc::now());
+        self.outcome = Some(outcome);
+    }
+
+    pub fn is_ended(&self) -> bool {
+        self.outcome.is_some()
+    }
+}
+
+// cargo build options, trusted input run as is on builder
+#[derive(Deserialize, Serialize, Clone, Default, PartialEq)]
+pub struct BuildOptions {
+    // cargo profile, release if not provided
+    pub profile: Option<String>,
+    // cargo features to activate
+    #[serde(default)]
+    pub features: Vec<String>,
+    // extra environment variables of cargo build
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+    // RUSTFLAGS of cargo build
+    pub rustflags: Option<String>,
+    // require Cargo.lock up to date
+    #[serde(default)]
+    pub locked: bool,
+    // cargo test before publish
+    #[serde(default)]
+    pub test: bool,
+    // seconds to run app with catalogs before publish, no smoke run if not provided
+    pub smoke_run: Option<u64>,
+    // strip symbols of published app
+    #[serde(default)]
+    pub strip: bool,
+    // compression of published app, not compressed if not provided
+    pub compression: Option<AppCompression>,
+    // build version whose published Cargo.lock reused, implies locked
+    pub lock_version: Option<u64>,
+}
+
+impl BuildOptions {
+    // output directory of profile under target/<target_platform>
+    pub fn get_profile_directory(&self) -> &str {
+        match self.profile.as_deref() {
+            None | Some("release") | Some("bench") => "release",
+            Some("dev") | Some("test") => "debug",
+            Some(profile) => profile,
+        }
+    }
+
+    // whether test step required before publish
+    pub fn is_test_required(&self) -> bool {
+        self.test || self.smoke_run.is_some()
+    }
+
+    // whether cargo run with --locked
+    pub fn is_locked(&self) -> bool {
+        self.locked || self.lock_version.is_some()
+    }
+}
+
+impl TryFrom<build::BuildOptions> for BuildOptions {
+    type Error = crate::Error;
+
+    fn try_from(origin: build::BuildOptions) -> Result<Self> {
+        let compression = origin
+            .compression
+            .map(|compression| compression.parse())
+            .transpose()?;
+        Ok(BuildOptions {
+            profile: origin.profile,
+            features: origin.features,
+            env: origin.env,
+            rustflags: origin.rustflags,
+            locked: origin.locked,
+            test: origin.test,
+            smoke_run: origin.smoke_run,
+            strip: origin.strip,
+            compression,
+            lock_version: origin.lock_version,
+        })
+    }
+}
+
+impl From<BuildOptions> for build::BuildOptions {
+    fn from(origin: BuildOptions) -> Self {
+        build::BuildOptions {
+            profile: origin.profile,
+            features: origin.features,
+            env: origin.env,
+            rustflags: origin.rustflags,
+            locked: origin.locked,
+            test: origin.test,
+            smoke_run: origin.smoke_run,
+            strip: origin.strip,
+            compression: origin
+                .compression
+                .map(|compression| compression.to_string()),
+            lock_version: origin.lock_version,
+        }
+    }
+}
+
+// clippy lint of app, configured per namespace or project
+#[derive(Deserialize, Serialize, Clone, PartialEq)]
+pub struct BuildLint {
+    // clippy lint args, e.g ["-D", "warnings"]
+    pub lints: Vec<String>,
+    // fail build if lint failed, warn only otherwise
+    pub block: bool,
+}
+
+impl Resource for BuildLint {
+    fn ty() -> ResourceType {
+        ResourceType::LintPolicy
+    }
+}
+
+// lint of project, or namespace if project has none
+pub async fn get_build_lint(
+    register: &mut Register,
+    lease_id: i64,
+    namespace: &str,
+    id: &str,
+) -> Result<Option<BuildLint>> {
+    let lint = register
+        .get_resource::<BuildLint>(Some(namespace), id, None, lease_id)
+        .await?;
+    match lint {
+        Some(lint) => Ok(Some(lint)),
+        None => {
+            register
+                .get_resource::<BuildLint>(None, namespace, None, lease_id)
+                .await
+        }
+    }
+}
+
+// policies applied when build queued, part of build identity
+#[derive(Deserialize, Serialize, Clone, Default, PartialEq)]
+pub struct BuildPolicies {
+    pub dependency_policy: Option<DependencyPolicy>,
+    pub lint: Option<BuildLint>,
+    // builder labels required
+    pub selector: HashMap<String, String>,
+}
+
+#[derive(Deserialize, Serialize, Clone)]
+pub enum BuildLintOutcome {
+    // no lint failure
+    Pass,
+    // lint failed, app published
+    Warn,
+    // lint failed, build failed
+    Fail,
+}
+
+impl Display for BuildLintOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let outcome_text = match self {
+            Self::Pass => "Pass",
+            Self::Warn => "Warn",
+            Self::Fail => "Fail",
+        };
+        write!(f, "{}", outcome_text)
+    }
+}
+
 // Build state per (build_id, version), persist in registry
 #[derive(Deserialize, Serialize)]
 pub struct BuildMetadata {
@@ -71,12 +317,30 @@ pub struct BuildMetadata {
     pub status: BuildStatus,
     // timestamp
     pub timestamp: DateTime<Utc>,
-    // builder id
+    // builder id, empty while build pending in cluster queue
     pub builder_id: String,
-    // builder external adress
+    // builder external adress, empty while build pending in cluster queue
     pub builder_address: String,
     // message
     pub message: Option<String>,
+    // build steps in order
+    #[serde(default)]
+    pub timeline: Vec<BuildStep>,
+    // cargo build options
+    #[serde(default)]
+    pub options: BuildOptions,
+    // manifest version built, none for builds recorded before
+    #[serde(default)]
+    pub manifest_version: Option<u64>,
+    // latest catalogs version when build queued
+    #[serde(default)]
+    pub catalogs_version: Option<u64>,
+    // lint outcome, none if lint not required or not run yet
+    #[serde(default)]
+    pub lint: Option<BuildLintOutcome>,
+    // dependency policy, lint and selector when build queued
+    #[serde(default)]
+    pub policies: BuildPolicies,
 }
 
 impl BuildMetadata {
@@ -87,6 +351,7 @@ impl BuildMetadata {
         builder_id: String,
         builder_address: String,
         message: Option<String>,
+        timeline: Vec<BuildStep>,
     ) -> Self {
         BuildMetadata {
             target_platform,
@@ -95,6 +360,55 @@ impl BuildMetadata {
             builder_id,
             builder_address,
             message,
+            timeline,
+            options: BuildOptions::default(),
+            manifest_version: None,
+            catalogs_version: None,
+            lint: None,
+            policies: BuildPolicies::default(),
+        }
+    }
+
+    pub fn set_options(&mut self, options: BuildOptions) {
+        self.options = options
+    }
+
+    pub fn set_lint(&mut self, lint: Option<BuildLintOutcome>) {
+        self.lint = lint
+    }
+
+    pub fn set_inputs(&mut self, manifest_version: u64, catalogs_version: Option<u64>) {
+        self.manifest_version = Some(manifest_version);
+        self.catalogs_version = catalogs_version
+    }
+
+    pub fn set_policies(&mut self, policies: BuildPolicies) {
+        self.policies = policies
+    }
+
+    // succeed build with same manifest, target platform, options, catalogs and policies
+    pub fn is_identical_build(
+        &self,
+        manifest_version: u64,
+        target_platform: &str,
+        options: &BuildOptions,
+        catalogs_version: Option<u64>,
+        policies: &BuildPolicies,
+    ) -> bool {
+        self.status == BuildStatus::Succeed
+            && self.manifest_version == Some(manifest_version)
+            && self.target_platform == target_platform
+            && &self.options == options
+            && self.catalogs_version == catalogs_version
+            && &self.policies == policies
+    }
+
+    // end the step in progress if any
+    pub fn end_timeline(&mut self, outcome: BuildStepOutcome) {
+        if let Some(step) = self.timeline.last_mut() {
+            if !step.is_ended() {
+                step.end(outcome)
+            }
         }
     }
 
@@ -112,25 +426,71 @@ impl Resource for BuildMetadata {
     }
 }
 
+// Build waiting for an available builder per (namespace, id, version), persist in registry
+#[derive(Deserialize, Serialize)]
+pub struct PendingBuild {
+    pub manifest_version: u64,
+    // requested target platform, builder default if none
+    pub target_platform: Option<String>,
+    // step timeout in seconds
+    pub step_timeout: Option<u64>,
+    // overall build timeout in seconds
+    pub build_timeout: Option<u64>,
+    // cargo build options
+    pub options: BuildOptions,
+    // builder labels required
+    #[serde(default)]
+    pub selector: HashMap<String, String>,
+    // enqueue timestamp
+    pub timestamp: DateTime<Utc>,
+}
+
+impl PendingBuild {
+    // build request with version allocated while pending
+    pub fn build_request(
+        &self,
+        namespace: String,
+        id: String,
+        version: u64,
+        target_platform: String,
+    ) -> build::BuildRequest {
+        build::BuildRequest {
+            namespace,
+            id,
+            manifest_version: self.manifest_version,
+            target_platform,
+            step_timeout: self.step_timeout,
+            build_timeout: self.build_timeout,
+            options: Some(self.options.clone().into()),
+            build_version: Some(version),
+            selector: self.selector.clone(),
+        }
+    }
+}
+
+impl Resource for PendingBuild {
+    fn ty() -> ResourceType {
+        ResourceType::BuildPending
+    }
+}
+
 pub struct BuildCacheMetadata {
     pub timestamp: DateTime<Utc>,
+    // cache size in byte
+    pub size: u64,
 }
 
 impl BuildCacheMetadata {
-    pub fn new() -> Self {
-        BuildCacheMetadata {
-            timestamp: Utc::now(),
-        }
+    pub fn new(timestamp: DateTime<Utc>, size: u64) -> Self {
+        BuildCacheMetadata { timestamp, size }
     }
 
     pub fn get_timestamp(&self) -> DateTime<Utc> {
         self.timestamp.to_owned()
     }
-}
 
-impl Default for BuildCacheMetadata {
-    fn default() -> Self {
-        Self::new()
+    pub fn get_size(&self) -> u64 {
+        self.size
     }
 }
 
@@ -155,6 +515,109 @@ impl Resource for BuildSnapshot {
     }
 }
 
+// Latest build matrix state per manifest id
+#[derive(Default, Deserialize, Serialize)]
+pub struct BuildMatrixSnapshot {
+    pub latest_version: u64,
+}
+
+impl Snapshot for BuildMatrixSnapshot {
+    fn incr_version(&mut self) {
+        self.latest_version += 1
+    }
+    fn get_version(&self) -> u64 {
+        self.latest_version
+    }
+}
+
+impl Resource for BuildMatrixSnapshot {
+    fn ty() -> ResourceType {
+        ResourceType::BuildMatrixSnapshot
+    }
+}
+
+// Build of one target platform in matrix
+#[derive(Deserialize, Serialize, Clone)]
+pub struct BuildMatrixEntry {
+    // target platform
+    pub target_platform: String,
+    // build version, none if build not triggered
+    pub build_version: Option<u64>,
+    // reason if build not triggered
+    pub message: Option<String>,
+}
+
+// Builds of one manifest version across target platforms, persist in registry
+#[derive(Deserialize, Serialize, Clone)]
+pub struct BuildMatrixMetadata {
+    // project manifest version
+    pub manifest_version: u64,
+    // builds per target platform
+    pub builds: Vec<BuildMatrixEntry>,
+    // timestamp
+    pub timestamp: DateTime<Utc>,
+}
+
+impl BuildMatrixMetadata {
+    pub fn new(manifest_version: u64, builds: Vec<BuildMatrixEntry>) -> Self {
+        BuildMatrixMetadata {
+            manifest_version,
+            builds,
+            timestamp: Utc::now(),
+        }
+    }
+}
+
+impl Resource for BuildMatrixMetadata {
+    fn ty() -> ResourceType {
+        ResourceType::BuildMatrixMetadata
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone, PartialEq)]
+pub enum BuildMatrixStatus {
+    // any build in progress
+    Running,
+    // all builds succeed
+    Succeed,
+    // any build failed or not triggered
+    Fail,
+    // any build cancelled
+    Cancel,
+}
+
+impl BuildMatrixStatus {
+    // aggregate matrix status given build status, none if build not triggered
+    pub fn aggregate(statuses: &[Option<BuildStatus>]) -> Self {
+        let mut aggregate = BuildMatrixStatus::Succeed;
+        for status in statuses {
+            match status {
+                None | Some(BuildStatus::Fail) => return BuildMatrixStatus::Fail,
+                Some(BuildStatus::Cancel) => aggregate = BuildMatrixStatus::Cancel,
+                Some(BuildStatus::Succeed) => (),
+                Some(_) => {
+                    if aggregate == BuildMatrixStatus::Succeed {
+                        aggregate = BuildMatrixStatus::Running
+                    }
+                }
+            }
+        }
+        aggregate
+    }
+}
+
+impl Display for BuildMatrixStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let status_text = match self {
+            Self::Running => "Running",
+            Self::Succeed => "Succeed",
+            Self::Fail => "Fail",
+            Self::Cancel => "Cancel",
+        };
+        write!(f, "{}", status_text)
+    }
+}
+
 #[derive(Default)]
 pub struct LocalBuildContextBuilder {
     // builder id
@@ -164,6 +627,8 @@ pub struct LocalBuildContextBuilder {
     workspace: Option<String>,
     restore_directory: Option<String>,
     log_directory: Option<String>,
+    // target platform -> linker
+    linkers: Option<HashMap<String, String>>,
 }
 
 impl LocalBuildContextBuilder {
@@ -192,6 +657,11 @@ impl LocalBuildContextBuilder {
         self
     }
 
+    pub fn linkers(mut self, linkers: HashMap<String, String>) -> Self {
+        self.linkers = Some(linkers);
+        self
+    }
+
     pub fn build(self) -> LocalBuildContext {
         LocalBuildContext {
             id: self.id.expect("builder id undefined"),
@@ -199,6 +669,7 @@ impl LocalBuildContextBuilder {
             workspace: self.workspace.expect("workspace directory undefined"),
             restore_directory: self.restore_directory.expect("restore directory undefined"),
             log_directory: self.log_directory.expect("log directory undefined"),
+            linkers: self.linkers.unwrap_or_default(),
         }
     }
 }
@@ -213,6 +684,8 @@ pub struct LocalBuildContext {
     pub workspace: String,
     pub restore_directory: String,
     pub log_directory: String,
+    // target platform -> linker
+    pub linkers: HashMap<String, String>,
 }
 
 impl LocalBuildContext {
@@ -222,6 +695,7 @@ impl LocalBuildContext {
         workspace: String,
         restore_directory: String,
         log_directory: String,
+        linkers: HashMap<String, String>,
     ) -> Self {
         LocalBuildContext {
             id,
@@ -229,6 +703,7 @@ impl LocalBuildContext {
             workspace,
             restore_directory,
             log_directory,
+            linkers,
         }
     }
 }
@@ -244,30 +719,45 @@ pub struct Build {
     // https://doc.rust-lang.org/nightly/rustc/platform-support.html
     pub target_platform: String,
     pub app: Option<App>,
+    pub options: BuildOptions,
+    // latest catalogs version when build queued
+    pub catalogs_version: Option<u64>,
+    // process group of running cargo build
+    pub process_group: ProcessGroup,
+    // clippy lint, no lint step if not provided
+    pub lint: Option<BuildLint>,
+    pub lint_outcome: Option<BuildLintOutcome>,
+    // namespace dependency policy, unrestricted if not provided
+    pub dependency_policy: Option<DependencyPolicy>,
+    // builder labels required
+    pub selector: HashMap<String, String>,
 }
 
 impl Build {
-    pub fn is_target_platform_support(target_platform: &str) -> bool {
-        matches!(
-            target_platform,
-            "aarch64-unknown-linux-gnu" | "x86_64-apple-darwin" | "x86_64-unknown-linux-gnu"
-        )
-    }
-
-    // read app build log
-    pub async fn read_log(
+    // app build log path
+    pub fn get_log_path(
         log_directory: &str,
         namespace: &str,
         id: &str,
         version: u64,
-    ) -> Result<Vec<u8>> {
-        let app_log_path = PathBuilder::default()
+    ) -> std::path::PathBuf {
+        PathBuilder::default()
             .push(log_directory)
             .push(namespace)
             .push(id)
             .push(version.to_string())
             .push(PATH_APP_BUILD_LOG)
-            .build();
+            .build()
+    }
+
+    // read app build log
+    pub async fn read_log(
+        log_directory: &str,
+        namespace: &str,
+        id: &str,
+        version: u64,
+    ) -> Result<Vec<u8>> {
+        let app_log_path = Self::get_log_path(log_directory, namespace, id, version);
         read_file(app_log_path.as_path()).await
     }
 
@@ -289,15 +779,66 @@ impl Build {
             build_context,
             target_platform,
             app: None,
+            options: BuildOptions::default(),
+            catalogs_version: None,
+            process_group: ProcessGroup::default(),
+            lint: None,
+            lint_outcome: None,
+            dependency_policy: None,
+            selector: HashMap::new(),
         }
     }
 
+    pub fn set_options(&mut self, options: BuildOptions) {
+        self.options = options
+    }
+
+    pub fn get_options(&self) -> &BuildOptions {
+        &self.options
+    }
+
+    pub fn set_catalogs_version(&mut self, catalogs_version: Option<u64>) {
+        self.catalogs_version = catalogs_version
+    }
+
+    pub fn get_catalogs_version(&self) -> Option<u64> {
+        self.catalogs_version
+    }
+
+    pub fn set_lint(&mut self, lint: Option<BuildLint>) {
+        self.lint = lint
+    }
+
+    pub fn get_lint_outcome(&self) -> Option<BuildLintOutcome> {
+        self.lint_outcome.clone()
+    }
+
+    pub fn set_dependency_policy(&mut self, dependency_policy: Option<DependencyPolicy>) {
+        self.dependency_policy = dependency_policy
+    }
+
+    pub fn set_selector(&mut self, selector: HashMap<String, String>) {
+        self.selector = selector
+    }
+
+    pub fn get_policies(&self) -> BuildPolicies {
+        BuildPolicies {
+            dependency_policy: self.dependency_policy.clone(),
+            lint: self.lint.clone(),
+            selector: self.selector.clone(),
+        }
+    }
+
+    pub fn get_process_group(&self) -> ProcessGroup {
+        self.process_group.clone()
+    }
+
     // (id, address)
     pub fn get_builder_meta(&self) -> (&String, &String) {
         (&self.build_context.id, &self.build_context.address)
     }
 
-    fn get_workspace(&self) -> &String {
+    pub fn get_workspace(&self) -> &String {
         &self.build_context.workspace
     }
 
@@ -309,6 +850,13 @@ impl Build {
         &self.build_context.restore_directory
     }
 
+    fn get_linker(&self) -> Option<&str> {
+        self.build_context
+            .linkers
+            .get(self.target_platform.as_str())
+            .map(String::as_str)
+    }
+
     pub fn get_build_meta(&self) -> (&String, &String, u64, u64, &String) {
         let namespace = &self.namespace;
         let id = &self.id;
@@ -348,6 +896,8 @@ impl Build {
             BuildStatus::Create => self.create_build_workspace().await,
             BuildStatus::Generate => self.generate_app().await,
             BuildStatus::Build => self.build_app().await,
+            BuildStatus::Lint => self.lint_app().await,
+            BuildStatus::Test => self.test_app().await,
             BuildStatus::Publish => self.publish_app().await,
             BuildStatus::Store => self.store_app().await,
             BuildStatus::Succeed => self.succeed(),
@@ -366,15 +916,33 @@ impl Build {
         }
     }
 
-    pub fn build_post_app_request(&self, buffer: Vec<u8>) -> PostAppRequest {
+    pub fn build_post_app_request(&self, buffer: Vec<u8>, digest: String) -> PostAppRequest {
         let namespace = self.namespace.to_owned();
         let id = self.id.to_owned();
         let version = self.build_version;
+        let compression = self
+            .options
+            .compression
+            .map(|compression| compression.to_string());
         PostAppRequest {
             namespace,
             id,
             version,
             buffer,
+            digest,
+            compression,
+        }
+    }
+
+    pub fn build_post_source_request(&self, buffer: Vec<u8>) -> PostSourceRequest {
+        let namespace = self.namespace.to_owned();
+        let id = self.id.to_owned();
+        let version = self.build_version;
+        PostSourceRequest {
+            namespace,
+            id,
+            version,
+            buffer,
         }
     }
 
@@ -412,7 +980,11 @@ impl Build {
             target_platform = target_platform.as_str(),
             "validate manifest"
         );
-        self.app.as_ref().expect("app not initialized").validate()?;
+        let app = self.app.as_ref().expect("app not initialized");
+        app.validate()?;
+        if let Some(dependency_policy) = self.dependency_policy.as_ref() {
+            dependency_policy.validate(app)?;
+        }
         Ok(Some(BuildStatus::Create))
     }
 
@@ -458,6 +1030,8 @@ impl Build {
         if app_restore_lock_file.try_lock()? {
             // try restore from compiled app
             if copy_directory(app_restore_path.clone(), app_workspace.clone()).await? {
+                // mark last hit, build cache evicted least recently used first
+                touch(app_restore_path.as_path())?;
                 app_restore_lock_file.unlock()?;
                 info!(
                     namespace = namespace,
@@ -483,7 +1057,7 @@ impl Build {
             .push(PATH_APP)
             .build();
         create_directory(app_path.as_path()).await?;
-        cargo_init(app_path.as_os_str()).await?;
+        cargo_init(app_path.as_os_str(), &self.process_group).await?;
         Ok(Some(BuildStatus::Generate))
     }
 
@@ -526,8 +1100,23 @@ impl Build {
             .push(PATH_APP_MAIN)
             .build();
         write_file(main_path.as_path(), generated_code.as_bytes()).await?;
+        // pin Cargo.lock published by prior build
+        if let Some(lock_version) = self.options.lock_version {
+            let request = GetSourceRequest {
+                namespace: namespace.to_owned(),
+                id: id.to_owned(),
+                version: lock_version,
+            };
+            let mut client = self.repository_client.clone();
+            let response = client.get_source(request).await?.into_inner();
+            let lock = pinned_cargo_lock(response.buffer.as_slice(), lock_version)?;
+            let cargo_lock_path = PathBuilder::clone_from(&app_workspace)
+                .push(PATH_APP_CARGO_LOCK)
+                .build();
+            write_file(cargo_lock_path.as_path(), lock.content.as_bytes()).await?;
+        }
         // fmt code
-        cargo_fmt(toml_path.as_os_str()).await?;
+        cargo_fmt(toml_path.as_os_str(), &self.process_group).await?;
         Ok(Some(BuildStatus::Build))
     }
 
@@ -567,7 +1156,194 @@ impl Build {
         let log_path = PathBuilder::clone_from(&log_directory)
             .push(PATH_APP_BUILD_LOG)
             .build();
-        cargo_build(cargo_workdir.as_path(), target_platform, log_path.as_path()).await?;
+        cargo_build(
+            cargo_workdir.as_path(),
+            target_platform,
+            log_path.as_path(),
+            &self.options,
+            self.get_linker(),
+            &self.process_group,
+        )
+        .await?;
+        match self.lint.is_some() {
+            true => Ok(Some(BuildStatus::Lint)),
+            false => Ok(Some(self.next_status_after_lint())),
+        }
+    }
+
+    fn next_status_after_lint(&self) -> BuildStatus {
+        match self.options.is_test_required() {
+            true => BuildStatus::Test,
+            false => BuildStatus::Publish,
+        }
+    }
+
+    // cargo clippy with configured lints, append output to build log
+    pub async fn lint_app(&mut self) -> Result<Option<BuildStatus>> {
+        let (namespace, id, manifest_version, build_version, target_platform) =
+            self.get_build_meta();
+        let workspace = self.get_workspace().as_str();
+        let log_directory = self.get_log_directory().as_str();
+        let namespace = namespace.as_str();
+        let id = id.as_str();
+        let target_platform = target_platform.as_str();
+        info!(
+            namespace = namespace,
+            id = id,
+            manifest_version = manifest_version,
+            build_version = build_version,
+            target_platform = target_platform,
+            "lint app"
+        );
+        let lint = self.lint.as_ref().expect("lint not initialized");
+        let cargo_workdir = PathBuilder::default()
+            .push(workspace)
+            .push(namespace)
+            .push(id)
+            .push(build_version.to_string())
+            .push(PATH_APP)
+            .build();
+        let log_path = PathBuilder::default()
+            .push(log_directory)
+            .push(namespace)
+            .push(id)
+            .push(build_version.to_string())
+            .push(PATH_APP_BUILD_LOG)
+            .build();
+        append_log_section(log_path.as_path(), "lint").await?;
+        let result = cargo_clippy(
+            cargo_workdir.as_path(),
+            target_platform,
+            log_path.as_path(),
+            &self.options,
+            self.get_linker(),
+            &lint.lints,
+            &self.process_group,
+        )
+        .await;
+        match result {
+            Ok(_) => self.lint_outcome = Some(BuildLintOutcome::Pass),
+            Err(err) if lint.block => {
+                self.lint_outcome = Some(BuildLintOutcome::Fail);
+                return Err(err);
+            }
+            Err(err) => {
+                warn!(
+                    namespace = namespace,
+                    id = id,
+                    build_version = build_version,
+                    "lint failed, error: '{:#?}'",
+                    err
+                );
+                self.lint_outcome = Some(BuildLintOutcome::Warn)
+            }
+        };
+        Ok(Some(self.next_status_after_lint()))
+    }
+
+    // cargo test and smoke run app, append output to build log
+    pub async fn test_app(&mut self) -> Result<Option<BuildStatus>> {
+        let (namespace, id, manifest_version, build_version, target_platform) =
+            self.get_build_meta();
+        let workspace = self.get_workspace().as_str();
+        let log_directory = self.get_log_directory().as_str();
+        let namespace = namespace.as_str();
+        let id = id.as_str();
+        let target_platform = target_platform.as_str();
+        info!(
+            namespace = namespace,
+            id = id,
+            manifest_version = manifest_version,
+            build_version = build_version,
+            target_platform = target_platform,
+            "test app"
+        );
+        let app_workspace = PathBuilder::default()
+            .push(workspace)
+            .push(namespace)
+            .push(id)
+            .push(build_version.to_string())
+            .build();
+        let cargo_workdir = PathBuilder::clone_from(&app_workspace)
+            .push(PATH_APP)
+            .build();
+        let log_path = PathBuilder::default()
+            .push(log_directory)
+            .push(namespace)
+            .push(id)
+            .push(build_version.to_string())
+            .push(PATH_APP_BUILD_LOG)
+            .build();
+        // app binary of other target platforms can not run on builder host
+        if !is_host_target_platform(target_platform) {
+            warn!(
+                namespace = namespace,
+                id = id,
+                build_version = build_version,
+                target_platform = target_platform,
+                "skip test app of non-host target platform"
+            );
+            append_log_section(
+                log_path.as_path(),
+                format!(
+                    "test skipped, target platform '{}' not host platform",
+                    target_platform
+                )
+                .as_str(),
+            )
+            .await?;
+            return Ok(Some(BuildStatus::Publish));
+        }
+        if self.options.test {
+            append_log_section(log_path.as_path(), "test").await?;
+            cargo_test(
+                cargo_workdir.as_path(),
+                target_platform,
+                log_path.as_path(),
+                &self.options,
+                self.get_linker(),
+                &self.process_group,
+            )
+            .await?;
+        }
+        let duration = match self.options.smoke_run {
+            Some(duration) => Duration::from_secs(duration),
+            None => return Ok(Some(BuildStatus::Publish)),
+        };
+        // run app in smoke directory with catalogs dumped as ./catalogs
+        let smoke_directory = PathBuilder::clone_from(&app_workspace)
+            .push(PATH_APP_SMOKE)
+            .build();
+        create_directory(smoke_directory.as_path()).await?;
+        append_log_section(log_path.as_path(), "smoke run").await?;
+        if let Some(catalogs_version) = self.catalogs_version {
+            let request = GetCatalogsRequest {
+                namespace: namespace.to_owned(),
+                id: id.to_owned(),
+                version: catalogs_version,
+            };
+            let mut client = self.repository_client.clone();
+            let response = client.get_catalogs(request).await?.into_inner();
+            let catalogs_directory = PathBuilder::clone_from(&smoke_directory)
+                .push(PATH_APP_CATALOGS)
+                .build();
+            Catalog::dump_catalogs(response.buffer.as_slice(), catalogs_directory.as_path())
+                .await?;
+        }
+        let binary_path = PathBuilder::clone_from(&app_workspace)
+            .push(PATH_APP_TARGET)
+            .push(target_platform)
+            .push(self.options.get_profile_directory())
+            .push(PATH_APP_BINARY)
+            .build();
+        smoke_run(
+            binary_path.as_path(),
+            smoke_directory.as_path(),
+            log_path.as_path(),
+            duration,
+            &self.process_group,
+        )
+        .await?;
         Ok(Some(BuildStatus::Publish))
     }
 
@@ -597,16 +1373,71 @@ impl Build {
         let target_path = PathBuilder::clone_from(&app_workspace)
             .push(PATH_APP_TARGET)
             .build();
-        let release_path = PathBuilder::clone_from(&target_path)
+        let binary_path = PathBuilder::clone_from(&target_path)
             .push(target_platform)
-            .push(PATH_APP_RELEASE_BINARY)
+            .push(self.options.get_profile_directory())
+            .push(PATH_APP_BINARY)
             .build();
-        let buffer = read_file(release_path.as_path()).await?;
-        let request = self.build_post_app_request(buffer);
+        // host strip can not handle binary of other target platforms
+        let strip = self.options.strip && is_host_target_platform(target_platform);
+        if self.options.strip && !strip {
+            warn!(
+                namespace = namespace,
+                id = id,
+                build_version = build_version,
+                target_platform = target_platform,
+                "skip strip app binary of non-host target platform"
+            );
+        }
+        let buffer = match strip {
+            true => {
+                let stripped_path = PathBuilder::clone_from(&app_workspace)
+                    .push(PATH_APP_STRIPPED)
+                    .build();
+                strip_binary(binary_path.as_path(), stripped_path.as_path()).await?;
+                read_file(stripped_path.as_path()).await?
+            }
+            false => read_file(binary_path.as_path()).await?,
+        };
+        // compress and digest binary off runtime workers, shared with rpc services and heartbeat
+        let compression = self.options.compression;
+        let (buffer, digest) = tokio::task::spawn_blocking(move || -> Result<(Vec<u8>, String)> {
+            let buffer = match compression {
+                Some(compression) => compression.compress(buffer.as_slice())?,
+                None => buffer,
+            };
+            // digest of published app, verified by repository and pbctl
+            let digest = sha256_digest(buffer.as_slice());
+            Ok((buffer, digest))
+        })
+        .await
+        .map_err(|err| build_error(String::from("compress app"), err.to_string()))??;
+        let request = self.build_post_app_request(buffer, digest);
         let _ = self.repository_client.post_app(request).await?.into_inner();
+        self.publish_source().await?;
         Ok(Some(BuildStatus::Store))
     }
 
+    // publish compiled sources, also called once build failed after generate
+    pub async fn publish_source(&mut self) -> Result<()> {
+        let (namespace, id, _, build_version, _) = self.get_build_meta();
+        let app_workspace = PathBuilder::default()
+            .push(self.get_workspace())
+            .push(namespace)
+            .push(id)
+            .push(build_version.to_string())
+            .build();
+        let sources = read_sources(app_workspace.as_path()).await?;
+        let buffer = Source::to_buffer(sources.as_slice())?;
+        let request = self.build_post_source_request(buffer);
+        let _ = self
+            .repository_client
+            .post_source(request)
+            .await?
+            .into_inner();
+        Ok(())
+    }
+
     // store cargo project
     pub async fn store_app(&mut self) -> Result<Option<BuildStatus>> {
         let (namespace, id, manifest_version, build_version, target_platform) =
@@ -676,7 +1507,9 @@ impl Build {
         // cleanup previous app build cache if any
         let _ = remove_directory(app_restore_path).await;
         create_directory(app_restore_path).await?;
-        move_directory(app_path, app_restore_path).await
+        move_directory(app_path, app_restore_path).await?;
+        // mark last hit, build cache evicted least recently used first
+        touch(app_restore_path)
     }
 
     pub fn succeed(&mut self) -> Result<Option<BuildStatus>> {
@@ -698,7 +1531,7 @@ impl Build {
         namespace: &str,
         id: &str,
         target_platform: &str,
-    ) -> Result<()> {
+    ) -> Result<bool> {
         let app_restore_directory = PathBuilder::default()
             .push(restore_directory)
             .push(namespace)
@@ -712,10 +1545,174 @@ impl Build {
             .push(PATH_APP_LOCK)
             .build();
         let mut app_restore_lock_file = open_lock_file(app_restore_lock_path.as_path())?;
-        if app_restore_lock_file.try_lock()? {
-            remove_directory(app_restore_path.as_path()).await?;
-            app_restore_lock_file.unlock()?;
+        // skip if app restore or store in progress
+        if !app_restore_lock_file.try_lock()? {
+            return Ok(false);
         }
-        Ok(())
+        let r = remove_directory(app_restore_path.as_path()).await;
+        app_restore_lock_file.unlock()?;
+        r.map(|_| true)
+    }
+
+    // build cache metadata of stored app, none if app not stored
+    pub async fn get_build_cache_metadata(
+        restore_directory: &str,
+        namespace: &str,
+        id: &str,
+        target_platform: &str,
+    ) -> Result<Option<BuildCacheMetadata>> {
+        let app_restore_path = PathBuilder::default()
+            .push(restore_directory)
+            .push(namespace)
+            .push(id)
+            .push(target_platform)
+            .push(PATH_APP)
+            .build();
+        if !app_restore_path.is_dir() {
+            return Ok(None);
+        }
+        let timestamp = modified_time(app_restore_path.as_path()).await?;
+        let size = directory_size(app_restore_path).await?;
+        Ok(Some(BuildCacheMetadata::new(timestamp, size)))
+    }
+
+    // scan stored apps in restore directory, keyed by (namespace, id, target_platform)
+    pub async fn scan_build_cache(
+        restore_directory: &str,
+    ) -> Result<Vec<((String, String, String), BuildCacheMetadata)>> {
+        let mut caches: Vec<((String, String, String), BuildCacheMetadata)> = vec![];
+        let restore_path = PathBuilder::default().push(restore_directory).build();
+        if !restore_path.is_dir() {
+            return Ok(caches);
+        }
+        for namespace in list_sub_directories(restore_path.as_path()).await? {
+            let namespace_path = PathBuilder::clone_from(&restore_path)
+                .push(namespace.as_str())
+                .build();
+            for id in list_sub_directories(namespace_path.as_path()).await? {
+                let id_path = PathBuilder::clone_from(&namespace_path)
+                    .push(id.as_str())
+                    .build();
+                for target_platform in list_sub_directories(id_path.as_path()).await? {
+                    if let Some(metadata) = Self::get_build_cache_metadata(
+                        restore_directory,
+                        namespace.as_str(),
+                        id.as_str(),
+                        target_platform.as_str(),
+                    )
+                    .await?
+                    {
+                        caches.push((
+                            (namespace.to_owned(), id.to_owned(), target_platform),
+                            metadata,
+                        ));
+                    }
+                }
+            }
+        }
+        Ok(caches)
+    }
+}
+
+// path relative to app directory, e.g app/src/main.rs -> src/main.rs
+fn app_relative_path(path: &str) -> &str {
+    path.strip_prefix(PATH_APP)
+        .and_then(|path| path.strip_prefix('/'))
+        .unwrap_or(path)
+}
+
+// compiled sources in app workspace, Cargo.lock missing if cargo build not run yet
+async fn read_sources(app_workspace: &Path) -> Result<Vec<Source>> {
+    let mut sources: Vec<Source> = vec![];
+    for source_path in [PATH_APP_TOML_MANIFEST, PATH_APP_CARGO_LOCK, PATH_APP_MAIN] {
+        let path = PathBuilder::clone_from(app_workspace)
+            .push(source_path)
+            .build();
+        if !path.is_file() {
+            continue;
+        }
+        let content = String::from_utf8(read_file(path.as_path()).await?)?;
+        let source_path = app_relative_path(source_path);
+        sources.push(Source::new(source_path.to_owned(), content));
+    }
+    Ok(sources)
+}
+
+// Cargo.lock in sources published by build version
+fn pinned_cargo_lock(buffer: &[u8], lock_version: u64) -> Result<Source> {
+    let sources = Source::from_buffer(buffer)?;
+    let lock_path = app_relative_path(PATH_APP_CARGO_LOCK);
+    match sources.into_iter().find(|source| source.path == lock_path) {
+        Some(lock) => Ok(lock),
+        None => Err(build_error(
+            String::from("pin Cargo.lock"),
+            format!("Cargo.lock not found in build version {}", lock_version),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{app_relative_path, pinned_cargo_lock, read_sources};
+    use crate::{
+        constants::{PATH_APP_CARGO_LOCK, PATH_APP_MAIN, PATH_APP_TOML_MANIFEST},
+        create_directory, remove_directory, write_file, PathBuilder, Source,
+    };
+
+    const TEST_CARGO_LOCK: &str = r#"
+version = 3
+
+[[package]]
+name = "app"
+version = "0.1.0"
+"#;
+
+    #[test]
+    fn test_pinned_cargo_lock() {
+        let sources = vec![
+            Source::new(
+                app_relative_path(PATH_APP_MAIN).to_owned(),
+                String::from("fn main() {}"),
+            ),
+            Source::new(
+                app_relative_path(PATH_APP_CARGO_LOCK).to_owned(),
+                String::from(TEST_CARGO_LOCK),
+            ),
+        ];
+        let buffer = Source::to_buffer(sources.as_slice()).unwrap();
+        let lock = pinned_cargo_lock(buffer.as_slice(), 0).unwrap();
+        assert_eq!("Cargo.lock", lock.path);
+        assert_eq!(TEST_CARGO_LOCK, lock.content);
+        let sources = vec![Source::new(
+            app_relative_path(PATH_APP_MAIN).to_owned(),
+            String::from("fn main() {}"),
+        )];
+        let buffer = Source::to_buffer(sources.as_slice()).unwrap();
+        assert!(pinned_cargo_lock(buffer.as_slice(), 0).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_read_sources_of_failed_build() -> crate::Result<()> {
+        // build failed after generate, before cargo build wrote Cargo.lock
+        let app_workspace = std::env::temp_dir().join("pipebuilder_test_read_sources");
+        for (path, content) in [
+            (PATH_APP_TOML_MANIFEST, "[package]"),
+            (PATH_APP_MAIN, "fn main() {}"),
+        ] {
+            let path = PathBuilder::clone_from(app_workspace.as_path())
+                .push(path)
+                .build();
+            create_directory(path.parent().unwrap()).await?;
+            write_file(path.as_path(), content.as_bytes()).await?;
+        }
+        let sources = read_sources(app_workspace.as_path()).await?;
+        let paths: Vec<&str> = sources.iter().map(|source| source.path.as_str()).collect();
+        assert_eq!(vec!["Cargo.toml", "src/main.rs"], paths);
+        assert_eq!("fn main() {}", sources[1].content);
+        // bundle still published without Cargo.lock
+        let buffer = Source::to_buffer(sources.as_slice())?;
+        assert!(pinned_cargo_lock(buffer.as_slice(), 0).is_err());
+        remove_directory(app_workspace).await
     }
 }
@@ -8,12 +8,28 @@ pub const ENV_FORMATTER: &str = "PIPEBUILDER_LOG_FORMATTER";
 pub const ENV_PIPEBUILDER_CONFIG_FILE: &str = "PIPEBUILDER_CONFIG_FILE";
 pub const ENV_PIPEBUILDER_NODE_ID: &str = "PIPEBUILDER_NODE_ID";
 pub const ENV_PIPEBUILDER_EXTERNAL_ADDR: &str = "PIPEBUILDER_EXTERNAL_ADDR";
+pub const ELECTION_SCHEDULER: &str = "/pipebuilder/election/scheduler";
 pub const DEFAULT_NODE_HEARTBEAT_PERIOD: Period = Period::Secs(30);
+pub const DEFAULT_BUILD_LOG_STREAM_PERIOD: Period = Period::Millis(500);
+pub const DEFAULT_MAX_PARALLEL_BUILDS: usize = 2;
+pub const DEFAULT_REPOSITORY_SOURCE_DIRECTORY: &str = "/opt/repository/source";
+pub const DEFAULT_SCHEDULE_MAX_BUILD_LOAD: f64 = 1.0;
+pub const DEFAULT_SCHEDULE_VIRTUAL_NODES: usize = 128;
+pub const DEFAULT_SCHEDULE_LEADER_CHECK_PERIOD: Period = Period::Secs(5);
+pub const DEFAULT_SCHEDULE_PENDING_BUILD_GRACE: Period = Period::Secs(60);
+pub const DEFAULT_BUILD_RETRY_MAX_ATTEMPTS: u32 = 3;
+pub const DEFAULT_BUILD_RETRY_BACKOFF: Period = Period::Secs(1);
+pub const DEFAULT_BUILD_CACHE_EVICTION_INTERVAL: Period = Period::Secs(60);
+pub const DEFAULT_BUILD_CACHE_EVICTION_HISTORY: usize = 32;
 
 pub const PATH_APP: &str = "app";
 pub const PATH_APP_LOCK: &str = "app.lock";
 pub const PATH_APP_BUILD_LOG: &str = "build.log";
 pub const PATH_APP_TOML_MANIFEST: &str = "app/Cargo.toml";
 pub const PATH_APP_MAIN: &str = "app/src/main.rs";
+pub const PATH_APP_CARGO_LOCK: &str = "app/Cargo.lock";
 pub const PATH_APP_TARGET: &str = "app/target";
-pub const PATH_APP_RELEASE_BINARY: &str = "release/app";
+pub const PATH_APP_BINARY: &str = "app";
+pub const PATH_APP_SMOKE: &str = "smoke";
+pub const PATH_APP_STRIPPED: &str = "app.stripped";
+pub const PATH_APP_CATALOGS: &str = "catalogs";
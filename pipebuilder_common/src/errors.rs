@@ -1,3 +1,4 @@
+use serde::Deserialize;
 use std::fmt::{Debug, Display};
 use std::{env, io, net, result, string::FromUtf8Error};
 use thiserror::Error;
@@ -11,6 +12,32 @@ impl Display for Error {
     }
 }
 
+impl Error {
+    pub fn is_timeout(&self) -> bool {
+        matches!(*self.0, ErrorImpl::Timeout { .. })
+    }
+
+    pub fn kind(&self) -> Option<ErrorKind> {
+        match *self.0 {
+            ErrorImpl::Rpc(_) | ErrorImpl::TonicTransport(_) => Some(ErrorKind::Rpc),
+            ErrorImpl::Io(_) => Some(ErrorKind::Io),
+            ErrorImpl::Cargo { .. } => Some(ErrorKind::Cargo),
+            ErrorImpl::Timeout { .. } => Some(ErrorKind::Timeout),
+            _ => None,
+        }
+    }
+}
+
+// error categories, e.g to decide whether a failure is retryable
+#[derive(Clone, Copy, Deserialize, PartialEq)]
+pub enum ErrorKind {
+    // grpc status or transport error
+    Rpc,
+    Io,
+    Cargo,
+    Timeout,
+}
+
 pub type Result<T> = result::Result<T, Error>;
 
 #[derive(Debug, Error)]
@@ -76,6 +103,14 @@ pub enum ErrorImpl {
     Build { operation: String, message: String },
     #[error("repository error, operation: {operation:?}, message: {message:?}")]
     Repository { operation: String, message: String },
+    #[error("timeout error, operation: {operation:?}, message: {message:?}")]
+    Timeout { operation: String, message: String },
+    #[error("digest mismatch, expected: {expected:?}, actual: {actual:?}")]
+    Digest { expected: String, actual: String },
+    #[error("dependency policy violated, violations: {violations:?}")]
+    DependencyPolicy { violations: Vec<String> },
+    #[error("invalid source path, path: {path:?}")]
+    SourcePath { path: String },
 }
 
 impl From<std::io::Error> for Error {
@@ -223,6 +258,22 @@ pub fn repository_error(operation: String, message: String) -> Error {
     Error(Box::new(ErrorImpl::Repository { operation, message }))
 }
 
+pub fn timeout_error(operation: String, message: String) -> Error {
+    Error(Box::new(ErrorImpl::Timeout { operation, message }))
+}
+
+pub fn digest_error(expected: String, actual: String) -> Error {
+    Error(Box::new(ErrorImpl::Digest { expected, actual }))
+}
+
+pub fn dependency_policy_error(violations: Vec<String>) -> Error {
+    Error(Box::new(ErrorImpl::DependencyPolicy { violations }))
+}
+
+pub fn invalid_source_path(path: String) -> Error {
+    Error(Box::new(ErrorImpl::SourcePath { path }))
+}
+
 // rpc status
 pub fn rpc_internal_error(error: Error) -> tonic::Status {
     tonic::Status::internal(format!("{:#?}", error))
@@ -12,6 +12,56 @@ pub struct BuildRequest {
     /// target platform
     #[prost(string, tag = "4")]
     pub target_platform: ::prost::alloc::string::String,
+    /// step timeout in seconds, override builder default
+    #[prost(uint64, optional, tag = "5")]
+    pub step_timeout: ::core::option::Option<u64>,
+    /// overall build timeout in seconds, override builder default
+    #[prost(uint64, optional, tag = "6")]
+    pub build_timeout: ::core::option::Option<u64>,
+    /// cargo build options
+    #[prost(message, optional, tag = "7")]
+    pub options: ::core::option::Option<BuildOptions>,
+    /// build version allocated while pending, allocate new version if undefined
+    #[prost(uint64, optional, tag = "8")]
+    pub build_version: ::core::option::Option<u64>,
+    /// builder labels required, recorded as build input
+    #[prost(map = "string, string", tag = "9")]
+    pub selector:
+        ::std::collections::HashMap<::prost::alloc::string::String, ::prost::alloc::string::String>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BuildOptions {
+    /// cargo profile, release if not provided
+    #[prost(string, optional, tag = "1")]
+    pub profile: ::core::option::Option<::prost::alloc::string::String>,
+    /// cargo features to activate
+    #[prost(string, repeated, tag = "2")]
+    pub features: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// extra environment variables of cargo build
+    #[prost(map = "string, string", tag = "3")]
+    pub env:
+        ::std::collections::HashMap<::prost::alloc::string::String, ::prost::alloc::string::String>,
+    /// RUSTFLAGS of cargo build
+    #[prost(string, optional, tag = "4")]
+    pub rustflags: ::core::option::Option<::prost::alloc::string::String>,
+    /// require Cargo.lock up to date
+    #[prost(bool, tag = "5")]
+    pub locked: bool,
+    /// cargo test before publish
+    #[prost(bool, tag = "6")]
+    pub test: bool,
+    /// seconds to run app with catalogs before publish
+    #[prost(uint64, optional, tag = "7")]
+    pub smoke_run: ::core::option::Option<u64>,
+    /// strip symbols of published app
+    #[prost(bool, tag = "8")]
+    pub strip: bool,
+    /// compression of published app, Zstd or Gzip
+    #[prost(string, optional, tag = "9")]
+    pub compression: ::core::option::Option<::prost::alloc::string::String>,
+    /// build version whose Cargo.lock reused, implies locked
+    #[prost(uint64, optional, tag = "10")]
+    pub lock_version: ::core::option::Option<u64>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct BuildResponse {
@@ -46,6 +96,9 @@ pub struct BuildMetadataKey {
     /// project build version
     #[prost(uint64, tag = "3")]
     pub version: u64,
+    /// position in local build queue starting from 1, none if build in progress
+    #[prost(uint64, optional, tag = "4")]
+    pub queue_position: ::core::option::Option<u64>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ScanBuildResponse {
@@ -71,6 +124,24 @@ pub struct GetBuildLogResponse {
     pub buffer: ::prost::alloc::vec::Vec<u8>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StreamBuildLogRequest {
+    /// project namespace
+    #[prost(string, tag = "1")]
+    pub namespace: ::prost::alloc::string::String,
+    /// project id
+    #[prost(string, tag = "2")]
+    pub id: ::prost::alloc::string::String,
+    /// project build version
+    #[prost(uint64, tag = "3")]
+    pub build_version: u64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StreamBuildLogResponse {
+    /// log chunk
+    #[prost(bytes = "vec", tag = "1")]
+    pub buffer: ::prost::alloc::vec::Vec<u8>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct DeleteBuildCacheRequest {
     /// project namespace
     #[prost(string, tag = "1")]
@@ -100,6 +171,12 @@ pub struct BuildCacheMetadata {
     /// cache hit timestamp
     #[prost(message, optional, tag = "4")]
     pub timestamp: ::core::option::Option<::prost_types::Timestamp>,
+    /// cache size in byte
+    #[prost(uint64, tag = "5")]
+    pub size: u64,
+    /// eviction reason, none if cache present
+    #[prost(string, optional, tag = "6")]
+    pub eviction: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ScanBuildCacheResponse {
@@ -208,6 +285,25 @@ pub mod builder_client {
             let path = http::uri::PathAndQuery::from_static("/build.Builder/GetBuildLog");
             self.inner.unary(request.into_request(), path, codec).await
         }
+        pub async fn stream_build_log(
+            &mut self,
+            request: impl tonic::IntoRequest<super::StreamBuildLogRequest>,
+        ) -> Result<
+            tonic::Response<tonic::codec::Streaming<super::StreamBuildLogResponse>>,
+            tonic::Status,
+        > {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/build.Builder/StreamBuildLog");
+            self.inner
+                .server_streaming(request.into_request(), path, codec)
+                .await
+        }
         pub async fn scan_build(
             &mut self,
             request: impl tonic::IntoRequest<super::ScanBuildRequest>,
@@ -271,6 +367,14 @@ pub mod builder_server {
             &self,
             request: tonic::Request<super::GetBuildLogRequest>,
         ) -> Result<tonic::Response<super::GetBuildLogResponse>, tonic::Status>;
+        #[doc = "Server streaming response type for the StreamBuildLog method."]
+        type StreamBuildLogStream: futures_core::Stream<Item = Result<super::StreamBuildLogResponse, tonic::Status>>
+            + Send
+            + 'static;
+        async fn stream_build_log(
+            &self,
+            request: tonic::Request<super::StreamBuildLogRequest>,
+        ) -> Result<tonic::Response<Self::StreamBuildLogStream>, tonic::Status>;
         async fn scan_build(
             &self,
             request: tonic::Request<super::ScanBuildRequest>,
@@ -416,6 +520,42 @@ pub mod builder_server {
                     };
                     Box::pin(fut)
                 }
+                "/build.Builder/StreamBuildLog" => {
+                    #[allow(non_camel_case_types)]
+                    struct StreamBuildLogSvc<T: Builder>(pub Arc<T>);
+                    impl<T: Builder>
+                        tonic::server::ServerStreamingService<super::StreamBuildLogRequest>
+                        for StreamBuildLogSvc<T>
+                    {
+                        type Response = super::StreamBuildLogResponse;
+                        type ResponseStream = T::StreamBuildLogStream;
+                        type Future =
+                            BoxFuture<tonic::Response<Self::ResponseStream>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::StreamBuildLogRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).stream_build_log(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = StreamBuildLogSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 "/build.Builder/ScanBuild" => {
                     #[allow(non_camel_case_types)]
                     struct ScanBuildSvc<T: Builder>(pub Arc<T>);
@@ -5,12 +5,14 @@ use crate::{
         repository::repository_client::RepositoryClient,
         schedule::scheduler_client::SchedulerClient,
     },
+    Register, ELECTION_SCHEDULER,
 };
 use serde::Deserialize;
-use std::fmt;
+use std::{fmt, sync::Arc};
+use tokio::sync::Mutex;
 use tonic::transport::Channel;
 
-#[derive(Deserialize)]
+#[derive(Clone, Copy, Deserialize)]
 pub enum RpcProtocolType {
     Http,
     Https,
@@ -148,3 +150,45 @@ impl<'a> SchedulerClientBuilder<'a> {
         Ok(client)
     }
 }
+
+// leader address and connected client
+type SchedulerLeader = (String, SchedulerClient<Channel>);
+
+// scheduler client connected to elected leader, reconnect once leader changed
+#[derive(Clone)]
+pub struct SchedulerLeaderClient {
+    protocol: RpcProtocolType,
+    register: Register,
+    leader: Arc<Mutex<Option<SchedulerLeader>>>,
+}
+
+impl SchedulerLeaderClient {
+    pub fn new(protocol: RpcProtocolType, register: Register) -> Self {
+        SchedulerLeaderClient {
+            protocol,
+            register,
+            leader: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    pub async fn connect(&self) -> Result<SchedulerClient<Channel>> {
+        let mut register = self.register.clone();
+        let address = match register.get_leader(ELECTION_SCHEDULER).await? {
+            Some(address) => address,
+            None => return Err(tonic::Status::unavailable("scheduler leader not elected").into()),
+        };
+        let mut leader = self.leader.lock().await;
+        if let Some((leader_address, client)) = leader.as_ref() {
+            if *leader_address == address {
+                return Ok(client.clone());
+            }
+        }
+        let client = SchedulerClientBuilder::default()
+            .protocol(self.protocol)
+            .address(address.as_str())
+            .connect()
+            .await?;
+        *leader = Some((address, client.clone()));
+        Ok(client)
+    }
+}
@@ -65,6 +65,12 @@ pub struct GetAppResponse {
     /// app binaries
     #[prost(bytes = "vec", tag = "1")]
     pub buffer: ::prost::alloc::vec::Vec<u8>,
+    /// sha256 digest of app binaries in hex
+    #[prost(string, optional, tag = "2")]
+    pub digest: ::core::option::Option<::prost::alloc::string::String>,
+    /// compression of app binaries
+    #[prost(string, optional, tag = "3")]
+    pub compression: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct PostAppRequest {
@@ -80,6 +86,12 @@ pub struct PostAppRequest {
     /// app binaries
     #[prost(bytes = "vec", tag = "4")]
     pub buffer: ::prost::alloc::vec::Vec<u8>,
+    /// sha256 digest of app binaries in hex, not verified if empty
+    #[prost(string, tag = "5")]
+    pub digest: ::prost::alloc::string::String,
+    /// compression of app binaries
+    #[prost(string, optional, tag = "6")]
+    pub compression: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct PostAppResponse {}
@@ -197,6 +209,55 @@ pub struct DeleteCatalogsRequest {
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct DeleteCatalogsResponse {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetSourceRequest {
+    /// project namespace
+    #[prost(string, tag = "1")]
+    pub namespace: ::prost::alloc::string::String,
+    /// project id
+    #[prost(string, tag = "2")]
+    pub id: ::prost::alloc::string::String,
+    /// project build version
+    #[prost(uint64, tag = "3")]
+    pub version: u64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetSourceResponse {
+    /// source bundle
+    #[prost(bytes = "vec", tag = "1")]
+    pub buffer: ::prost::alloc::vec::Vec<u8>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PostSourceRequest {
+    /// project namespace
+    #[prost(string, tag = "1")]
+    pub namespace: ::prost::alloc::string::String,
+    /// project id
+    #[prost(string, tag = "2")]
+    pub id: ::prost::alloc::string::String,
+    /// project build version
+    #[prost(uint64, tag = "3")]
+    pub version: u64,
+    /// source bundle
+    #[prost(bytes = "vec", tag = "4")]
+    pub buffer: ::prost::alloc::vec::Vec<u8>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PostSourceResponse {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DeleteSourceRequest {
+    /// project namespace
+    #[prost(string, tag = "1")]
+    pub namespace: ::prost::alloc::string::String,
+    /// project id
+    #[prost(string, tag = "2")]
+    pub id: ::prost::alloc::string::String,
+    /// project build version
+    #[prost(uint64, tag = "3")]
+    pub version: u64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DeleteSourceResponse {}
 #[doc = r" Generated client implementations."]
 pub mod repository_client {
     #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
@@ -430,6 +491,48 @@ pub mod repository_client {
                 http::uri::PathAndQuery::from_static("/repository.Repository/DeleteCatalogs");
             self.inner.unary(request.into_request(), path, codec).await
         }
+        pub async fn get_source(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetSourceRequest>,
+        ) -> Result<tonic::Response<super::GetSourceResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/repository.Repository/GetSource");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn post_source(
+            &mut self,
+            request: impl tonic::IntoRequest<super::PostSourceRequest>,
+        ) -> Result<tonic::Response<super::PostSourceResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/repository.Repository/PostSource");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn delete_source(
+            &mut self,
+            request: impl tonic::IntoRequest<super::DeleteSourceRequest>,
+        ) -> Result<tonic::Response<super::DeleteSourceResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/repository.Repository/DeleteSource");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
     }
 }
 #[doc = r" Generated server implementations."]
@@ -487,6 +590,18 @@ pub mod repository_server {
             &self,
             request: tonic::Request<super::DeleteCatalogsRequest>,
         ) -> Result<tonic::Response<super::DeleteCatalogsResponse>, tonic::Status>;
+        async fn get_source(
+            &self,
+            request: tonic::Request<super::GetSourceRequest>,
+        ) -> Result<tonic::Response<super::GetSourceResponse>, tonic::Status>;
+        async fn post_source(
+            &self,
+            request: tonic::Request<super::PostSourceRequest>,
+        ) -> Result<tonic::Response<super::PostSourceResponse>, tonic::Status>;
+        async fn delete_source(
+            &self,
+            request: tonic::Request<super::DeleteSourceRequest>,
+        ) -> Result<tonic::Response<super::DeleteSourceResponse>, tonic::Status>;
     }
     #[derive(Debug)]
     pub struct RepositoryServer<T: Repository> {
@@ -910,6 +1025,99 @@ pub mod repository_server {
                     };
                     Box::pin(fut)
                 }
+                "/repository.Repository/GetSource" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetSourceSvc<T: Repository>(pub Arc<T>);
+                    impl<T: Repository> tonic::server::UnaryService<super::GetSourceRequest> for GetSourceSvc<T> {
+                        type Response = super::GetSourceResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetSourceRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).get_source(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetSourceSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/repository.Repository/PostSource" => {
+                    #[allow(non_camel_case_types)]
+                    struct PostSourceSvc<T: Repository>(pub Arc<T>);
+                    impl<T: Repository> tonic::server::UnaryService<super::PostSourceRequest> for PostSourceSvc<T> {
+                        type Response = super::PostSourceResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::PostSourceRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).post_source(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = PostSourceSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/repository.Repository/DeleteSource" => {
+                    #[allow(non_camel_case_types)]
+                    struct DeleteSourceSvc<T: Repository>(pub Arc<T>);
+                    impl<T: Repository> tonic::server::UnaryService<super::DeleteSourceRequest> for DeleteSourceSvc<T> {
+                        type Response = super::DeleteSourceResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::DeleteSourceRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).delete_source(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = DeleteSourceSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => Box::pin(async move {
                     Ok(http::Response::builder()
                         .status(200)
@@ -9,6 +9,10 @@ pub struct ScheduleRequest {
     /// target platform
     #[prost(string, optional, tag = "3")]
     pub target_platform: ::core::option::Option<::prost::alloc::string::String>,
+    /// builder labels required
+    #[prost(map = "string, string", tag = "4")]
+    pub selector:
+        ::std::collections::HashMap<::prost::alloc::string::String, ::prost::alloc::string::String>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct BuilderInfo {
@@ -18,7 +22,7 @@ pub struct BuilderInfo {
     /// builder address
     #[prost(string, tag = "2")]
     pub address: ::prost::alloc::string::String,
-    /// target platform support by builder
+    /// target platform to build, requested or builder default
     #[prost(string, tag = "3")]
     pub target_platform: ::prost::alloc::string::String,
 }
@@ -27,6 +31,14 @@ pub struct ScheduleResponse {
     #[prost(message, optional, tag = "1")]
     pub builder_info: ::core::option::Option<BuilderInfo>,
 }
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReleaseRequest {
+    /// builder id scheduled
+    #[prost(string, tag = "1")]
+    pub builder_id: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReleaseResponse {}
 #[doc = r" Generated client implementations."]
 pub mod scheduler_client {
     #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
@@ -101,6 +113,21 @@ pub mod scheduler_client {
             let path = http::uri::PathAndQuery::from_static("/schedule.Scheduler/Schedule");
             self.inner.unary(request.into_request(), path, codec).await
         }
+        #[doc = " undo a schedule whose build never reached the builder"]
+        pub async fn release(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ReleaseRequest>,
+        ) -> Result<tonic::Response<super::ReleaseResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/schedule.Scheduler/Release");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
     }
 }
 #[doc = r" Generated server implementations."]
@@ -114,6 +141,11 @@ pub mod scheduler_server {
             &self,
             request: tonic::Request<super::ScheduleRequest>,
         ) -> Result<tonic::Response<super::ScheduleResponse>, tonic::Status>;
+        #[doc = " undo a schedule whose build never reached the builder"]
+        async fn release(
+            &self,
+            request: tonic::Request<super::ReleaseRequest>,
+        ) -> Result<tonic::Response<super::ReleaseResponse>, tonic::Status>;
     }
     #[derive(Debug)]
     pub struct SchedulerServer<T: Scheduler> {
@@ -185,6 +217,37 @@ pub mod scheduler_server {
                     };
                     Box::pin(fut)
                 }
+                "/schedule.Scheduler/Release" => {
+                    #[allow(non_camel_case_types)]
+                    struct ReleaseSvc<T: Scheduler>(pub Arc<T>);
+                    impl<T: Scheduler> tonic::server::UnaryService<super::ReleaseRequest> for ReleaseSvc<T> {
+                        type Response = super::ReleaseResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ReleaseRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).release(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ReleaseSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => Box::pin(async move {
                     Ok(http::Response::builder()
                         .status(200)
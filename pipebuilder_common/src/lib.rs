@@ -15,6 +15,7 @@ mod register;
 mod resource;
 mod schedule;
 mod service;
+mod source;
 mod time;
 mod utils;
 
@@ -33,5 +34,6 @@ pub use register::*;
 pub use resource::*;
 pub use schedule::*;
 pub use service::*;
+pub use source::*;
 pub use time::*;
 pub use utils::*;
@@ -1,7 +1,11 @@
-use crate::{Resource, ResourceType};
+use crate::{dependency_policy_error, Resource, ResourceType, Result};
 use chrono::{DateTime, Utc};
+use pipegen::models::{App, Dependency};
 use serde::{Deserialize, Serialize};
 
+// registry of dependencies without git or path source
+const REGISTRY_CRATES_IO: &str = "crates-io";
+
 #[derive(Deserialize, Serialize)]
 pub struct Namespace {
     pub created: DateTime<Utc>,
@@ -25,3 +29,155 @@ impl Resource for Namespace {
         ResourceType::Namespace
     }
 }
+
+// dependency sources allowed in namespace, unrestricted if no policy found
+#[derive(Deserialize, Serialize, Clone, Default, PartialEq)]
+pub struct DependencyPolicy {
+    // allowed registries, e.g crates-io, any registry if not provided
+    pub registries: Option<Vec<String>>,
+    // allowed git hosts, e.g github.com, any host if not provided
+    pub git_hosts: Option<Vec<String>>,
+    // banned crates
+    #[serde(default)]
+    pub banned_crates: Vec<String>,
+    // allow path dependencies on builder filesystem
+    #[serde(default)]
+    pub allow_path: bool,
+}
+
+impl DependencyPolicy {
+    // list violations of dependencies
+    pub fn check(&self, dependencies: &[Dependency]) -> Vec<String> {
+        let mut violations: Vec<String> = vec![];
+        for dependency in dependencies {
+            let name = dependency.get_name();
+            // crate name if dependency renamed
+            let package = dependency.get_package().unwrap_or_else(|| name.clone());
+            if self.banned_crates.contains(&package) {
+                violations.push(format!("crate '{}' is banned", package));
+            }
+            if let Some(path) = dependency.get_path() {
+                if !self.allow_path {
+                    violations.push(format!(
+                        "dependency '{}' with path '{}' not allowed",
+                        name, path
+                    ));
+                }
+                continue;
+            }
+            if let Some(git) = dependency.get_git() {
+                let host = git_host(git.as_str());
+                if !Self::is_allowed(self.git_hosts.as_ref(), host) {
+                    violations.push(format!(
+                        "dependency '{}' with git host '{}' not allowed",
+                        name, host
+                    ));
+                }
+                continue;
+            }
+            if !Self::is_allowed(self.registries.as_ref(), REGISTRY_CRATES_IO) {
+                violations.push(format!(
+                    "dependency '{}' with registry '{}' not allowed",
+                    name, REGISTRY_CRATES_IO
+                ));
+            }
+        }
+        violations
+    }
+
+    pub fn validate(&self, app: &App) -> Result<()> {
+        let violations = self.check(app.get_dependencies());
+        match violations.is_empty() {
+            true => Ok(()),
+            false => Err(dependency_policy_error(violations)),
+        }
+    }
+
+    pub fn validate_manifest(&self, buffer: &[u8]) -> Result<()> {
+        let app = App::from_buffer(buffer)?;
+        self.validate(&app)
+    }
+
+    fn is_allowed(allowed: Option<&Vec<String>>, source: &str) -> bool {
+        match allowed {
+            Some(allowed) => allowed
+                .iter()
+                .any(|allowed| allowed.eq_ignore_ascii_case(source)),
+            None => true,
+        }
+    }
+}
+
+impl Resource for DependencyPolicy {
+    fn ty() -> ResourceType {
+        ResourceType::DependencyPolicy
+    }
+}
+
+// host of git url, e.g https://github.com/pipebase/pipebase.git -> github.com
+fn git_host(url: &str) -> &str {
+    let url = url.split_once("://").map_or(url, |(_, url)| url);
+    let authority = url.split('/').next().unwrap_or_default();
+    let host = authority.rsplit('@').next().unwrap_or_default();
+    host.split(':').next().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::DependencyPolicy;
+
+    const TEST_MANIFEST: &str = r#"
+name: print_timer
+dependencies:
+  - name: pipewarp
+    git: https://git@github.com/pipebase/pipebase.git
+    modules: ["pipewarp::*"]
+  - name: pipemongo
+    path: /opt/pipebase/pipemongo
+    modules: ["pipemongo::*"]
+pipes:
+  - name: timer1
+    config:
+      ty: TimerConfig
+      path: catalogs/timer.yml
+    output: UnsignedLongLong
+  - name: printer
+    config:
+      ty: PrinterConfig
+    upstreams: ["timer1"]
+"#;
+
+    #[test]
+    fn test_dependency_policy() {
+        let policy = DependencyPolicy::default();
+        policy
+            .validate_manifest(TEST_MANIFEST.as_bytes())
+            .expect_err("path dependency allowed");
+        let policy = DependencyPolicy {
+            registries: Some(vec![String::from("crates-io")]),
+            git_hosts: Some(vec![String::from("github.com")]),
+            banned_crates: vec![],
+            allow_path: true,
+        };
+        policy
+            .validate_manifest(TEST_MANIFEST.as_bytes())
+            .expect("dependency policy violated");
+        let policy = DependencyPolicy {
+            registries: Some(vec![]),
+            git_hosts: Some(vec![String::from("gitlab.com")]),
+            banned_crates: vec![String::from("pipemongo")],
+            allow_path: false,
+        };
+        let app =
+            pipegen::models::App::from_buffer(TEST_MANIFEST.as_bytes()).expect("invalid manifest");
+        // git host, banned crate, path and default registry dependencies
+        let violations = policy.check(app.get_dependencies());
+        assert_eq!(
+            violations.len(),
+            3 + app.get_dependencies().len() - 2,
+            "{:?}",
+            violations
+        );
+    }
+}
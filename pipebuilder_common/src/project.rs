@@ -1,16 +1,27 @@
 use crate::{Resource, ResourceType};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 #[derive(Deserialize, Serialize)]
 pub struct Project {
     pub created: DateTime<Utc>,
+    // builder label selector of project builds
+    #[serde(default)]
+    pub selector: HashMap<String, String>,
 }
 
 impl Project {
     pub fn new() -> Self {
         let created = Utc::now();
-        Project { created }
+        Project {
+            created,
+            selector: HashMap::new(),
+        }
+    }
+
+    pub fn set_selector(&mut self, selector: HashMap<String, String>) {
+        self.selector = selector
     }
 }
 
@@ -297,6 +297,48 @@ impl Register {
         Ok(())
     }
 
+    // campaign for leadership of election with lease, block until elected, return leader key
+    pub async fn campaign(&mut self, name: &str, value: &str, lease_id: i64) -> Result<Vec<u8>> {
+        info!(election_name = name, "campaign for leader ...");
+        let resp = self.client.campaign(name, value, lease_id).await?;
+        info!(election_name = name, "elected as leader ...");
+        let leader_key = resp
+            .leader()
+            .map(|leader| leader.key().to_vec())
+            .unwrap_or_default();
+        Ok(leader_key)
+    }
+
+    // value proclaimed by current leader of election, none if no leader elected
+    pub async fn get_leader(&mut self, name: &str) -> Result<Option<String>> {
+        match self.leader(name).await? {
+            Some(kv) => Ok(Some(kv.value_str()?.to_owned())),
+            None => Ok(None),
+        }
+    }
+
+    // whether leader key still leads the election
+    pub async fn is_leader(&mut self, name: &str, leader_key: &[u8]) -> Result<bool> {
+        match self.leader(name).await? {
+            Some(kv) => Ok(kv.key() == leader_key),
+            None => Ok(false),
+        }
+    }
+
+    async fn leader(&mut self, name: &str) -> Result<Option<KeyValue>> {
+        let resp = match self.client.leader(name).await {
+            Ok(resp) => resp,
+            // etcd reply error status if no leader
+            Err(etcd_client::Error::GRpcStatus(status))
+                if status.message().contains("no leader") =>
+            {
+                return Ok(None)
+            }
+            Err(err) => return Err(err.into()),
+        };
+        Ok(resp.kv().cloned())
+    }
+
     async fn do_update_snapshot_resource<S>(
         &mut self,
         namespace: &str,
@@ -603,6 +645,34 @@ impl Register {
         Ok(())
     }
 
+    // delete resource and return deleted value, none if not exist
+    pub async fn take_resource<R>(
+        &mut self,
+        namespace: Option<&str>,
+        id: &str,
+        version: Option<u64>,
+    ) -> Result<Option<R>>
+    where
+        R: Resource + DeserializeOwned,
+    {
+        let builder = ResourceKeyBuilder::new().resource(R::ty()).id(id);
+        let builder = match namespace {
+            Some(namespace) => builder.namespace(namespace),
+            None => builder,
+        };
+        let key = match version {
+            Some(version) => builder.version(version).build(),
+            None => builder.build(),
+        };
+        let resp = self
+            .delete(key, Some(DeleteOptions::new().with_prev_key()))
+            .await?;
+        match resp.prev_kvs().first() {
+            Some(kv) => Ok(Some(serde_json::from_slice::<R>(kv.value())?)),
+            None => Ok(None),
+        }
+    }
+
     pub async fn is_resource_exist<R>(&mut self, namespace: &str, id: Option<&str>) -> Result<bool>
     where
         R: Resource,
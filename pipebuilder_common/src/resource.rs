@@ -3,6 +3,9 @@ use std::fmt;
 pub const RESOURCE_NODE: &str = "node";
 pub const RESOURCE_BUILD_SNAPSHOT: &str = "build/snapshot";
 pub const RESOURCE_BUILD_METADATA: &str = "build/metadata";
+pub const RESOURCE_BUILD_PENDING: &str = "build/pending";
+pub const RESOURCE_BUILD_MATRIX_SNAPSHOT: &str = "build-matrix/snapshot";
+pub const RESOURCE_BUILD_MATRIX_METADATA: &str = "build-matrix/metadata";
 pub const RESOURCE_MANIFEST_SNAPSHOT: &str = "manifest/snapshot";
 pub const RESOURCE_APP_METADATA: &str = "app/metadata";
 pub const RESOURCE_MANIFEST_METADATA: &str = "manifest/metadata";
@@ -12,6 +15,9 @@ pub const RESOURCE_CATALOG_SCHEMA_SNAPSHOT: &str = "catalog-schema/snapshot";
 pub const RESOURCE_CATALOG_SCHEMA_METADATA: &str = "catalog-schema/metadata";
 pub const RESOURCE_CATALOGS_SNAPSHOT: &str = "catalogs/snapshot";
 pub const RESOURCE_CATALOGS_METADATA: &str = "catalogs/metadata";
+pub const RESOURCE_SOURCE_METADATA: &str = "source/metadata";
+pub const RESOURCE_DEPENDENCY_POLICY: &str = "dependency-policy";
+pub const RESOURCE_LINT_POLICY: &str = "lint-policy";
 
 #[derive(Clone)]
 pub enum ResourceType {
@@ -19,6 +25,9 @@ pub enum ResourceType {
     AppMetadata,
     BuildSnapshot,
     BuildMetadata,
+    BuildPending,
+    BuildMatrixSnapshot,
+    BuildMatrixMetadata,
     ManifestSnapshot,
     ManifestMetadata,
     Namespace,
@@ -27,6 +36,9 @@ pub enum ResourceType {
     CatalogSchemaMetadata,
     CatalogsSnapshot,
     CatalogsMetadata,
+    SourceMetadata,
+    DependencyPolicy,
+    LintPolicy,
 }
 
 impl fmt::Display for ResourceType {
@@ -36,6 +48,9 @@ impl fmt::Display for ResourceType {
             ResourceType::AppMetadata => write!(f, "{}", RESOURCE_APP_METADATA),
             ResourceType::BuildSnapshot => write!(f, "{}", RESOURCE_BUILD_SNAPSHOT),
             ResourceType::BuildMetadata => write!(f, "{}", RESOURCE_BUILD_METADATA),
+            ResourceType::BuildPending => write!(f, "{}", RESOURCE_BUILD_PENDING),
+            ResourceType::BuildMatrixSnapshot => write!(f, "{}", RESOURCE_BUILD_MATRIX_SNAPSHOT),
+            ResourceType::BuildMatrixMetadata => write!(f, "{}", RESOURCE_BUILD_MATRIX_METADATA),
             ResourceType::ManifestSnapshot => write!(f, "{}", RESOURCE_MANIFEST_SNAPSHOT),
             ResourceType::ManifestMetadata => write!(f, "{}", RESOURCE_MANIFEST_METADATA),
             ResourceType::Namespace => write!(f, "{}", RESOURCE_NAMESPACE),
@@ -48,6 +63,9 @@ impl fmt::Display for ResourceType {
             }
             ResourceType::CatalogsSnapshot => write!(f, "{}", RESOURCE_CATALOGS_SNAPSHOT),
             ResourceType::CatalogsMetadata => write!(f, "{}", RESOURCE_CATALOGS_METADATA),
+            ResourceType::SourceMetadata => write!(f, "{}", RESOURCE_SOURCE_METADATA),
+            ResourceType::DependencyPolicy => write!(f, "{}", RESOURCE_DEPENDENCY_POLICY),
+            ResourceType::LintPolicy => write!(f, "{}", RESOURCE_LINT_POLICY),
         }
     }
 }
@@ -1,3 +1,9 @@
+use fnv::FnvHasher;
+use std::{
+    collections::{BTreeMap, HashSet},
+    hash::{Hash, Hasher},
+};
+
 pub trait ScheduleHash<T> {
     fn schedule_hash(&self) -> T;
 }
@@ -10,3 +16,180 @@ impl<'a> ScheduleHash<String> for ScheduleDescriptor<'a> {
         format!("{}/{}", self.0, self.1)
     }
 }
+
+// consistent hash ring, each node placed at its virtual node positions
+pub struct HashRing {
+    // virtual nodes per node
+    vnodes: usize,
+    // position -> node id
+    positions: BTreeMap<u64, String>,
+    nodes: HashSet<String>,
+}
+
+impl HashRing {
+    pub fn new(vnodes: usize) -> Self {
+        HashRing {
+            vnodes: vnodes.max(1),
+            positions: BTreeMap::new(),
+            nodes: HashSet::new(),
+        }
+    }
+
+    pub fn add(&mut self, node: &str) {
+        if !self.nodes.insert(node.to_owned()) {
+            return;
+        }
+        for vnode in 0..self.vnodes {
+            let position = ring_hash(&(node, vnode));
+            self.positions.insert(position, node.to_owned());
+        }
+    }
+
+    pub fn remove(&mut self, node: &str) {
+        if !self.nodes.remove(node) {
+            return;
+        }
+        self.positions.retain(|_, owner| owner != node);
+    }
+
+    pub fn clear(&mut self) {
+        self.positions.clear();
+        self.nodes.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    // owner of key, the first node clockwise from key position
+    pub fn get<K>(&self, key: &K) -> Option<&str>
+    where
+        K: Hash + ?Sized,
+    {
+        self.walk(key).into_iter().next()
+    }
+
+    // distinct nodes clockwise from key position, owner first
+    pub fn walk<K>(&self, key: &K) -> Vec<&str>
+    where
+        K: Hash + ?Sized,
+    {
+        let position = ring_hash(key);
+        let mut walked: Vec<&str> = Vec::with_capacity(self.nodes.len());
+        for (_, node) in self
+            .positions
+            .range(position..)
+            .chain(self.positions.range(..position))
+        {
+            if walked.len() == self.nodes.len() {
+                break;
+            }
+            if !walked.contains(&node.as_str()) {
+                walked.push(node.as_str());
+            }
+        }
+        walked
+    }
+}
+
+// fnv1a with murmur3 finalizer, spread similar keys across the ring
+fn ring_hash<K>(key: &K) -> u64
+where
+    K: Hash + ?Sized,
+{
+    let mut hasher = FnvHasher::default();
+    key.hash(&mut hasher);
+    let mut hash = hasher.finish();
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xff51afd7ed558ccd);
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xc4ceb9fe1a85ec53);
+    hash ^ (hash >> 33)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::{HashRing, ScheduleDescriptor, ScheduleHash};
+    use std::collections::HashMap;
+
+    const TEST_VIRTUAL_NODES: usize = 128;
+    const TEST_KEYS: usize = 10000;
+
+    fn test_ring(builders: usize) -> HashRing {
+        let mut ring = HashRing::new(TEST_VIRTUAL_NODES);
+        for i in 0..builders {
+            ring.add(format!("builder{}", i).as_str());
+        }
+        ring
+    }
+
+    fn test_assignments(ring: &HashRing) -> Vec<String> {
+        (0..TEST_KEYS)
+            .map(|i| {
+                let key =
+                    ScheduleDescriptor("dev", format!("project{}", i).as_str()).schedule_hash();
+                ring.get(key.as_str()).expect("empty ring").to_owned()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_hash_ring_builder_join() {
+        let mut ring = test_ring(4);
+        let before = test_assignments(&ring);
+        ring.add("builder4");
+        let after = test_assignments(&ring);
+        let moved = before
+            .iter()
+            .zip(after.iter())
+            .filter(|(before, after)| before != after)
+            .inspect(|(_, after)| assert_eq!("builder4", after.as_str()))
+            .count();
+        // ideally 1/5 of keys move to the new builder
+        let moved = moved as f64 / TEST_KEYS as f64;
+        assert!(moved > 0.1 && moved < 0.3, "moved {}", moved);
+    }
+
+    #[test]
+    fn test_hash_ring_builder_leave() {
+        let mut ring = test_ring(5);
+        let before = test_assignments(&ring);
+        ring.remove("builder2");
+        let after = test_assignments(&ring);
+        let mut moved = 0;
+        for (before, after) in before.iter().zip(after.iter()) {
+            if before == "builder2" {
+                assert_ne!("builder2", after.as_str());
+                moved += 1;
+                continue;
+            }
+            // keys of remaining builders never move
+            assert_eq!(before, after);
+        }
+        let moved = moved as f64 / TEST_KEYS as f64;
+        assert!(moved > 0.1 && moved < 0.3, "moved {}", moved);
+    }
+
+    #[test]
+    fn test_hash_ring_balance() {
+        let ring = test_ring(4);
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        for builder in test_assignments(&ring) {
+            *counts.entry(builder).or_default() += 1;
+        }
+        assert_eq!(4, counts.len());
+        for (builder, count) in counts {
+            // ideally 1/4 of keys per builder
+            let share = count as f64 / TEST_KEYS as f64;
+            assert!(share > 0.15 && share < 0.35, "{} share {}", builder, share);
+        }
+        let walked = ring.walk("dev/project0");
+        assert_eq!(4, walked.len());
+        assert_eq!(ring.get("dev/project0"), walked.first().copied());
+    }
+}
@@ -1,4 +1,5 @@
 use crate::{
+    cpu_load, free_disk_space,
     grpc::node::{self, node_server::Node},
     Period, Register, Resource, ResourceType, DEFAULT_NODE_HEARTBEAT_PERIOD,
     ENV_PIPEBUILDER_EXTERNAL_ADDR, ENV_PIPEBUILDER_NODE_ID,
@@ -6,9 +7,10 @@ use crate::{
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::{
+    collections::HashMap,
     sync::{
-        atomic::{AtomicU8, Ordering},
-        Arc,
+        atomic::{AtomicU8, AtomicUsize, Ordering},
+        Arc, RwLock,
     },
     time::Duration,
 };
@@ -85,6 +87,12 @@ pub struct NodeConfig {
     pub internal_address: String,
     pub external_address: Option<String>,
     pub heartbeat_period: Option<Period>,
+    // target platforms the node can build, default as host platform if not provided
+    pub target_platforms: Option<Vec<String>>,
+    // key value labels matched by build selectors, e.g memory: high
+    pub labels: Option<HashMap<String, String>>,
+    // linker per target platform for cross compilation, e.g aarch64-unknown-linux-gnu: aarch64-linux-gnu-gcc
+    pub linkers: Option<HashMap<String, String>>,
 }
 
 #[derive(Clone, Deserialize, Serialize)]
@@ -157,6 +165,15 @@ pub struct NodeState {
     pub status: NodeStatus,
     // timestamp
     pub timestamp: DateTime<Utc>,
+    // target platforms the node can build
+    #[serde(default)]
+    pub target_platforms: Vec<String>,
+    // build load, reported by builder only
+    #[serde(default)]
+    pub load: Option<NodeLoad>,
+    // key value labels
+    #[serde(default)]
+    pub labels: HashMap<String, String>,
 }
 
 impl Resource for NodeState {
@@ -171,30 +188,112 @@ impl NodeState {
     }
 
     pub fn accept_target_platform(&self, target_platform: &str) -> bool {
-        match target_platform {
-            "aarch64-unknown-linux-gnu" => {
-                matches!(self.arch, NodeArch::AARCH64) && matches!(self.os, NodeOS::LINUX)
-            }
-            "x86_64-apple-darwin" => {
-                matches!(self.arch, NodeArch::X86_64) && matches!(self.os, NodeOS::MACOS)
-            }
-            "x86_64-unknown-linux-gnu" => {
-                matches!(self.arch, NodeArch::X86_64) && matches!(self.os, NodeOS::LINUX)
-            }
-            _ => false,
-        }
+        self.target_platforms
+            .iter()
+            .any(|accepted| accepted == target_platform)
+    }
+
+    // whether all selector key values present in labels
+    pub fn match_labels(&self, selector: &HashMap<String, String>) -> bool {
+        selector
+            .iter()
+            .all(|(key, value)| self.labels.get(key) == Some(value))
     }
 
+    // whether app binary of target platform runs on node host
+    pub fn is_host_target_platform(&self, target_platform: &str) -> bool {
+        host_target_platform(&self.arch, &self.os).as_deref() == Some(target_platform)
+    }
+
+    // default target platform, the first one declared
     pub fn get_support_target_platform(&self) -> Option<String> {
-        match (&self.arch, &self.os) {
-            (NodeArch::AARCH64, NodeOS::LINUX) => Some(String::from("aarch64-unknown-linux-gnu")),
-            (NodeArch::X86_64, NodeOS::MACOS) => Some(String::from("x86_64-apple-darwin")),
-            (NodeArch::X86_64, NodeOS::LINUX) => Some(String::from("x86_64-unknown-linux-gnu")),
-            (_, _) => None,
-        }
+        self.target_platforms.first().cloned()
+    }
+}
+
+// builder load reported in heartbeat
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct NodeLoad {
+    // builds in progress
+    pub running_builds: usize,
+    // builds wait in local queue
+    pub queued_builds: usize,
+    // max number of builds run in parallel
+    pub max_parallel_builds: usize,
+    // free disk space of build workspace in bytes
+    pub free_disk: Option<u64>,
+    // one minute load average per cpu
+    pub cpu_load: Option<f64>,
+}
+
+impl NodeLoad {
+    // running and queued builds per build slot
+    pub fn get_build_load(&self) -> f64 {
+        let builds = self.running_builds + self.queued_builds;
+        builds as f64 / self.max_parallel_builds.max(1) as f64
     }
 }
 
+// load shared between node service and builds, sampled in heartbeat
+#[derive(Clone, Default)]
+pub struct NodeLoadGauge {
+    running_builds: Arc<AtomicUsize>,
+    queued_builds: Arc<AtomicUsize>,
+    // none until capacity set by builder
+    max_parallel_builds: Arc<RwLock<Option<usize>>>,
+    // directory whose free disk space reported
+    disk_path: Arc<RwLock<Option<String>>>,
+}
+
+impl NodeLoadGauge {
+    pub fn set_capacity(&self, max_parallel_builds: usize, disk_path: String) {
+        *self
+            .max_parallel_builds
+            .write()
+            .expect("lock max parallel builds") = Some(max_parallel_builds);
+        *self.disk_path.write().expect("lock disk path") = Some(disk_path);
+    }
+
+    pub fn set_builds(&self, running_builds: usize, queued_builds: usize) {
+        self.running_builds.store(running_builds, Ordering::Release);
+        self.queued_builds.store(queued_builds, Ordering::Release);
+    }
+
+    // sample load, none if capacity never set
+    pub fn sample(&self) -> Option<NodeLoad> {
+        let max_parallel_builds = (*self
+            .max_parallel_builds
+            .read()
+            .expect("lock max parallel builds"))?;
+        let disk_path = self.disk_path.read().expect("lock disk path").clone();
+        let free_disk = disk_path.and_then(|disk_path| free_disk_space(disk_path).ok());
+        Some(NodeLoad {
+            running_builds: self.running_builds.load(Ordering::Acquire),
+            queued_builds: self.queued_builds.load(Ordering::Acquire),
+            max_parallel_builds,
+            free_disk,
+            cpu_load: cpu_load().ok(),
+        })
+    }
+}
+
+// target platform of host
+pub(crate) fn host_target_platform(arch: &NodeArch, os: &NodeOS) -> Option<String> {
+    match (arch, os) {
+        (NodeArch::AARCH64, NodeOS::LINUX) => Some(String::from("aarch64-unknown-linux-gnu")),
+        (NodeArch::X86_64, NodeOS::MACOS) => Some(String::from("x86_64-apple-darwin")),
+        (NodeArch::X86_64, NodeOS::LINUX) => Some(String::from("x86_64-unknown-linux-gnu")),
+        (_, _) => None,
+    }
+}
+
+// whether target platform is the one of host
+pub fn is_host_target_platform(target_platform: &str) -> bool {
+    let arch: NodeArch = std::env::consts::ARCH.into();
+    let os: NodeOS = std::env::consts::OS.into();
+    host_target_platform(&arch, &os).as_deref() == Some(target_platform)
+}
+
 #[derive(Clone)]
 pub struct NodeService {
     // node id
@@ -209,12 +308,20 @@ pub struct NodeService {
     internal_address: String,
     // node external address
     external_address: String,
+    // target platforms the node can build
+    target_platforms: Vec<String>,
+    // key value labels
+    labels: HashMap<String, String>,
+    // target platform -> linker
+    linkers: HashMap<String, String>,
     // node lease id for ownership of keys
     lease_id: i64,
     // node heartbeat period
     heartbeat_period: Duration,
     // node runtime status code
     status_code: Arc<AtomicU8>,
+    // node load reported in heartbeat
+    load_gauge: NodeLoadGauge,
 }
 
 impl NodeService {
@@ -236,6 +343,12 @@ impl NodeService {
         let heartbeat_period = heartbeat_period.unwrap_or(DEFAULT_NODE_HEARTBEAT_PERIOD);
         let arch: NodeArch = std::env::consts::ARCH.into();
         let os: NodeOS = std::env::consts::OS.into();
+        let target_platforms = match config.target_platforms {
+            Some(target_platforms) => target_platforms,
+            None => host_target_platform(&arch, &os).into_iter().collect(),
+        };
+        let labels = config.labels.unwrap_or_default();
+        let linkers = config.linkers.unwrap_or_default();
         NodeService {
             id,
             role,
@@ -243,9 +356,13 @@ impl NodeService {
             os,
             internal_address,
             external_address,
+            target_platforms,
+            labels,
+            linkers,
             lease_id,
             heartbeat_period: heartbeat_period.into(),
             status_code: Arc::new(AtomicU8::new(NodeStatus::Active as u8)),
+            load_gauge: NodeLoadGauge::default(),
         }
     }
 
@@ -265,6 +382,14 @@ impl NodeService {
         self.status_code.clone()
     }
 
+    pub fn get_load_gauge(&self) -> NodeLoadGauge {
+        self.load_gauge.clone()
+    }
+
+    pub fn get_linkers(&self) -> HashMap<String, String> {
+        self.linkers.clone()
+    }
+
     pub fn run(&self, mut register: Register, shutdown_tx: Sender<()>) {
         let heartbeat_period = self.heartbeat_period.to_owned();
         let mut interval = tokio::time::interval(heartbeat_period);
@@ -274,7 +399,10 @@ impl NodeService {
         let os = self.os.to_owned();
         let internal_address = self.internal_address.to_owned();
         let external_address = self.external_address.to_owned();
+        let target_platforms = self.target_platforms.to_owned();
+        let labels = self.labels.to_owned();
         let status_code = self.status_code.clone();
+        let load_gauge = self.load_gauge.clone();
         let lease_id = self.lease_id;
         let _ = tokio::spawn(async move {
             loop {
@@ -291,6 +419,9 @@ impl NodeService {
                     external_address: external_address.clone(),
                     status: status.clone(),
                     timestamp,
+                    target_platforms: target_platforms.clone(),
+                    load: load_gauge.sample(),
+                    labels: labels.clone(),
                 };
                 match register
                     .put_resource::<NodeState>(None, id.as_str(), None, state, lease_id)
@@ -358,3 +489,46 @@ impl Node for NodeService {
         Ok(tonic::Response::new(node::ShutdownResponse {}))
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use crate::{NodeArch, NodeOS, NodeRole, NodeState, NodeStatus};
+    use chrono::Utc;
+    use std::collections::HashMap;
+
+    fn test_labels(labels: &[(&str, &str)]) -> HashMap<String, String> {
+        labels
+            .iter()
+            .map(|(key, value)| (String::from(*key), String::from(*value)))
+            .collect()
+    }
+
+    fn test_node_state(labels: &[(&str, &str)]) -> NodeState {
+        NodeState {
+            role: NodeRole::Builder,
+            arch: NodeArch::X86_64,
+            os: NodeOS::LINUX,
+            internal_address: String::from("127.0.0.1:16000"),
+            external_address: String::from("127.0.0.1:16000"),
+            status: NodeStatus::Active,
+            timestamp: Utc::now(),
+            target_platforms: vec![],
+            load: None,
+            labels: test_labels(labels),
+        }
+    }
+
+    #[test]
+    fn test_match_labels() {
+        let state = test_node_state(&[("gpu", "true"), ("zone", "us-east")]);
+        assert!(state.match_labels(&HashMap::new()));
+        assert!(state.match_labels(&test_labels(&[("gpu", "true")])));
+        assert!(state.match_labels(&test_labels(&[("gpu", "true"), ("zone", "us-east")])));
+        assert!(!state.match_labels(&test_labels(&[("gpu", "false")])));
+        assert!(!state.match_labels(&test_labels(&[("gpu", "true"), ("disk", "ssd")])));
+        let state = test_node_state(&[]);
+        assert!(state.match_labels(&HashMap::new()));
+        assert!(!state.match_labels(&test_labels(&[("gpu", "true")])));
+    }
+}
@@ -0,0 +1,116 @@
+use crate::{
+    create_directory, invalid_source_path, write_file, BlobResource, PathBuilder, Resource,
+    ResourceType, Result,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Component, Path};
+
+// metadata for app source bundle (namespace, id, version)
+#[derive(Deserialize, Serialize)]
+pub struct SourceMetadata {
+    // pull count
+    pub pulls: u64,
+    // source bundle size in byte
+    pub size: usize,
+    // created timestamp
+    pub created: DateTime<Utc>,
+}
+
+impl BlobResource for SourceMetadata {
+    fn new(size: usize) -> Self {
+        SourceMetadata {
+            pulls: 0,
+            size,
+            created: Utc::now(),
+        }
+    }
+
+    fn incr_usage(&mut self) {
+        self.pulls += 1
+    }
+}
+
+impl Resource for SourceMetadata {
+    fn ty() -> ResourceType {
+        ResourceType::SourceMetadata
+    }
+}
+
+// compiled app source file
+#[derive(Serialize, Deserialize)]
+pub struct Source {
+    // path relative to app directory, e.g src/main.rs
+    pub path: String,
+    // file content
+    pub content: String,
+}
+
+impl Source {
+    pub fn new(path: String, content: String) -> Self {
+        Source { path, content }
+    }
+
+    // serialize array of sources as source bundle
+    pub fn to_buffer(sources: &[Self]) -> Result<Vec<u8>> {
+        let buffer = serde_yaml::to_vec(sources)?;
+        Ok(buffer)
+    }
+
+    // deserialize array of sources from source bundle
+    pub fn from_buffer(sources: &[u8]) -> Result<Vec<Self>> {
+        let sources: Vec<Self> = serde_yaml::from_slice(sources)?;
+        Ok(sources)
+    }
+
+    // source path should be relative and stay inside app directory
+    fn validate_path(path: &str) -> Result<()> {
+        let path_ref = Path::new(path);
+        let is_valid = !path.is_empty()
+            && path_ref
+                .components()
+                .all(|component| matches!(component, Component::Normal(_) | Component::CurDir));
+        match is_valid {
+            true => Ok(()),
+            false => Err(invalid_source_path(path.to_owned())),
+        }
+    }
+
+    pub async fn dump_sources<P>(sources: &[u8], directory: P) -> Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        let sources = Self::from_buffer(sources)?;
+        // reject whole bundle before any file written
+        for source in sources.iter() {
+            Self::validate_path(source.path.as_str())?;
+        }
+        for source in sources.iter() {
+            let path = PathBuilder::default()
+                .push(directory.as_ref())
+                .push(source.path.as_str())
+                .build();
+            if let Some(parent) = path.parent() {
+                create_directory(parent).await?;
+            }
+            write_file(path.as_path(), source.content.as_bytes()).await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::Source;
+
+    #[test]
+    fn test_validate_source_path() {
+        assert!(Source::validate_path("app/src/main.rs").is_ok());
+        assert!(Source::validate_path("./app/Cargo.lock").is_ok());
+        assert!(Source::validate_path("").is_err());
+        assert!(Source::validate_path("/etc/passwd").is_err());
+        assert!(Source::validate_path("../outside").is_err());
+        assert!(Source::validate_path("app/../../outside").is_err());
+    }
+}
@@ -1,19 +1,24 @@
-use crate::errors::{cargo_error, Result};
+use crate::{
+    errors::{build_error, cargo_error, Result},
+    BuildOptions,
+};
 use chrono::{DateTime, TimeZone, Utc};
 use etcd_client::{Event, EventType};
 use filetime::FileTime;
-use fnv::FnvHasher;
 use fslock::{LockFile, ToOsStr};
 use pipegen::models::Dependency;
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use std::{
     collections::HashMap,
-    ffi::OsString,
+    ffi::{CString, OsString},
     future::Future,
-    hash::{Hash, Hasher},
+    os::unix::ffi::OsStrExt,
     path::{Path, PathBuf},
     pin::Pin,
+    process::Stdio,
+    sync::{Arc, Mutex},
+    time::Duration,
 };
 use tokio::{
     fs::{self, File},
@@ -39,6 +44,30 @@ where
     Ok(file)
 }
 
+pub async fn append_file<P>(path: P) -> Result<File>
+where
+    P: AsRef<std::path::Path>,
+{
+    let file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .await?;
+    Ok(file)
+}
+
+// append section title to log, separate output of build steps
+pub async fn append_log_section<P>(path: P, section: &str) -> Result<()>
+where
+    P: AsRef<std::path::Path>,
+{
+    let mut file = append_file(path).await?;
+    file.write_all(format!("\n==> {}\n", section).as_bytes())
+        .await?;
+    file.flush().await?;
+    Ok(())
+}
+
 pub async fn read_file<P>(path: P) -> Result<Vec<u8>>
 where
     P: AsRef<std::path::Path>,
@@ -174,6 +203,64 @@ where
     })
 }
 
+// total size in bytes of file or files under directory
+pub fn directory_size<P>(path: P) -> Pin<Box<dyn Future<Output = Result<u64>> + Send>>
+where
+    P: AsRef<Path> + Send + 'static,
+{
+    Box::pin(async move {
+        let mut path_buf = PathBuf::new();
+        path_buf.push(path);
+        let path = path_buf;
+        let metadata = fs::symlink_metadata(&path).await?;
+        if !metadata.is_dir() {
+            return Ok(metadata.len());
+        }
+        let mut size = 0;
+        let mut entries = fs::read_dir(&path).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            size += directory_size(entry.path()).await?;
+        }
+        Ok(size)
+    })
+}
+
+// names of sub directories
+pub async fn list_sub_directories<P>(path: P) -> Result<Vec<String>>
+where
+    P: AsRef<Path>,
+{
+    let mut names: Vec<String> = vec![];
+    let mut entries = fs::read_dir(path).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        if !entry.file_type().await?.is_dir() {
+            continue;
+        }
+        if let Some(name) = entry.file_name().to_str() {
+            names.push(name.to_owned())
+        }
+    }
+    Ok(names)
+}
+
+// last modification time of file or directory
+pub async fn modified_time<P>(path: P) -> Result<DateTime<Utc>>
+where
+    P: AsRef<Path>,
+{
+    let metadata = fs::metadata(path).await?;
+    Ok(metadata.modified()?.into())
+}
+
+// set modification time of file or directory as now
+pub fn touch<P>(path: P) -> Result<()>
+where
+    P: AsRef<Path>,
+{
+    filetime::set_file_mtime(path, FileTime::now())?;
+    Ok(())
+}
+
 // move directory and return success flag
 pub async fn move_directory<P>(from: P, to: P) -> Result<()>
 where
@@ -208,6 +295,8 @@ pub fn append_dot_format_suffix(filename: &str, format: &str) -> String {
 
 // run cmd and collect status and output
 async fn cmd_status_output(mut cmd: Command) -> Result<(i32, String)> {
+    // kill child process if dropped, e.g build timeout
+    cmd.kill_on_drop(true);
     let output = cmd.output().await?;
     match output.status.success() {
         true => {
@@ -222,15 +311,189 @@ async fn cmd_status_output(mut cmd: Command) -> Result<(i32, String)> {
     }
 }
 
-// run cmd and collect status
-async fn cmd_status(mut cmd: Command) -> Result<i32> {
-    let status = cmd.status().await?;
+// strip symbols of app binary into output path
+pub async fn strip_binary<P>(binary_path: P, output_path: P) -> Result<()>
+where
+    P: AsRef<std::path::Path>,
+{
+    let mut cmd = Command::new("strip");
+    cmd.arg("-o")
+        .arg(output_path.as_ref())
+        .arg(binary_path.as_ref());
+    let (code, stderr) = cmd_status_output(cmd).await?;
+    match code == 0 {
+        true => Ok(()),
+        false => Err(build_error(
+            String::from("strip"),
+            format!("strip exit with code {}, stderr: {}", code, stderr),
+        )),
+    }
+}
+
+// period to check whether killed process group exited
+const PROCESS_GROUP_EXIT_CHECK_PERIOD: Duration = Duration::from_millis(100);
+// max checks for processes left in killed group, e.g rustc
+const PROCESS_GROUP_EXIT_CHECK_LIMIT: u32 = 50;
+
+#[derive(Default)]
+struct ProcessGroupState {
+    // process group id of running child
+    pgid: Option<i32>,
+    // process group killed on drop, exit not confirmed yet
+    killed: Option<i32>,
+    // cancelled, never spawn child
+    cancelled: bool,
+}
+
+// process group of child process, shared between build and canceller
+#[derive(Clone, Default)]
+pub struct ProcessGroup {
+    state: Arc<Mutex<ProcessGroupState>>,
+}
+
+impl ProcessGroup {
+    // spawn child as group leader, none if cancelled
+    fn spawn(&self, cmd: &mut Command) -> Result<Option<tokio::process::Child>> {
+        let mut state = self.state.lock().expect("lock process group");
+        if state.cancelled {
+            return Ok(None);
+        }
+        let child = cmd.process_group(0).spawn()?;
+        state.pgid = child.id().map(|id| id as i32);
+        // group killed before exited or waited, its pgid may be reused since
+        state.killed = None;
+        Ok(Some(child))
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        let state = self.state.lock().expect("lock process group");
+        state.cancelled
+    }
+
+    fn is_running(&self) -> bool {
+        let state = self.state.lock().expect("lock process group");
+        state.pgid.is_some()
+    }
+
+    // kill all processes in group and wait until exit, never spawn child afterwards
+    pub async fn cancel(&self) {
+        self.state.lock().expect("lock process group").cancelled = true;
+        self.kill().await
+    }
+
+    // kill all processes in group, including one killed on drop, and wait until exit
+    pub async fn kill(&self) {
+        let pgid = {
+            let mut state = self.state.lock().expect("lock process group");
+            state.pgid.or_else(|| state.killed.take())
+        };
+        let pgid = match pgid {
+            Some(pgid) => pgid,
+            None => return,
+        };
+        info!(pgid = pgid, "kill process group");
+        kill_process_group(pgid);
+        // wait child reaped
+        while self.is_running() {
+            tokio::time::sleep(PROCESS_GROUP_EXIT_CHECK_PERIOD).await;
+        }
+        // wait processes left in group reaped by init
+        for _ in 0..PROCESS_GROUP_EXIT_CHECK_LIMIT {
+            if !is_process_group_alive(pgid) {
+                return;
+            }
+            tokio::time::sleep(PROCESS_GROUP_EXIT_CHECK_PERIOD).await;
+        }
+        warn!(pgid = pgid, "process group still alive after kill");
+    }
+}
+
+// release process group, kill it if child not exited, e.g dropped by build timeout
+struct ProcessGroupGuard<'a> {
+    group: &'a ProcessGroup,
+    exited: bool,
+}
+
+impl<'a> Drop for ProcessGroupGuard<'a> {
+    fn drop(&mut self) {
+        let mut state = self.group.state.lock().expect("lock process group");
+        if let Some(pgid) = state.pgid.take() {
+            if !self.exited {
+                kill_process_group(pgid);
+                state.killed = Some(pgid);
+            }
+        }
+    }
+}
+
+fn kill_process_group(pgid: i32) {
+    // SAFETY: killpg has no memory safety requirement
+    unsafe {
+        libc::killpg(pgid, libc::SIGKILL);
+    }
+}
+
+fn is_process_group_alive(pgid: i32) -> bool {
+    // SAFETY: signal 0 only checks existence of processes in group
+    unsafe { libc::killpg(pgid, 0) == 0 }
+}
+
+// run cmd in own process group and collect status, pending if group cancelled
+async fn cmd_status_in_group(mut cmd: Command, group: &ProcessGroup) -> Result<i32> {
+    cmd.kill_on_drop(true);
+    let mut child = match group.spawn(&mut cmd)? {
+        Some(child) => child,
+        None => return std::future::pending().await,
+    };
+    let mut guard = ProcessGroupGuard {
+        group,
+        exited: false,
+    };
+    let status = child.wait().await;
+    guard.exited = true;
+    drop(guard);
+    if group.is_cancelled() {
+        // wait until aborted by canceller
+        return std::future::pending().await;
+    }
+    let status = status?;
     match status.success() {
         true => Ok(0),
         false => Ok(status.code().unwrap_or(1)),
     }
 }
 
+// run cmd in own process group and collect status and stderr, pending if group cancelled
+async fn cmd_status_output_in_group(
+    mut cmd: Command,
+    group: &ProcessGroup,
+) -> Result<(i32, String)> {
+    cmd.stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    let child = match group.spawn(&mut cmd)? {
+        Some(child) => child,
+        None => return std::future::pending().await,
+    };
+    let mut guard = ProcessGroupGuard {
+        group,
+        exited: false,
+    };
+    let output = child.wait_with_output().await;
+    guard.exited = true;
+    drop(guard);
+    if group.is_cancelled() {
+        // wait until aborted by canceller
+        return std::future::pending().await;
+    }
+    let output = output?;
+    let stderr = String::from_utf8(output.stderr)?;
+    match output.status.success() {
+        true => Ok((0, stderr)),
+        false => Ok((output.status.code().unwrap_or(1), stderr)),
+    }
+}
+
 // cargo ops
 fn cargo_binary() -> OsString {
     match std::env::var_os("CARGO") {
@@ -239,26 +502,26 @@ fn cargo_binary() -> OsString {
     }
 }
 
-pub async fn cargo_init<S>(path: S) -> Result<()>
+pub async fn cargo_init<S>(path: S, process_group: &ProcessGroup) -> Result<()>
 where
     S: AsRef<std::ffi::OsStr>,
 {
     let mut cmd = Command::new(cargo_binary());
     cmd.arg("init").arg(path);
-    let (code, out) = cmd_status_output(cmd).await?;
+    let (code, out) = cmd_status_output_in_group(cmd, process_group).await?;
     match code == 0 {
         true => Ok(()),
         false => Err(cargo_error("init", code, out)),
     }
 }
 
-pub async fn cargo_fmt<S>(path: S) -> Result<()>
+pub async fn cargo_fmt<S>(path: S, process_group: &ProcessGroup) -> Result<()>
 where
     S: AsRef<std::ffi::OsStr>,
 {
     let mut cmd = Command::new(cargo_binary());
     cmd.arg("fmt").arg("--manifest-path").arg(path);
-    let (code, out) = cmd_status_output(cmd).await?;
+    let (code, out) = cmd_status_output_in_group(cmd, process_group).await?;
     match code == 0 {
         true => Ok(()),
         false => Err(cargo_error("fmt", code, out)),
@@ -266,7 +529,14 @@ where
 }
 
 // target platform: https://doc.rust-lang.org/cargo/commands/cargo-build.html#compilation-options
-pub async fn cargo_build<P, S>(cargo_workdir: P, target_platform: S, log_path: P) -> Result<()>
+pub async fn cargo_build<P, S>(
+    cargo_workdir: P,
+    target_platform: S,
+    log_path: P,
+    options: &BuildOptions,
+    linker: Option<&str>,
+    process_group: &ProcessGroup,
+) -> Result<()>
 where
     P: AsRef<std::path::Path>,
     S: AsRef<std::ffi::OsStr>,
@@ -275,21 +545,181 @@ where
     let mut cmd = Command::new(cargo_binary());
     cmd.arg("build")
         .arg("--target")
-        .arg(target_platform)
-        .arg("--release");
+        .arg(target_platform.as_ref());
+    cargo_options(&mut cmd, target_platform, options, linker);
     cmd.stderr(log_file);
-    // change cwd as cargo workdir
-    let current_dir = std::env::current_dir()?;
-    std::env::set_current_dir(cargo_workdir)?;
-    let code = cmd_status(cmd).await?;
-    // restore previous workdir
-    std::env::set_current_dir(current_dir)?;
+    // isolate concurrent builds, never change cwd of builder process
+    let cargo_workdir = cargo_workdir.as_ref();
+    cmd.current_dir(cargo_workdir)
+        .env("CARGO_TARGET_DIR", cargo_workdir.join("target"));
+    let code = cmd_status_in_group(cmd, process_group).await?;
     match code == 0 {
         true => Ok(()),
         false => Err(cargo_error("build", code, String::from("check build log"))),
     }
 }
 
+// run tests of built app with same build options, append output to build log
+pub async fn cargo_test<P, S>(
+    cargo_workdir: P,
+    target_platform: S,
+    log_path: P,
+    options: &BuildOptions,
+    linker: Option<&str>,
+    process_group: &ProcessGroup,
+) -> Result<()>
+where
+    P: AsRef<std::path::Path>,
+    S: AsRef<std::ffi::OsStr>,
+{
+    let log_file = append_file(log_path).await?.into_std().await;
+    let mut cmd = Command::new(cargo_binary());
+    cmd.arg("test")
+        .arg("--target")
+        .arg(target_platform.as_ref());
+    cargo_options(&mut cmd, target_platform, options, linker);
+    cmd.stdout(log_file.try_clone()?).stderr(log_file);
+    let cargo_workdir = cargo_workdir.as_ref();
+    cmd.current_dir(cargo_workdir)
+        .env("CARGO_TARGET_DIR", cargo_workdir.join("target"));
+    let code = cmd_status_in_group(cmd, process_group).await?;
+    match code == 0 {
+        true => Ok(()),
+        false => Err(cargo_error("test", code, String::from("check build log"))),
+    }
+}
+
+// check app with clippy lints, append output to build log
+pub async fn cargo_clippy<P, S>(
+    cargo_workdir: P,
+    target_platform: S,
+    log_path: P,
+    options: &BuildOptions,
+    linker: Option<&str>,
+    lints: &[String],
+    process_group: &ProcessGroup,
+) -> Result<()>
+where
+    P: AsRef<std::path::Path>,
+    S: AsRef<std::ffi::OsStr>,
+{
+    let log_file = append_file(log_path).await?.into_std().await;
+    let mut cmd = Command::new(cargo_binary());
+    cmd.arg("clippy")
+        .arg("--target")
+        .arg(target_platform.as_ref());
+    cargo_options(&mut cmd, target_platform, options, linker);
+    cmd.arg("--").args(lints);
+    cmd.stderr(log_file);
+    let cargo_workdir = cargo_workdir.as_ref();
+    cmd.current_dir(cargo_workdir)
+        .env("CARGO_TARGET_DIR", cargo_workdir.join("target"));
+    let code = cmd_status_in_group(cmd, process_group).await?;
+    match code == 0 {
+        true => Ok(()),
+        false => Err(cargo_error("clippy", code, String::from("check build log"))),
+    }
+}
+
+// run app binary in workdir for duration, fail if app exit with error before
+pub async fn smoke_run<P>(
+    binary_path: P,
+    workdir: P,
+    log_path: P,
+    duration: Duration,
+    process_group: &ProcessGroup,
+) -> Result<()>
+where
+    P: AsRef<std::path::Path>,
+{
+    let log_file = append_file(log_path).await?.into_std().await;
+    let mut cmd = Command::new(binary_path.as_ref());
+    cmd.stdout(log_file.try_clone()?).stderr(log_file);
+    cmd.current_dir(workdir);
+    // app still running when duration elapsed, killed by group guard on drop
+    let code = match tokio::time::timeout(duration, cmd_status_in_group(cmd, process_group)).await {
+        Ok(code) => code?,
+        Err(_) => {
+            // wait app exit, so that workspace no longer written once published
+            process_group.kill().await;
+            return Ok(());
+        }
+    };
+    match code == 0 {
+        true => Ok(()),
+        false => Err(build_error(
+            String::from("smoke run"),
+            format!("app exit with code {}, check build log", code),
+        )),
+    }
+}
+
+// cargo profile, features and environment of build options
+fn cargo_options<S>(
+    cmd: &mut Command,
+    target_platform: S,
+    options: &BuildOptions,
+    linker: Option<&str>,
+) where
+    S: AsRef<std::ffi::OsStr>,
+{
+    match options.profile.as_ref() {
+        Some(profile) => cmd.arg("--profile").arg(profile),
+        None => cmd.arg("--release"),
+    };
+    if !options.features.is_empty() {
+        cmd.arg("--features").arg(options.features.join(","));
+    }
+    if options.is_locked() {
+        cmd.arg("--locked");
+    }
+    cmd.envs(&options.env);
+    if let Some(rustflags) = options.rustflags.as_ref() {
+        cmd.env("RUSTFLAGS", rustflags);
+    }
+    if let Some(linker) = linker {
+        cmd.env(cargo_target_linker_env(target_platform), linker);
+    }
+}
+
+// CARGO_TARGET_<TRIPLE>_LINKER, triple in uppercase with '-' replaced by '_'
+fn cargo_target_linker_env<S>(target_platform: S) -> String
+where
+    S: AsRef<std::ffi::OsStr>,
+{
+    let triple = target_platform
+        .as_ref()
+        .to_string_lossy()
+        .to_uppercase()
+        .replace('-', "_");
+    format!("CARGO_TARGET_{}_LINKER", triple)
+}
+
+// free disk space in bytes of file system containing path
+pub fn free_disk_space<P>(path: P) -> Result<u64>
+where
+    P: AsRef<Path>,
+{
+    let path = CString::new(path.as_ref().as_os_str().as_bytes()).map_err(std::io::Error::from)?;
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statvfs(path.as_ptr(), &mut stat) } != 0 {
+        return Err(std::io::Error::last_os_error().into());
+    }
+    // field types vary across platforms
+    #[allow(clippy::unnecessary_cast)]
+    Ok(stat.f_bavail as u64 * stat.f_frsize as u64)
+}
+
+// one minute load average per cpu
+pub fn cpu_load() -> Result<f64> {
+    let mut loadavg = [0f64; 3];
+    if unsafe { libc::getloadavg(loadavg.as_mut_ptr(), 3) } < 1 {
+        return Err(std::io::Error::other("load average unavailable").into());
+    }
+    let cpus = std::thread::available_parallelism()?.get();
+    Ok(loadavg[0] / cpus as f64)
+}
+
 // etcd ops
 pub fn log_event(event: &Event) -> Result<()> {
     if let Some(kv) = event.kv() {
@@ -320,25 +750,14 @@ where
 }
 
 // hash
-fn fnv1a<T>(t: &T) -> u64
-where
-    T: Hash,
-{
-    let mut hasher = FnvHasher::default();
-    t.hash(&mut hasher);
-    hasher.finish()
-}
-
-pub fn hash_distance<T>(t0: &T, t1: &T) -> u64
-where
-    T: Hash,
-{
-    let h0 = fnv1a(t0);
-    let h1 = fnv1a(t1);
-    if h0 > h1 {
-        return h0 - h1;
-    }
-    h1 - h0
+// sha256 digest in lowercase hex
+pub fn sha256_digest(buffer: &[u8]) -> String {
+    let digest = ring::digest::digest(&ring::digest::SHA256, buffer);
+    digest
+        .as_ref()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
 }
 
 // App cargo.toml
@@ -447,7 +866,12 @@ pub fn datetime_utc_to_prost_timestamp(datetime: DateTime<Utc>) -> prost_types::
 #[cfg(test)]
 mod tests {
 
-    use crate::{copy_directory, read_file, remove_directory, Result};
+    use super::{cmd_status_in_group, PROCESS_GROUP_EXIT_CHECK_PERIOD};
+    use crate::{
+        cargo_build, cargo_init, copy_directory, host_target_platform, read_file, remove_directory,
+        sha256_digest, BuildOptions, ProcessGroup, Result,
+    };
+    use tokio::process::Command;
 
     #[tokio::test]
     async fn test_copy_directory() -> Result<()> {
@@ -464,4 +888,135 @@ mod tests {
         assert_eq!("hello", actual.as_str());
         remove_directory("resources/utils/files/to/app").await
     }
+
+    fn test_target_platform() -> String {
+        host_target_platform(&std::env::consts::ARCH.into(), &std::env::consts::OS.into())
+            .expect("host target platform not supported")
+    }
+
+    async fn init_and_build(workdir: std::path::PathBuf) -> Result<()> {
+        let _ = remove_directory(workdir.as_path()).await;
+        cargo_init(workdir.as_path(), &ProcessGroup::default()).await?;
+        let log_path = workdir.join("build.log");
+        cargo_build(
+            workdir.as_path(),
+            test_target_platform().as_str(),
+            log_path.as_path(),
+            &BuildOptions::default(),
+            None,
+            &ProcessGroup::default(),
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_cargo_build_concurrent() -> Result<()> {
+        let current_dir = std::env::current_dir()?;
+        let directory = std::env::temp_dir().join("pipebuilder_test_cargo_build_concurrent");
+        let app0 = directory.join("app0");
+        let app1 = directory.join("app1");
+        let (r0, r1) = tokio::join!(init_and_build(app0.clone()), init_and_build(app1.clone()));
+        r0?;
+        r1?;
+        // each build write into its own target directory
+        let target_platform = test_target_platform();
+        for (app, name) in [(&app0, "app0"), (&app1, "app1")] {
+            let binary = app
+                .join("target")
+                .join(target_platform.as_str())
+                .join("release")
+                .join(name);
+            assert!(binary.is_file(), "binary '{}' not found", binary.display());
+        }
+        assert_eq!(current_dir, std::env::current_dir()?);
+        remove_directory(directory).await
+    }
+
+    // sh with a background child, both in the spawned process group
+    fn process_tree_cmd() -> Command {
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c").arg("sleep 60 & sleep 60");
+        cmd
+    }
+
+    async fn spawned_pgid(group: &ProcessGroup) -> i32 {
+        loop {
+            if let Some(pgid) = group.state.lock().expect("lock process group").pgid {
+                return pgid;
+            }
+            tokio::time::sleep(PROCESS_GROUP_EXIT_CHECK_PERIOD).await;
+        }
+    }
+
+    // errno of signal 0 sent to process group, none if any process alive
+    fn killpg_errno(pgid: i32) -> Option<i32> {
+        // SAFETY: signal 0 only checks existence of processes in group
+        match unsafe { libc::killpg(pgid, 0) } {
+            0 => None,
+            _ => std::io::Error::last_os_error().raw_os_error(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_process_group_cancel() {
+        let group = ProcessGroup::default();
+        let task = {
+            let group = group.clone();
+            tokio::spawn(async move { cmd_status_in_group(process_tree_cmd(), &group).await })
+        };
+        let pgid = spawned_pgid(&group).await;
+        assert_eq!(None, killpg_errno(pgid));
+        group.cancel().await;
+        assert_eq!(Some(libc::ESRCH), killpg_errno(pgid));
+        // cancelled command never complete, aborted by canceller
+        assert!(!task.is_finished());
+        task.abort();
+    }
+
+    #[tokio::test]
+    async fn test_process_group_drop_guard() {
+        let group = ProcessGroup::default();
+        let task = {
+            let group = group.clone();
+            tokio::spawn(async move { cmd_status_in_group(process_tree_cmd(), &group).await })
+        };
+        let pgid = spawned_pgid(&group).await;
+        assert_eq!(None, killpg_errno(pgid));
+        // aborted without cancel, e.g build timeout
+        task.abort();
+        assert!(task.await.unwrap_err().is_cancelled());
+        // wait group killed on drop exit
+        group.kill().await;
+        assert_eq!(Some(libc::ESRCH), killpg_errno(pgid));
+        assert!(!group.is_cancelled());
+    }
+
+    #[tokio::test]
+    async fn test_process_group_spawn_forget_killed() {
+        let group = ProcessGroup::default();
+        let task = {
+            let group = group.clone();
+            tokio::spawn(async move { cmd_status_in_group(process_tree_cmd(), &group).await })
+        };
+        spawned_pgid(&group).await;
+        task.abort();
+        assert!(task.await.unwrap_err().is_cancelled());
+        assert!(group.state.lock().unwrap().killed.is_some());
+        // next child never kill the stale group
+        assert_eq!(
+            0,
+            cmd_status_in_group(Command::new("true"), &group)
+                .await
+                .unwrap()
+        );
+        assert!(group.state.lock().unwrap().killed.is_none());
+    }
+
+    #[test]
+    fn test_sha256_digest() {
+        assert_eq!(
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+            sha256_digest(&[])
+        );
+    }
 }
@@ -54,6 +54,10 @@ mod handlers {
             .push(PATH_APP)
             .build();
         let buffer = read_file(path.as_path()).await?;
-        Ok(models::GetAppResponse { buffer })
+        Ok(models::GetAppResponse {
+            buffer,
+            digest: None,
+            compression: None,
+        })
     }
 }